@@ -0,0 +1,47 @@
+// Spatial grid benchmarks: dense cell array versus a hash map keyed by cell
+
+use std::collections::HashMap;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use predator_prey_sim::simulation::spatial::{cell_index, SpatialGrid};
+use predator_prey_sim::utils::math::Vector2;
+use rand::{Rng, SeedableRng};
+
+const WIDTH: f64 = 2000.0;
+const HEIGHT: f64 = 2000.0;
+const CELL_SIZE: f64 = 50.0;
+
+fn random_positions(count: usize) -> Vec<Vector2> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    (0..count)
+        .map(|_| Vector2::new(rng.gen_range(0.0..WIDTH), rng.gen_range(0.0..HEIGHT)))
+        .collect()
+}
+
+fn build_hash_grid(positions: &[Vector2]) -> HashMap<(usize, usize), Vec<usize>> {
+    let cols = (WIDTH / CELL_SIZE) as usize;
+    let rows = (HEIGHT / CELL_SIZE) as usize;
+    let mut cells: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (index, pos) in positions.iter().enumerate() {
+        cells.entry(cell_index(*pos, cols, rows, WIDTH, HEIGHT)).or_default().push(index);
+    }
+    cells
+}
+
+fn grid_build(c: &mut Criterion) {
+    let positions = random_positions(20_000);
+    let mut group = c.benchmark_group("grid_build_20k");
+
+    group.bench_function("dense", |b| {
+        let mut grid = SpatialGrid::default();
+        b.iter(|| grid.rebuild(black_box(&positions).iter().copied(), WIDTH, HEIGHT, CELL_SIZE))
+    });
+    group.bench_function("hash_map", |b| {
+        b.iter(|| build_hash_grid(black_box(&positions)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, grid_build);
+criterion_main!(benches);
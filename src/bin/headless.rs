@@ -0,0 +1,241 @@
+// Headless runner: no window, no egui. Prints population counts every tick.
+//
+// Usage: headless [PARAMS.toml] [TICKS] [--stress-test] [--jsonl FILE] [--frames-dir DIR] [--serve PORT [--bind ADDR]]
+//
+//   --stress-test  fill the world to max_agents, time TICKS updates (300 by
+//                  default) and print the tick rate instead of counts
+//   --jsonl FILE   write every agent's state per tick to FILE as JSON Lines
+//                  instead of printing, stopping early on extinction
+//   --frames-dir DIR  also write a PNG per tick into DIR, stopping early on
+//                  extinction (needs the `gui` feature)
+//   --serve PORT   stream every tick to TCP clients on PORT at the configured
+//                  tick rate instead of printing (needs the `net` feature)
+//   --bind ADDR    address --serve listens on (default 127.0.0.1, local
+//                  clients only; e.g. 0.0.0.0 for remote clients)
+//
+// --stress-test, --jsonl, --frames-dir and --serve are separate modes; pick one.
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::runner::{run_headless_to_jsonl, stress_test, Simulation};
+use predator_prey_sim::simulation::world::World;
+
+/// Ticks to run when none are given on the command line
+const DEFAULT_TICKS: u64 = 1000;
+
+/// Timed updates in a stress test when no tick count is given
+const STRESS_TEST_TICKS: u64 = 300;
+
+const USAGE: &str = "Usage: headless [PARAMS.toml] [TICKS] [--stress-test] [--jsonl FILE] [--frames-dir DIR] [--serve PORT [--bind ADDR]]";
+
+/// Parsed command line
+#[derive(Debug, Default)]
+struct Options {
+    params_path: Option<String>,
+    ticks: Option<u64>,
+    /// Time updates of a world filled to `max_agents` instead of printing counts
+    stress_test: bool,
+    /// File to write per-tick agent state to as JSON Lines (replaced if present)
+    jsonl_path: Option<String>,
+    /// Directory to dump a PNG per tick into
+    #[cfg(feature = "gui")]
+    frames_dir: Option<String>,
+    /// Port to stream state on instead of printing counts
+    #[cfg(feature = "net")]
+    serve_port: Option<u16>,
+    /// Address to listen on; only local clients unless set
+    #[cfg(feature = "net")]
+    bind_addr: Option<std::net::IpAddr>,
+}
+
+/// Parse the arguments after the program name
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--stress-test" => options.stress_test = true,
+            "--jsonl" => options.jsonl_path = Some(args.next().ok_or("--jsonl needs a file")?),
+            "--frames-dir" => {
+                let dir = args.next().ok_or("--frames-dir needs a directory")?;
+                #[cfg(feature = "gui")]
+                {
+                    options.frames_dir = Some(dir);
+                }
+                #[cfg(not(feature = "gui"))]
+                {
+                    let _ = dir;
+                    return Err("--frames-dir needs the `gui` feature".to_string());
+                }
+            }
+            "--serve" => {
+                let port = args.next().ok_or("--serve needs a port")?;
+                let port = port.parse::<u16>().map_err(|_| format!("Invalid port: {}", port))?;
+                #[cfg(feature = "net")]
+                {
+                    options.serve_port = Some(port);
+                }
+                #[cfg(not(feature = "net"))]
+                {
+                    let _ = port;
+                    return Err("--serve needs the `net` feature".to_string());
+                }
+            }
+            "--bind" => {
+                let addr = args.next().ok_or("--bind needs an address")?;
+                let addr = addr.parse::<std::net::IpAddr>().map_err(|_| format!("Invalid address: {}", addr))?;
+                #[cfg(feature = "net")]
+                {
+                    options.bind_addr = Some(addr);
+                }
+                #[cfg(not(feature = "net"))]
+                {
+                    let _ = addr;
+                    return Err("--bind needs the `net` feature".to_string());
+                }
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+            _ if options.params_path.is_none() => options.params_path = Some(arg),
+            _ if options.ticks.is_none() => {
+                let ticks = arg.parse::<u64>().map_err(|_| format!("Invalid tick count: {}", arg))?;
+                options.ticks = Some(ticks);
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+    // Each of these replaces the printed counts, so at most one may be given
+    let modes = [
+        ("--stress-test", options.stress_test),
+        ("--jsonl", options.jsonl_path.is_some()),
+        #[cfg(feature = "gui")]
+        ("--frames-dir", options.frames_dir.is_some()),
+        #[cfg(feature = "net")]
+        ("--serve", options.serve_port.is_some()),
+    ];
+    let chosen: Vec<&str> = modes.iter().filter(|(_, set)| *set).map(|(flag, _)| *flag).collect();
+    if chosen.len() > 1 {
+        return Err(format!("{} cannot be combined", chosen.join(" and ")));
+    }
+    #[cfg(feature = "net")]
+    if options.bind_addr.is_some() && options.serve_port.is_none() {
+        return Err("--bind only applies to --serve".to_string());
+    }
+    Ok(options)
+}
+
+/// Print `message` and the usage line, then exit with an error
+fn fail(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    std::process::exit(1);
+}
+
+fn main() {
+    let options = parse_args(std::env::args().skip(1)).unwrap_or_else(|e| fail(&e));
+    let params = match &options.params_path {
+        Some(path) => match Parameters::load_from_file(path) {
+            Ok(params) => params,
+            Err(e) => {
+                eprintln!("Could not load {}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => Parameters::default(),
+    };
+
+    if options.stress_test {
+        let mut world = World::new(params);
+        println!("{}", stress_test(&mut world, options.ticks.unwrap_or(STRESS_TEST_TICKS)).status());
+        return;
+    }
+
+    #[cfg(feature = "net")]
+    if let Some(port) = options.serve_port {
+        // Streaming runs until extinction unless a tick count is given
+        let addr = options.bind_addr.unwrap_or(std::net::Ipv4Addr::LOCALHOST.into());
+        serve(params, (addr, port).into(), options.ticks.unwrap_or(u64::MAX));
+        return;
+    }
+
+    let ticks = options.ticks.unwrap_or(DEFAULT_TICKS);
+    if let Some(path) = &options.jsonl_path {
+        let mut world = World::new(params);
+        match run_headless_to_jsonl(&mut world, ticks, path) {
+            Ok(outcome) => println!("{}", outcome.status()),
+            Err(e) => {
+                eprintln!("Could not write {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    #[cfg(feature = "gui")]
+    if let Some(dir) = &options.frames_dir {
+        dump_frames(params, ticks, dir);
+        return;
+    }
+
+    let mut simulation = Simulation::new(params);
+    println!("tick,predators,prey");
+    for _ in 0..ticks {
+        simulation.run_for(1);
+        let world = simulation.world();
+        println!("{},{},{}", world.tick(), world.predator_count(), world.prey_count());
+    }
+}
+
+/// Print counts and write a PNG frame per tick into `dir`
+#[cfg(feature = "gui")]
+fn dump_frames(params: Parameters, ticks: u64, dir: &str) {
+    use predator_prey_sim::simulation::runner::run_headless_with;
+    use predator_prey_sim::ui::frames::FrameDumper;
+    use predator_prey_sim::ui::visualization::VisualizationSettings;
+
+    // One pixel per world unit
+    let (width, height) = (params.world.width.round().max(1.0) as u32, params.world.height.round().max(1.0) as u32);
+    let dumper = match FrameDumper::new(dir, width, height) {
+        Ok(dumper) => dumper,
+        Err(e) => {
+            eprintln!("Could not create {}: {}", dir, e);
+            std::process::exit(1);
+        }
+    };
+    let settings = VisualizationSettings::default();
+    let mut world = World::new(params);
+    let mut result = Ok(());
+    println!("tick,predators,prey");
+    let outcome = run_headless_with(&mut world, ticks, |world| {
+        println!("{},{},{}", world.tick(), world.predator_count(), world.prey_count());
+        if result.is_ok() {
+            result = dumper.dump(world, &settings).map(|_| ());
+        }
+    });
+    if let Err(e) = result {
+        eprintln!("Could not write frames to {}: {}", dir, e);
+        std::process::exit(1);
+    }
+    eprintln!("{}", outcome.status());
+}
+
+/// Run at the configured tick rate, streaming every tick to TCP clients on `addr`
+#[cfg(feature = "net")]
+fn serve(params: Parameters, addr: std::net::SocketAddr, ticks: u64) {
+    use predator_prey_sim::simulation::runner::run_headless_serving;
+    use predator_prey_sim::utils::net::StateServer;
+
+    let pace = std::time::Duration::from_secs_f64(params.simulation.dt);
+    let mut world = World::new(params);
+    let mut server = match StateServer::bind(addr) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Could not listen on {}: {}", addr, e);
+            std::process::exit(1);
+        }
+    };
+    println!("Streaming state on {}", addr);
+    match run_headless_serving(&mut world, ticks, &mut server, pace) {
+        Ok(outcome) => println!("{}", outcome.status()),
+        Err(e) => {
+            eprintln!("Streaming failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,9 +1,12 @@
 // Simulation parameters struct
 
 use serde::{Deserialize, Serialize};
+use crate::utils::math::{Vector2, fnv1a_64};
+use crate::utils::rng::RngAlgorithm;
 
 /// Predator-specific parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PredatorParameters {
     pub initial_energy: f64,
     pub max_speed: f64,
@@ -13,7 +16,37 @@ pub struct PredatorParameters {
     pub energy_gain_from_prey: f64,
     pub reproduction_threshold: f64,
     pub reproduction_cost: f64,
+    /// Energy a newborn starts with (None = `initial_energy`)
+    pub offspring_energy: Option<f64>,
     pub initial_count: u32,
+    /// Fractional perception change per capture / per unsuccessful stretch (0 = off)
+    pub learning_rate: f64,
+    /// Lower bound for a learned perception radius
+    pub min_perception: f64,
+    /// Upper bound for a learned perception radius
+    pub max_perception: f64,
+    /// Whether predators without visible prey head toward recent prey alarm calls
+    pub alarm_attracts_predators: bool,
+    /// Distance at which predators hear alarm calls
+    pub alarm_radius: f64,
+    /// Whether sated predators prefer higher-energy prey over merely closer prey
+    pub prefer_high_energy_prey: bool,
+    /// How predators steer toward their chosen prey
+    pub pursuit: PursuitMode,
+    /// Only capture prey that aren't heading toward the predator (it must
+    /// approach from outside the prey's forward arc)
+    pub rear_attack_only: bool,
+    /// Dilution effect: a capture succeeds with probability 1 / (1 + factor * n),
+    /// n = other prey within the target's detection radius (0 = always succeeds)
+    pub dilution_factor: f64,
+    /// How surplus energy turns into offspring
+    pub reproduction_strategy: ReproductionStrategy,
+    /// Energy kept when budding; everything above it feeds reproduction progress
+    pub budding_threshold: f64,
+    /// Age in ticks at which a predator dies however well fed (None = immortal)
+    pub max_age: Option<u32>,
+    /// Speed (fraction of max speed) of the wander when idle (0 = slow to a stop)
+    pub wander_strength: f64,
 }
 
 impl Default for PredatorParameters {
@@ -27,13 +60,56 @@ impl Default for PredatorParameters {
             energy_gain_from_prey: 50.0,
             reproduction_threshold: 150.0,
             reproduction_cost: 80.0,
+            offspring_energy: None,
             initial_count: 10,
+            learning_rate: 0.0,
+            min_perception: 20.0,
+            max_perception: 150.0,
+            alarm_attracts_predators: false,
+            alarm_radius: 150.0,
+            prefer_high_energy_prey: false,
+            pursuit: PursuitMode::DirectSeek,
+            rear_attack_only: false,
+            dilution_factor: 0.0,
+            reproduction_strategy: ReproductionStrategy::Threshold,
+            budding_threshold: 100.0,
+            max_age: None,
+            wander_strength: 0.0,
         }
     }
 }
 
+impl PredatorParameters {
+    /// Energy a newborn predator starts with
+    pub fn newborn_energy(&self) -> f64 {
+        self.offspring_energy.unwrap_or(self.initial_energy)
+    }
+}
+
+/// Predator steering strategy when chasing prey
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum PursuitMode {
+    /// Head straight for the prey's current position
+    #[default]
+    DirectSeek,
+    /// Lead the prey: head for where it will be when the predator arrives
+    Intercept,
+}
+
+/// How agents reproduce
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ReproductionStrategy {
+    /// Split once energy reaches `reproduction_threshold`, paying `reproduction_cost`
+    #[default]
+    Threshold,
+    /// Continuously convert energy above `budding_threshold` into progress
+    /// (`reproduction_cost` per offspring), spawning a child at progress 1.0
+    Budding,
+}
+
 /// Prey-specific parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PreyParameters {
     pub initial_energy: f64,
     pub max_speed: f64,
@@ -43,7 +119,95 @@ pub struct PreyParameters {
     pub energy_loss_fleeing: f64,
     pub reproduction_threshold: f64,
     pub reproduction_cost: f64,
+    /// Energy a newborn starts with (None = `initial_energy`)
+    pub offspring_energy: Option<f64>,
     pub initial_count: u32,
+    /// Number of nearby prey at which regeneration drops to zero (0 = off)
+    pub density_cap: f64,
+    /// Ticks a prey keeps fleeing from a threat it can no longer see (0 = off)
+    pub memory_ticks: u32,
+    /// Whether prey cornered against a wall dodge along it instead of pressing into it
+    pub dodge_along_walls: bool,
+    /// Flee from all visible predators at once, each weighted by proximity and
+    /// scaled up by this factor times its approach speed (0 = flee the nearest only)
+    pub threat_speed_weight: f64,
+    /// Whether the SIR disease spreads among prey
+    pub infection_enabled: bool,
+    /// Distance within which an infected prey can infect a susceptible one
+    pub infection_radius: f64,
+    /// Per-tick probability that a susceptible prey near an infected one catches it
+    pub infection_rate: f64,
+    /// Ticks an infected prey stays infected before recovering
+    pub infection_duration: u32,
+    /// Extra energy lost per second while infected
+    pub infection_energy_loss: f64,
+    /// Prey may only reproduce within this many ticks of eating food (0 = off)
+    pub well_fed_window: u32,
+    /// Prey may only reproduce once at least this many ticks old (0 = from birth)
+    pub maturity_age: u32,
+    /// Distance of an instant startle dash away from a close predator (0 = off)
+    pub dash_distance: f64,
+    /// A predator within this distance triggers a dash
+    pub dash_trigger_range: f64,
+    /// Ticks after a dash before the prey can dash again
+    pub dash_cooldown: u32,
+    /// Pull (fraction of max speed) back toward the remembered safe spot (0 = off)
+    pub home_range_weight: f64,
+    /// Calm prey below this energy return to their safe spot; above it they adopt
+    /// their current position as the new one
+    pub comfort_energy: f64,
+    /// How surplus energy turns into offspring
+    pub reproduction_strategy: ReproductionStrategy,
+    /// Energy kept when budding; everything above it feeds reproduction progress
+    pub budding_threshold: f64,
+    /// Boids steering among calm prey (fractions of max speed, 0 = off):
+    /// toward the local center, matching neighbors' velocity, and apart
+    pub cohesion_weight: f64,
+    pub alignment_weight: f64,
+    pub separation_weight: f64,
+    /// Neighbors closer than this push each other apart
+    pub separation_distance: f64,
+    /// Cohesion multiplier by energy fraction, so hungry prey graze loosely
+    /// and well-fed prey herd tightly
+    pub cohesion_energy_curve: EnergyCurve,
+    /// How hard prey steer down a predator scent gradient (fraction of max
+    /// speed; only with scent trails enabled)
+    pub scent_flee_weight: f64,
+    /// Resource competition: each breeding attempt succeeds with probability
+    /// `1 - crowding_suppression * min(nearby prey / crowding_cap, 1)` (0 = off)
+    pub crowding_suppression: f64,
+    /// Visible prey at which crowding suppression takes full effect
+    pub crowding_cap: u32,
+    /// Age in ticks at which a prey dies however well fed (None = immortal)
+    pub max_age: Option<u32>,
+    /// Speed (fraction of max speed) of the wander when idle (0 = slow to a stop)
+    pub wander_strength: f64,
+}
+
+/// Multiplier as a function of energy fraction f (energy relative to the
+/// reproduction threshold, clamped to 0..1): `hungry + (fed - hungry) * f^exponent`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnergyCurve {
+    pub hungry: f64,
+    pub fed: f64,
+    pub exponent: f64,
+}
+
+impl Default for EnergyCurve {
+    fn default() -> Self {
+        Self {
+            hungry: 1.0,
+            fed: 1.0,
+            exponent: 1.0,
+        }
+    }
+}
+
+impl EnergyCurve {
+    /// Multiplier at energy fraction `fraction`
+    pub fn at(&self, fraction: f64) -> f64 {
+        self.hungry + (self.fed - self.hungry) * fraction.clamp(0.0, 1.0).powf(self.exponent)
+    }
 }
 
 impl Default for PreyParameters {
@@ -57,20 +221,291 @@ impl Default for PreyParameters {
             energy_loss_fleeing: 0.2,
             reproduction_threshold: 120.0,
             reproduction_cost: 60.0,
+            offspring_energy: None,
             initial_count: 50,
+            density_cap: 0.0,
+            memory_ticks: 0,
+            dodge_along_walls: false,
+            threat_speed_weight: 0.0,
+            infection_enabled: false,
+            infection_radius: 15.0,
+            infection_rate: 0.05,
+            infection_duration: 300,
+            infection_energy_loss: 0.5,
+            well_fed_window: 0,
+            maturity_age: 0,
+            dash_distance: 0.0,
+            dash_trigger_range: 10.0,
+            dash_cooldown: 120,
+            home_range_weight: 0.0,
+            comfort_energy: 100.0,
+            reproduction_strategy: ReproductionStrategy::Threshold,
+            budding_threshold: 80.0,
+            cohesion_weight: 0.0,
+            alignment_weight: 0.0,
+            separation_weight: 0.0,
+            separation_distance: 10.0,
+            cohesion_energy_curve: EnergyCurve::default(),
+            scent_flee_weight: 0.5,
+            crowding_suppression: 0.0,
+            crowding_cap: 10,
+            max_age: None,
+            wander_strength: 0.0,
         }
     }
 }
 
+impl PreyParameters {
+    /// Energy a newborn prey starts with
+    pub fn newborn_energy(&self) -> f64 {
+        self.offspring_energy.unwrap_or(self.initial_energy)
+    }
+}
+
 /// World/environment parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WorldParameters {
     pub width: f64,
     pub height: f64,
     pub boundary_type: BoundaryType,
+    /// Expected food items spawned per tick per 10,000 square units of world
     pub food_spawn_rate: f64,
+    /// Energy a prey gains from eating one food item
     pub food_energy: f64,
     pub enable_food: bool,
+    /// Regions agents may not occupy
+    #[serde(default)]
+    pub obstacles: Vec<Obstacle>,
+    /// Minimum distance between a newly spawned agent and existing ones (0 = off)
+    #[serde(default)]
+    pub min_spawn_separation: f64,
+    /// Randomly placed agents keep at least this distance from every edge
+    #[serde(default = "default_spawn_margin")]
+    pub spawn_margin: f64,
+    /// Regions where time runs faster or slower for agents inside them
+    #[serde(default)]
+    pub time_zones: Vec<TimeZone>,
+    /// How the initial agents are placed
+    #[serde(default)]
+    pub spawn_pattern: SpawnPattern,
+    /// Procedural obstacles added (on top of `obstacles`) when a world is created
+    #[serde(default)]
+    pub obstacle_generator: Option<ObstacleGenerator>,
+    /// Left-to-right temperature gradient that slows agents in the cold
+    #[serde(default)]
+    pub temperature_gradient: Option<TemperatureGradient>,
+    /// Decaying predator scent that prey can smell without seeing the predator
+    #[serde(default)]
+    pub scent_trails: Option<ScentTrails>,
+}
+
+fn default_spawn_margin() -> f64 {
+    5.0
+}
+
+impl WorldParameters {
+    /// Whether a position lies outside every obstacle
+    pub fn is_free(&self, pos: Vector2) -> bool {
+        !self.obstacles.iter().any(|o| o.contains(pos))
+    }
+
+    /// Time step multiplier at a position (the first containing zone wins, 1 outside all zones)
+    pub fn dt_scale_at(&self, pos: Vector2) -> f64 {
+        self.time_zones
+            .iter()
+            .find(|z| z.contains(pos))
+            .map_or(1.0, |z| z.dt_scale)
+    }
+
+    /// Max speed multiplier at a position (1 without a temperature gradient)
+    pub fn speed_scale_at(&self, pos: Vector2) -> f64 {
+        self.temperature_gradient
+            .map_or(1.0, |g| g.speed_scale(g.temperature_at(pos, self.width)))
+    }
+}
+
+/// Temperature varying linearly from the left edge (x = 0) to the right edge (x = width)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TemperatureGradient {
+    pub left: f64,
+    pub right: f64,
+    /// At or below this temperature agents reach only `min_speed_factor` of their max speed
+    pub cold: f64,
+    /// At or above this temperature agents reach their full max speed
+    pub warm: f64,
+    pub min_speed_factor: f64,
+}
+
+impl Default for TemperatureGradient {
+    fn default() -> Self {
+        Self {
+            left: 0.0,
+            right: 30.0,
+            cold: 0.0,
+            warm: 25.0,
+            min_speed_factor: 0.3,
+        }
+    }
+}
+
+impl TemperatureGradient {
+    /// Temperature at a position in a world `width` wide
+    pub fn temperature_at(&self, pos: Vector2, width: f64) -> f64 {
+        let t = if width > 0.0 { (pos.x / width).clamp(0.0, 1.0) } else { 0.0 };
+        self.left + (self.right - self.left) * t
+    }
+
+    /// How warm `temperature` is, from 0 (cold or colder) to 1 (warm or warmer)
+    pub fn warmth(&self, temperature: f64) -> f64 {
+        if self.warm <= self.cold {
+            return if temperature >= self.warm { 1.0 } else { 0.0 };
+        }
+        ((temperature - self.cold) / (self.warm - self.cold)).clamp(0.0, 1.0)
+    }
+
+    /// Max speed multiplier at `temperature`, from `min_speed_factor` up to 1
+    pub fn speed_scale(&self, temperature: f64) -> f64 {
+        self.min_speed_factor + (1.0 - self.min_speed_factor) * self.warmth(temperature)
+    }
+}
+
+/// Predator scent laid on a coarse grid
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScentTrails {
+    /// Edge length of a scent grid cell
+    pub cell_size: f64,
+    /// Scent a predator adds to its cell each tick
+    pub deposit: f64,
+    /// Fraction of scent left in every cell after each tick
+    pub retention: f64,
+    /// Prey ignore cells fainter than this
+    pub detection_threshold: f64,
+}
+
+impl Default for ScentTrails {
+    fn default() -> Self {
+        Self {
+            cell_size: 20.0,
+            deposit: 1.0,
+            retention: 0.98,
+            detection_threshold: 0.5,
+        }
+    }
+}
+
+/// Axis-aligned rectangular obstacle (top-left corner plus size)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Obstacle {
+    /// Create a new obstacle
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Whether a position lies inside the obstacle
+    pub fn contains(&self, pos: Vector2) -> bool {
+        pos.x >= self.x && pos.x <= self.x + self.width && pos.y >= self.y && pos.y <= self.y + self.height
+    }
+}
+
+/// Axis-aligned rectangular region that scales the time step of agents inside it
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeZone {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Multiplier on `dt` (below 1 = slow motion, above 1 = fast forward)
+    pub dt_scale: f64,
+}
+
+impl TimeZone {
+    /// Create a new time zone
+    pub fn new(x: f64, y: f64, width: f64, height: f64, dt_scale: f64) -> Self {
+        Self { x, y, width, height, dt_scale }
+    }
+
+    /// Whether a position lies inside the zone
+    pub fn contains(&self, pos: Vector2) -> bool {
+        pos.x >= self.x && pos.x <= self.x + self.width && pos.y >= self.y && pos.y <= self.y + self.height
+    }
+}
+
+/// Procedural obstacle layouts, drawn from the world RNG
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ObstacleGenerator {
+    /// `count` square blocks of side `size` scattered uniformly
+    RandomBlocks { count: u32, size: f64 },
+    /// A solid wall of the given thickness along all four edges
+    BorderRing { thickness: f64 },
+    /// Grid of `cell_size` cells where each cell's right and bottom wall is
+    /// present with probability `density`
+    MazeSimple { cell_size: f64, density: f64 },
+}
+
+impl ObstacleGenerator {
+    /// Thickness of maze walls as a fraction of the cell size
+    const MAZE_WALL_FRACTION: f64 = 0.15;
+
+    /// Generate the obstacles for a world of the given size
+    pub fn generate(&self, width: f64, height: f64, rng: &mut impl rand::Rng) -> Vec<Obstacle> {
+        match *self {
+            ObstacleGenerator::RandomBlocks { count, size } => {
+                let size = size.min(width).min(height);
+                (0..count)
+                    .map(|_| {
+                        let x = rng.gen::<f64>() * (width - size);
+                        let y = rng.gen::<f64>() * (height - size);
+                        Obstacle::new(x, y, size, size)
+                    })
+                    .collect()
+            }
+            ObstacleGenerator::BorderRing { thickness } => vec![
+                Obstacle::new(0.0, 0.0, width, thickness),
+                Obstacle::new(0.0, height - thickness, width, thickness),
+                Obstacle::new(0.0, thickness, thickness, height - 2.0 * thickness),
+                Obstacle::new(width - thickness, thickness, thickness, height - 2.0 * thickness),
+            ],
+            ObstacleGenerator::MazeSimple { cell_size, density } => {
+                if cell_size <= 0.0 {
+                    return Vec::new();
+                }
+                let wall = cell_size * Self::MAZE_WALL_FRACTION;
+                let cols = (width / cell_size) as usize;
+                let rows = (height / cell_size) as usize;
+                let mut obstacles = Vec::new();
+                for row in 0..rows {
+                    for col in 0..cols {
+                        let (x, y) = (col as f64 * cell_size, row as f64 * cell_size);
+                        if rng.gen::<f64>() < density {
+                            obstacles.push(Obstacle::new(x + cell_size - wall, y, wall, cell_size));
+                        }
+                        if rng.gen::<f64>() < density {
+                            obstacles.push(Obstacle::new(x, y + cell_size - wall, cell_size, wall));
+                        }
+                    }
+                }
+                obstacles
+            }
+        }
+    }
+}
+
+/// Placement of the initial agents
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum SpawnPattern {
+    /// Uniformly random positions
+    #[default]
+    Random,
+    /// Cell centers of an evenly spaced grid, with no randomness at all.
+    /// Predators fill the first cells and prey the rest, unless interleaved.
+    Lattice { interleave: bool },
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -88,17 +523,103 @@ impl Default for WorldParameters {
             food_spawn_rate: 0.01,
             food_energy: 20.0,
             enable_food: false,
+            obstacles: Vec::new(),
+            min_spawn_separation: 0.0,
+            spawn_margin: default_spawn_margin(),
+            time_zones: Vec::new(),
+            spawn_pattern: SpawnPattern::Random,
+            obstacle_generator: None,
+            temperature_gradient: None,
+            scent_trails: None,
+        }
+    }
+}
+
+/// How agent positions are advanced each tick
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum IntegrationScheme {
+    /// Explicit Euler: position advances with the velocity from the start of the tick
+    Euler,
+    /// Semi-implicit Euler: velocity is updated first, then position uses the new velocity
+    #[default]
+    SemiImplicitEuler,
+}
+
+/// Structure indexing agent positions for neighbor queries
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum SpatialIndexKind {
+    /// Uniform grid with cells sized to the largest perception radius
+    #[default]
+    Grid,
+    /// Quadtree that subdivides where agents crowd; lighter on memory when
+    /// agents clump in a small part of the world
+    QuadTree,
+}
+
+/// Controller that nudges prey energy regeneration up while prey are below
+/// `target_prey` and down while they are above it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoBalance {
+    pub enabled: bool,
+    pub target_prey: u32,
+    /// Fractional change of the regeneration factor per tick at 100% deviation
+    pub gain: f64,
+}
+
+impl Default for AutoBalance {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_prey: 100,
+            gain: 0.01,
         }
     }
 }
 
 /// Simulation control parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SimulationParameters {
     pub tick_rate: f64, // Updates per second
     pub max_agents: u32,
+    /// Per-type population caps for births and spawns (0 = unlimited);
+    /// the stricter of these and `max_agents` applies
+    pub max_predators: u32,
+    pub max_prey: u32,
     pub enable_reproduction: bool,
     pub dt: f64, // Delta time (usually 1.0 / tick_rate)
+    pub integration: IntegrationScheme,
+    /// Place offspring at an offset hashed from (parent id, parent age)
+    /// instead of a random one
+    pub deterministic_offspring: bool,
+    /// Standard deviation of Gaussian noise on perceived neighbor positions (0 = exact)
+    pub sensor_noise: f64,
+    /// Upper bound on the energy a newborn agent starts with (0 = no cap)
+    pub max_offspring_energy: f64,
+    /// Most births honored in one tick (0 = unlimited). Excess births are
+    /// dropped, not queued: the parent has already paid the reproduction cost.
+    pub max_births_per_tick: u32,
+    /// Seed for the world RNG (None = fresh entropy every run)
+    pub seed: Option<u64>,
+    /// On reset, reseed with `seed + run number` instead of `seed` itself, giving
+    /// a new but still reproducible run each time (no effect without a seed)
+    pub advance_seed_on_reset: bool,
+    /// Algorithm of the world RNG; runs only match for the same algorithm and seed
+    pub rng_algorithm: RngAlgorithm,
+    /// Encode species and generation in agent ids instead of a flat sequence
+    pub structured_ids: bool,
+    /// Resolve each tick's actions in a seeded random order instead of
+    /// insertion order (predators are still served before prey)
+    pub shuffle_update_order: bool,
+    /// Homeostat tuning prey regeneration toward a target population
+    pub auto_balance: AutoBalance,
+    /// Index neighbors with a spatial grid; off checks every pair (for benchmarking)
+    pub spatial_index: bool,
+    /// Which structure `spatial_index` uses
+    pub spatial_index_kind: SpatialIndexKind,
+    /// Most agents a quadtree leaf holds before it subdivides
+    pub quadtree_capacity: u32,
 }
 
 impl Default for SimulationParameters {
@@ -106,8 +627,24 @@ impl Default for SimulationParameters {
         Self {
             tick_rate: 60.0,
             max_agents: 1000,
+            max_predators: 0,
+            max_prey: 0,
             enable_reproduction: true,
             dt: 1.0 / 60.0,
+            integration: IntegrationScheme::SemiImplicitEuler,
+            deterministic_offspring: false,
+            sensor_noise: 0.0,
+            max_offspring_energy: 0.0,
+            max_births_per_tick: 0,
+            seed: None,
+            advance_seed_on_reset: false,
+            rng_algorithm: RngAlgorithm::default(),
+            structured_ids: false,
+            shuffle_update_order: false,
+            auto_balance: AutoBalance::default(),
+            spatial_index: true,
+            spatial_index_kind: SpatialIndexKind::Grid,
+            quadtree_capacity: 8,
         }
     }
 }
@@ -119,7 +656,7 @@ impl SimulationParameters {
 }
 
 /// Complete simulation parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameters {
     pub predator: PredatorParameters,
     pub prey: PreyParameters,
@@ -153,6 +690,11 @@ impl Parameters {
             return Err("Initial energy must be positive".to_string());
         }
 
+        let offspring_energies = [self.predator.offspring_energy, self.prey.offspring_energy];
+        if offspring_energies.iter().flatten().any(|energy| *energy <= 0.0) {
+            return Err("Offspring energy must be positive".to_string());
+        }
+
         if self.simulation.tick_rate <= 0.0 {
             return Err("Tick rate must be positive".to_string());
         }
@@ -160,18 +702,195 @@ impl Parameters {
         Ok(())
     }
 
+    /// Valid but risky combinations that are likely to destabilize a run
+    pub fn warnings(&self) -> Vec<ParameterWarning> {
+        let dt = self.simulation.dt;
+        let mut warnings = Vec::new();
+
+        let predator_step = self.predator.max_speed * dt;
+        if predator_step > self.predator.capture_distance {
+            warnings.push(ParameterWarning::PredatorTunneling {
+                step: predator_step,
+                capture_distance: self.predator.capture_distance,
+            });
+        }
+
+        let prey_step = self.prey.max_speed * dt;
+        if prey_step > self.predator.perception_radius {
+            warnings.push(ParameterWarning::PreyOutrunsPerception {
+                step: prey_step,
+                perception_radius: self.predator.perception_radius,
+            });
+        }
+
+        warnings
+    }
+
+    /// Apply the suggested fix for every warning; returns whether anything changed
+    pub fn clamp_unstable(&mut self) -> bool {
+        let warnings = self.warnings();
+        for warning in &warnings {
+            warning.apply_fix(self);
+        }
+        !warnings.is_empty()
+    }
+
     /// Save parameters to a TOML file
-    pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let toml_string = toml::to_string_pretty(self)?;
-        std::fs::write(path, toml_string)?;
+    pub fn save_to_file(&self, path: &str) -> Result<(), ParametersError> {
+        let toml_string = toml::to_string_pretty(self).map_err(ParametersError::Serialize)?;
+        std::fs::write(path, toml_string).map_err(ParametersError::Io)?;
         Ok(())
     }
 
+    /// Short stable identifier for this configuration: the first 8 hex digits
+    /// of a 64-bit FNV-1a digest over the canonical TOML serialization
+    pub fn config_hash(&self) -> String {
+        let canonical = toml::to_string(self).expect("parameters always serialize to TOML");
+        format!("{:016x}", fnv1a_64(canonical.as_bytes()))[..8].to_string()
+    }
+
+    /// Describe every parameter for external frontends: a JSON array with one
+    /// object per field (dotted `name`, JSON `type`, `default` value) plus the
+    /// control panel's `min`/`max` where the field has a slider
+    pub fn schema_json() -> String {
+        let defaults = serde_json::to_value(Parameters::default()).unwrap_or_default();
+        let ranges = crate::config::ranges::slider_ranges();
+        let mut fields = Vec::new();
+        schema_fields("", &defaults, &ranges, &mut fields);
+        serde_json::to_string_pretty(&fields).unwrap_or_default()
+    }
+
     /// Load parameters from a TOML file
-    pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let content = std::fs::read_to_string(path)?;
-        let params: Parameters = toml::from_str(&content)?;
-        params.validate()?;
+    pub fn load_from_file(path: &str) -> Result<Self, ParametersError> {
+        let content = std::fs::read_to_string(path).map_err(ParametersError::Io)?;
+        let params: Parameters = toml::from_str(&content).map_err(ParametersError::Parse)?;
+        params.validate().map_err(ParametersError::Validation)?;
         Ok(params)
     }
 }
+
+/// Append a schema entry for every leaf of `value`, naming nested fields by dotted path
+fn schema_fields(
+    path: &str,
+    value: &serde_json::Value,
+    ranges: &[(&str, f64, f64)],
+    fields: &mut Vec<serde_json::Value>,
+) {
+    use serde_json::Value;
+
+    if let Value::Object(map) = value {
+        for (key, child) in map {
+            let child_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+            schema_fields(&child_path, child, ranges, fields);
+        }
+        return;
+    }
+
+    let kind = match value {
+        Value::Bool(_) => "bool",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        // Optional settings that are off by default
+        _ => "optional",
+    };
+    let mut field = serde_json::json!({ "name": path, "type": kind, "default": value });
+    if let Some((_, min, max)) = ranges.iter().find(|(name, _, _)| *name == path) {
+        field["min"] = serde_json::json!(min);
+        field["max"] = serde_json::json!(max);
+    }
+    fields.push(field);
+}
+
+/// A parameter combination that passes `validate` but is likely to misbehave
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterWarning {
+    /// Predators move further per tick than their capture distance, so they
+    /// can step straight over prey
+    PredatorTunneling { step: f64, capture_distance: f64 },
+    /// Prey move further per tick than predators can see, so they can cross
+    /// a predator's whole perception radius unnoticed
+    PreyOutrunsPerception { step: f64, perception_radius: f64 },
+}
+
+impl ParameterWarning {
+    /// Suggested fix, phrased for the control panel
+    pub fn suggestion(&self) -> String {
+        match self {
+            ParameterWarning::PredatorTunneling { capture_distance, step } => format!(
+                "lower predator max speed by {:.0}% or raise the tick rate",
+                (1.0 - capture_distance / step) * 100.0
+            ),
+            ParameterWarning::PreyOutrunsPerception { perception_radius, step } => format!(
+                "lower prey max speed by {:.0}% or raise the tick rate",
+                (1.0 - perception_radius / step) * 100.0
+            ),
+        }
+    }
+
+    /// Clamp the offending speed so the warning no longer applies
+    pub fn apply_fix(&self, params: &mut Parameters) {
+        let dt = params.simulation.dt;
+        match self {
+            ParameterWarning::PredatorTunneling { capture_distance, .. } => {
+                params.predator.max_speed = params.predator.max_speed.min(capture_distance / dt);
+            }
+            ParameterWarning::PreyOutrunsPerception { perception_radius, .. } => {
+                params.prey.max_speed = params.prey.max_speed.min(perception_radius / dt);
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ParameterWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterWarning::PredatorTunneling { step, capture_distance } => write!(
+                f,
+                "predators move {:.1} per tick, past their capture distance of {:.1}",
+                step, capture_distance
+            ),
+            ParameterWarning::PreyOutrunsPerception { step, perception_radius } => write!(
+                f,
+                "prey move {:.1} per tick, past predator perception of {:.1}",
+                step, perception_radius
+            ),
+        }
+    }
+}
+
+/// Why saving or loading a parameter file failed
+#[derive(Debug)]
+pub enum ParametersError {
+    /// The file couldn't be read or written
+    Io(std::io::Error),
+    /// The file isn't valid TOML for `Parameters`
+    Parse(toml::de::Error),
+    /// The parameters couldn't be turned into TOML
+    Serialize(toml::ser::Error),
+    /// The file parsed, but `Parameters::validate` rejected it
+    Validation(String),
+}
+
+impl std::fmt::Display for ParametersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParametersError::Io(e) => write!(f, "I/O error: {}", e),
+            ParametersError::Parse(e) => write!(f, "invalid parameter file: {}", e),
+            ParametersError::Serialize(e) => write!(f, "could not serialize parameters: {}", e),
+            ParametersError::Validation(msg) => write!(f, "invalid parameters: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ParametersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParametersError::Io(e) => Some(e),
+            ParametersError::Parse(e) => Some(e),
+            ParametersError::Serialize(e) => Some(e),
+            ParametersError::Validation(_) => None,
+        }
+    }
+}
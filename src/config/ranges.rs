@@ -0,0 +1,178 @@
+// Slider ranges of the parameters, shared by the control panel and the schema export
+
+use std::ops::RangeInclusive;
+
+// Predator
+pub const PREDATOR_INITIAL_COUNT: RangeInclusive<u32> = 0..=500;
+pub const PREDATOR_INITIAL_ENERGY: RangeInclusive<f64> = 10.0..=500.0;
+pub const PREDATOR_MAX_SPEED: RangeInclusive<f64> = 0.5..=10.0;
+pub const PREDATOR_PERCEPTION_RADIUS: RangeInclusive<f64> = 10.0..=200.0;
+pub const PREDATOR_CAPTURE_DISTANCE: RangeInclusive<f64> = 1.0..=20.0;
+pub const PREDATOR_DILUTION_FACTOR: RangeInclusive<f64> = 0.0..=2.0;
+pub const PREDATOR_ALARM_RADIUS: RangeInclusive<f64> = 10.0..=400.0;
+pub const PREDATOR_LEARNING_RATE: RangeInclusive<f64> = 0.0..=0.2;
+pub const PREDATOR_MIN_PERCEPTION: RangeInclusive<f64> = 10.0..=200.0;
+pub const PREDATOR_MAX_PERCEPTION: RangeInclusive<f64> = 10.0..=200.0;
+pub const PREDATOR_ENERGY_PER_TICK: RangeInclusive<f64> = 0.1..=5.0;
+pub const PREDATOR_ENERGY_GAIN_FROM_PREY: RangeInclusive<f64> = 10.0..=200.0;
+pub const PREDATOR_REPRODUCTION_THRESHOLD: RangeInclusive<f64> = 50.0..=500.0;
+pub const PREDATOR_REPRODUCTION_COST: RangeInclusive<f64> = 20.0..=200.0;
+pub const PREDATOR_OFFSPRING_ENERGY: RangeInclusive<f64> = 1.0..=200.0;
+pub const PREDATOR_BUDDING_THRESHOLD: RangeInclusive<f64> = 10.0..=500.0;
+pub const PREDATOR_MAX_AGE: RangeInclusive<u32> = 10..=20000;
+pub const PREDATOR_WANDER_STRENGTH: RangeInclusive<f64> = 0.0..=1.0;
+
+// Prey
+pub const PREY_INITIAL_COUNT: RangeInclusive<u32> = 0..=500;
+pub const PREY_INITIAL_ENERGY: RangeInclusive<f64> = 10.0..=500.0;
+pub const PREY_MAX_SPEED: RangeInclusive<f64> = 0.5..=10.0;
+pub const PREY_DETECTION_RADIUS: RangeInclusive<f64> = 10.0..=200.0;
+pub const PREY_FLEE_DISTANCE: RangeInclusive<f64> = 10.0..=100.0;
+pub const PREY_MEMORY_TICKS: RangeInclusive<u32> = 0..=120;
+pub const PREY_THREAT_SPEED_WEIGHT: RangeInclusive<f64> = 0.0..=2.0;
+pub const PREY_INFECTION_RADIUS: RangeInclusive<f64> = 1.0..=100.0;
+pub const PREY_INFECTION_RATE: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_INFECTION_DURATION: RangeInclusive<u32> = 1..=2000;
+pub const PREY_INFECTION_ENERGY_LOSS: RangeInclusive<f64> = 0.0..=5.0;
+pub const PREY_ENERGY_REGENERATION: RangeInclusive<f64> = 0.0..=2.0;
+pub const PREY_DENSITY_CAP: RangeInclusive<f64> = 0.0..=50.0;
+pub const PREY_ENERGY_LOSS_FLEEING: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_REPRODUCTION_THRESHOLD: RangeInclusive<f64> = 50.0..=500.0;
+pub const PREY_REPRODUCTION_COST: RangeInclusive<f64> = 20.0..=200.0;
+pub const PREY_OFFSPRING_ENERGY: RangeInclusive<f64> = 1.0..=200.0;
+pub const PREY_BUDDING_THRESHOLD: RangeInclusive<f64> = 10.0..=500.0;
+pub const PREY_MAX_AGE: RangeInclusive<u32> = 10..=20000;
+pub const PREY_WANDER_STRENGTH: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_WELL_FED_WINDOW: RangeInclusive<u32> = 0..=600;
+pub const PREY_MATURITY_AGE: RangeInclusive<u32> = 0..=1000;
+pub const PREY_DASH_DISTANCE: RangeInclusive<f64> = 0.0..=50.0;
+pub const PREY_DASH_TRIGGER_RANGE: RangeInclusive<f64> = 1.0..=50.0;
+pub const PREY_DASH_COOLDOWN: RangeInclusive<u32> = 0..=600;
+pub const PREY_COHESION_WEIGHT: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_ALIGNMENT_WEIGHT: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_SEPARATION_WEIGHT: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_SEPARATION_DISTANCE: RangeInclusive<f64> = 1.0..=50.0;
+pub const PREY_SCENT_FLEE_WEIGHT: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_HOME_RANGE_WEIGHT: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_COMFORT_ENERGY: RangeInclusive<f64> = 0.0..=300.0;
+pub const PREY_CROWDING_SUPPRESSION: RangeInclusive<f64> = 0.0..=1.0;
+pub const PREY_CROWDING_CAP: RangeInclusive<u32> = 1..=50;
+pub const PREY_COHESION_ENERGY_CURVE_HUNGRY: RangeInclusive<f64> = 0.0..=2.0;
+pub const PREY_COHESION_ENERGY_CURVE_FED: RangeInclusive<f64> = 0.0..=2.0;
+pub const PREY_COHESION_ENERGY_CURVE_EXPONENT: RangeInclusive<f64> = 0.1..=4.0;
+
+// World
+pub const WORLD_WIDTH: RangeInclusive<f64> = 200.0..=2000.0;
+pub const WORLD_HEIGHT: RangeInclusive<f64> = 200.0..=2000.0;
+pub const WORLD_MIN_SPAWN_SEPARATION: RangeInclusive<f64> = 0.0..=50.0;
+pub const WORLD_SPAWN_MARGIN: RangeInclusive<f64> = 0.0..=100.0;
+pub const WORLD_FOOD_SPAWN_RATE: RangeInclusive<f64> = 0.0..=0.2;
+pub const WORLD_FOOD_ENERGY: RangeInclusive<f64> = 1.0..=100.0;
+pub const WORLD_TEMPERATURE_GRADIENT_LEFT: RangeInclusive<f64> = -20.0..=50.0;
+pub const WORLD_TEMPERATURE_GRADIENT_RIGHT: RangeInclusive<f64> = -20.0..=50.0;
+pub const WORLD_TEMPERATURE_GRADIENT_MIN_SPEED_FACTOR: RangeInclusive<f64> = 0.0..=1.0;
+pub const WORLD_SCENT_TRAILS_CELL_SIZE: RangeInclusive<f64> = 5.0..=100.0;
+pub const WORLD_SCENT_TRAILS_DEPOSIT: RangeInclusive<f64> = 0.1..=10.0;
+pub const WORLD_SCENT_TRAILS_RETENTION: RangeInclusive<f64> = 0.5..=0.999;
+pub const WORLD_SCENT_TRAILS_DETECTION_THRESHOLD: RangeInclusive<f64> = 0.0..=10.0;
+
+// Simulation
+pub const SIMULATION_TICK_RATE: RangeInclusive<f64> = 1.0..=120.0;
+pub const SIMULATION_MAX_AGENTS: RangeInclusive<u32> = 10..=5000;
+pub const SIMULATION_MAX_PREDATORS: RangeInclusive<u32> = 0..=5000;
+pub const SIMULATION_MAX_PREY: RangeInclusive<u32> = 0..=5000;
+pub const SIMULATION_SENSOR_NOISE: RangeInclusive<f64> = 0.0..=20.0;
+pub const SIMULATION_MAX_OFFSPRING_ENERGY: RangeInclusive<f64> = 0.0..=500.0;
+pub const SIMULATION_MAX_BIRTHS_PER_TICK: RangeInclusive<u32> = 0..=100;
+pub const SIMULATION_QUADTREE_CAPACITY: RangeInclusive<u32> = 1..=64;
+pub const SIMULATION_AUTO_BALANCE_TARGET_PREY: RangeInclusive<u32> = 10..=1000;
+pub const SIMULATION_AUTO_BALANCE_GAIN: RangeInclusive<f64> = 0.0..=0.1;
+
+/// Every parameter slider range as (dotted field path, min, max)
+pub fn slider_ranges() -> Vec<(&'static str, f64, f64)> {
+    vec![
+        entry("predator.initial_count", PREDATOR_INITIAL_COUNT),
+        entry("predator.initial_energy", PREDATOR_INITIAL_ENERGY),
+        entry("predator.max_speed", PREDATOR_MAX_SPEED),
+        entry("predator.perception_radius", PREDATOR_PERCEPTION_RADIUS),
+        entry("predator.capture_distance", PREDATOR_CAPTURE_DISTANCE),
+        entry("predator.dilution_factor", PREDATOR_DILUTION_FACTOR),
+        entry("predator.alarm_radius", PREDATOR_ALARM_RADIUS),
+        entry("predator.learning_rate", PREDATOR_LEARNING_RATE),
+        entry("predator.min_perception", PREDATOR_MIN_PERCEPTION),
+        entry("predator.max_perception", PREDATOR_MAX_PERCEPTION),
+        entry("predator.energy_per_tick", PREDATOR_ENERGY_PER_TICK),
+        entry("predator.energy_gain_from_prey", PREDATOR_ENERGY_GAIN_FROM_PREY),
+        entry("predator.reproduction_threshold", PREDATOR_REPRODUCTION_THRESHOLD),
+        entry("predator.reproduction_cost", PREDATOR_REPRODUCTION_COST),
+        entry("predator.offspring_energy", PREDATOR_OFFSPRING_ENERGY),
+        entry("predator.budding_threshold", PREDATOR_BUDDING_THRESHOLD),
+        entry("predator.max_age", PREDATOR_MAX_AGE),
+        entry("predator.wander_strength", PREDATOR_WANDER_STRENGTH),
+        entry("prey.initial_count", PREY_INITIAL_COUNT),
+        entry("prey.initial_energy", PREY_INITIAL_ENERGY),
+        entry("prey.max_speed", PREY_MAX_SPEED),
+        entry("prey.detection_radius", PREY_DETECTION_RADIUS),
+        entry("prey.flee_distance", PREY_FLEE_DISTANCE),
+        entry("prey.memory_ticks", PREY_MEMORY_TICKS),
+        entry("prey.threat_speed_weight", PREY_THREAT_SPEED_WEIGHT),
+        entry("prey.infection_radius", PREY_INFECTION_RADIUS),
+        entry("prey.infection_rate", PREY_INFECTION_RATE),
+        entry("prey.infection_duration", PREY_INFECTION_DURATION),
+        entry("prey.infection_energy_loss", PREY_INFECTION_ENERGY_LOSS),
+        entry("prey.energy_regeneration", PREY_ENERGY_REGENERATION),
+        entry("prey.density_cap", PREY_DENSITY_CAP),
+        entry("prey.energy_loss_fleeing", PREY_ENERGY_LOSS_FLEEING),
+        entry("prey.reproduction_threshold", PREY_REPRODUCTION_THRESHOLD),
+        entry("prey.reproduction_cost", PREY_REPRODUCTION_COST),
+        entry("prey.offspring_energy", PREY_OFFSPRING_ENERGY),
+        entry("prey.budding_threshold", PREY_BUDDING_THRESHOLD),
+        entry("prey.max_age", PREY_MAX_AGE),
+        entry("prey.wander_strength", PREY_WANDER_STRENGTH),
+        entry("prey.well_fed_window", PREY_WELL_FED_WINDOW),
+        entry("prey.maturity_age", PREY_MATURITY_AGE),
+        entry("prey.dash_distance", PREY_DASH_DISTANCE),
+        entry("prey.dash_trigger_range", PREY_DASH_TRIGGER_RANGE),
+        entry("prey.dash_cooldown", PREY_DASH_COOLDOWN),
+        entry("prey.cohesion_weight", PREY_COHESION_WEIGHT),
+        entry("prey.alignment_weight", PREY_ALIGNMENT_WEIGHT),
+        entry("prey.separation_weight", PREY_SEPARATION_WEIGHT),
+        entry("prey.separation_distance", PREY_SEPARATION_DISTANCE),
+        entry("prey.scent_flee_weight", PREY_SCENT_FLEE_WEIGHT),
+        entry("prey.home_range_weight", PREY_HOME_RANGE_WEIGHT),
+        entry("prey.comfort_energy", PREY_COMFORT_ENERGY),
+        entry("prey.crowding_suppression", PREY_CROWDING_SUPPRESSION),
+        entry("prey.crowding_cap", PREY_CROWDING_CAP),
+        entry("prey.cohesion_energy_curve.hungry", PREY_COHESION_ENERGY_CURVE_HUNGRY),
+        entry("prey.cohesion_energy_curve.fed", PREY_COHESION_ENERGY_CURVE_FED),
+        entry("prey.cohesion_energy_curve.exponent", PREY_COHESION_ENERGY_CURVE_EXPONENT),
+        entry("world.width", WORLD_WIDTH),
+        entry("world.height", WORLD_HEIGHT),
+        entry("world.min_spawn_separation", WORLD_MIN_SPAWN_SEPARATION),
+        entry("world.spawn_margin", WORLD_SPAWN_MARGIN),
+        entry("world.food_spawn_rate", WORLD_FOOD_SPAWN_RATE),
+        entry("world.food_energy", WORLD_FOOD_ENERGY),
+        entry("world.temperature_gradient.left", WORLD_TEMPERATURE_GRADIENT_LEFT),
+        entry("world.temperature_gradient.right", WORLD_TEMPERATURE_GRADIENT_RIGHT),
+        entry("world.temperature_gradient.min_speed_factor", WORLD_TEMPERATURE_GRADIENT_MIN_SPEED_FACTOR),
+        entry("world.scent_trails.cell_size", WORLD_SCENT_TRAILS_CELL_SIZE),
+        entry("world.scent_trails.deposit", WORLD_SCENT_TRAILS_DEPOSIT),
+        entry("world.scent_trails.retention", WORLD_SCENT_TRAILS_RETENTION),
+        entry("world.scent_trails.detection_threshold", WORLD_SCENT_TRAILS_DETECTION_THRESHOLD),
+        entry("simulation.tick_rate", SIMULATION_TICK_RATE),
+        entry("simulation.max_agents", SIMULATION_MAX_AGENTS),
+        entry("simulation.max_predators", SIMULATION_MAX_PREDATORS),
+        entry("simulation.max_prey", SIMULATION_MAX_PREY),
+        entry("simulation.sensor_noise", SIMULATION_SENSOR_NOISE),
+        entry("simulation.max_offspring_energy", SIMULATION_MAX_OFFSPRING_ENERGY),
+        entry("simulation.max_births_per_tick", SIMULATION_MAX_BIRTHS_PER_TICK),
+        entry("simulation.quadtree_capacity", SIMULATION_QUADTREE_CAPACITY),
+        entry("simulation.auto_balance.target_prey", SIMULATION_AUTO_BALANCE_TARGET_PREY),
+        entry("simulation.auto_balance.gain", SIMULATION_AUTO_BALANCE_GAIN),
+    ]
+}
+
+/// Slider range entry with its bounds widened to f64
+fn entry<T: Into<f64> + Copy>(path: &'static str, range: RangeInclusive<T>) -> (&'static str, f64, f64) {
+    (path, (*range.start()).into(), (*range.end()).into())
+}
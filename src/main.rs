@@ -3,33 +3,124 @@
 use eframe::egui;
 use predator_prey_sim::simulation::world::World;
 use predator_prey_sim::config::parameters::Parameters;
-use predator_prey_sim::ui::controls::ControlPanel;
-use predator_prey_sim::ui::visualization::render_world;
-use predator_prey_sim::ui::statistics::{StatisticsCollector, render_population_graph, Statistics};
+use predator_prey_sim::ui::controls::{ControlPanel, SpawnMode};
+use predator_prey_sim::ui::session::Session;
+use predator_prey_sim::ui::brush::BrushMode;
+use predator_prey_sim::ui::visualization::{render_world, agent_under_cursor, render_hover_highlight, render_selection_highlight, render_neighbor_lines};
+use predator_prey_sim::simulation::agent::{AgentId, AgentType};
+use predator_prey_sim::simulation::runner::{fill_to_max_agents, run_headless, StopReason};
+use predator_prey_sim::ui::statistics::{StatisticsCollector, render_population_graph, render_energy_stack, render_hover_guide, Statistics, GraphStyle, TickRateMeter, is_tick_rate_low};
+use predator_prey_sim::utils::color::{Colors, Theme};
+use predator_prey_sim::utils::math::ease_toward;
+use predator_prey_sim::utils::export::{RunMetadata, RunSummary, write_csv_with_metadata, grid_to_csv, edge_list, run_report_markdown};
+
+/// Window title; the configuration hash is appended at runtime
+const WINDOW_TITLE: &str = "Predator-Prey Simulation";
+
+/// Default file name prefix for CSV exports
+const CSV_EXPORT_PREFIX: &str = "simulation_export";
+
+/// Default file name prefix and resolution for density grid exports
+const DENSITY_EXPORT_PREFIX: &str = "density_grid";
+const DENSITY_GRID_COLS: usize = 40;
+const DENSITY_GRID_ROWS: usize = 30;
+
+/// Default file name prefix for phase-space (predator vs prey count) exports
+const PHASE_EXPORT_PREFIX: &str = "phase_plot";
+
+/// Default file name prefix for perception graph edge lists
+const EDGES_EXPORT_PREFIX: &str = "perception_edges";
+
+/// Default file name prefix for Markdown run reports
+const REPORT_EXPORT_PREFIX: &str = "simulation_report";
+
+/// File the whole app session is saved to and loaded from
+const SESSION_PATH: &str = "session.json";
+
+/// Stop tick offered when "Stop at tick" is first enabled
+const DEFAULT_STOP_TICK: u64 = 1000;
+
+/// Prey carrying capacity offered when the reference line is first enabled
+const DEFAULT_CARRYING_CAPACITY: f64 = 100.0;
+
+/// Screen-space radius (pixels) for picking agents with the mouse
+const PICK_RADIUS_PX: f64 = 10.0;
+
+/// Ticks a "what-if" preview runs ahead by default
+const DEFAULT_PREVIEW_TICKS: u64 = 500;
+
+/// Gap (pixels) between the two canvases in split view
+const SPLIT_GAP_PX: f32 = 4.0;
+
+/// Zoom change per point of mouse-wheel scroll (exponential, so steps feel even)
+const ZOOM_PER_SCROLL_POINT: f32 = 0.002;
 
 fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1200.0, 800.0])
-            .with_title("Predator-Prey Simulation"),
+            .with_title(WINDOW_TITLE),
         ..Default::default()
     };
     
     eframe::run_native(
-        "Predator-Prey Simulation",
+        WINDOW_TITLE,
         options,
-        Box::new(|_cc| Box::new(PredatorPreyApp::default())),
+        Box::new(|cc| {
+            let app = PredatorPreyApp::default();
+            // Panels follow the theme from the first frame, not only once it is toggled
+            cc.egui_ctx.set_visuals(app.layout.visuals());
+            Box::new(app)
+        }),
     )
 }
 
+/// Second, independent world shown beside the main one for A/B comparisons.
+/// Its parameters are fixed when split view is enabled; the controls keep editing world A.
+struct ComparisonRun {
+    world: World,
+    statistics: StatisticsCollector,
+}
+
+impl ComparisonRun {
+    fn new(params: Parameters) -> Self {
+        Self {
+            world: World::new(params),
+            statistics: StatisticsCollector::new(1000),
+        }
+    }
+}
+
 struct PredatorPreyApp {
     world: World,
+    comparison: Option<ComparisonRun>,
     control_panel: ControlPanel,
     layout: predator_prey_sim::ui::layout::UILayout,
     last_update_time: f64,
     reset_requested: bool,
     statistics: StatisticsCollector,
     show_graph: bool,
+    /// Show the energy stack instead of population counts in the graph window
+    show_energy_stack: bool,
+    export_status: Option<String>,
+    context_target: Option<AgentId>,
+    /// Agent picked with a left click, shown in the inspector
+    selected: Option<AgentId>,
+    stop_at_tick: Option<u64>,
+    stop_status: Option<String>,
+    /// How far ahead a preview of a forked world runs
+    preview_ticks: u64,
+    /// Predicted outcome from the last preview
+    preview_status: Option<String>,
+    carrying_capacity: Option<f64>,
+    /// Configuration hash and the parameters it was computed from
+    config_hash: Option<(Parameters, String)>,
+    title_hash: Option<String>,
+    tick_rate_meter: TickRateMeter,
+    /// Wall-clock time of the last world render, in milliseconds
+    render_ms: Option<f64>,
+    /// Speed multiplier actually applied, easing toward the slider value
+    effective_speed: f64,
 }
 
 impl Default for PredatorPreyApp {
@@ -39,36 +130,169 @@ impl Default for PredatorPreyApp {
         
         Self {
             world,
+            comparison: None,
             control_panel: ControlPanel::default(),
             layout: predator_prey_sim::ui::layout::UILayout::default(),
             last_update_time: 0.0,
             reset_requested: false,
             statistics: StatisticsCollector::new(1000), // Keep last 1000 data points
             show_graph: true,
+            show_energy_stack: false,
+            export_status: None,
+            context_target: None,
+            selected: None,
+            stop_at_tick: None,
+            stop_status: None,
+            preview_ticks: DEFAULT_PREVIEW_TICKS,
+            preview_status: None,
+            carrying_capacity: None,
+            config_hash: None,
+            title_hash: None,
+            tick_rate_meter: TickRateMeter::default(),
+            render_ms: None,
+            effective_speed: 1.0,
         }
     }
 }
 
+impl PredatorPreyApp {
+    /// Metadata describing the current run, written alongside exports
+    fn run_metadata(&self) -> RunMetadata {
+        RunMetadata::new(self.world.parameters().clone(), self.world.current_seed(), self.world.tick())
+    }
+
+    /// Hash of the current parameters, recomputed only when they change
+    fn config_hash(&mut self) -> String {
+        let params = self.world.parameters();
+        match &self.config_hash {
+            Some((hashed, hash)) if hashed == params => hash.clone(),
+            _ => {
+                let hash = params.config_hash();
+                self.config_hash = Some((params.clone(), hash.clone()));
+                hash
+            }
+        }
+    }
+
+    /// Default export file name, labelled with the configuration hash
+    fn export_path(&mut self, prefix: &str, extension: &str) -> String {
+        format!("{}_{}.{}", prefix, self.config_hash(), extension)
+    }
+
+    /// Export the statistics history as CSV with a metadata header
+    fn export_csv(&mut self) {
+        let csv = self.statistics.to_csv();
+        let path = self.export_path(CSV_EXPORT_PREFIX, "csv");
+        self.export_status = Some(match write_csv_with_metadata(&path, &self.run_metadata(), &csv) {
+            Ok(()) => format!("Exported to {}", path),
+            Err(e) => format!("Export failed: {}", e),
+        });
+    }
+
+    /// Export the (predator, prey) count pairs for phase plots
+    fn export_phase_csv(&mut self) {
+        let csv = self.statistics.phase_csv();
+        let path = self.export_path(PHASE_EXPORT_PREFIX, "csv");
+        self.export_status = Some(match write_csv_with_metadata(&path, &self.run_metadata(), &csv) {
+            Ok(()) => format!("Exported to {}", path),
+            Err(e) => format!("Export failed: {}", e),
+        });
+    }
+
+    /// Export the current agent density grid as a CSV matrix
+    fn export_density_grid(&mut self) {
+        let grid = self.world.density_grid(DENSITY_GRID_COLS, DENSITY_GRID_ROWS, None);
+        let path = self.export_path(DENSITY_EXPORT_PREFIX, "csv");
+        self.export_status = Some(match std::fs::write(&path, grid_to_csv(&grid)) {
+            Ok(()) => format!("Exported to {}", path),
+            Err(e) => format!("Export failed: {}", e),
+        });
+    }
+
+    /// Export who-sees-whom as an edge list for network analysis
+    fn export_perception_edges(&mut self) {
+        let edges = edge_list(&self.world.perception_edges());
+        let path = self.export_path(EDGES_EXPORT_PREFIX, "txt");
+        self.export_status = Some(match std::fs::write(&path, edges) {
+            Ok(()) => format!("Exported to {}", path),
+            Err(e) => format!("Export failed: {}", e),
+        });
+    }
+
+    /// Save parameters, world, statistics and view settings to one file
+    fn save_session(&mut self) {
+        let session = Session {
+            params: self.control_panel.params.clone(),
+            world: self.world.snapshot(),
+            statistics: self.statistics.clone(),
+            viz_settings: self.control_panel.viz_settings.clone(),
+        };
+        self.export_status = Some(match session.save(SESSION_PATH) {
+            Ok(()) => format!("Session saved to {}", SESSION_PATH),
+            Err(e) => format!("Session save failed: {}", e),
+        });
+    }
+
+    /// Restore a session written by `save_session`
+    fn load_session(&mut self) {
+        match Session::load(SESSION_PATH) {
+            Ok(session) => {
+                self.control_panel.freeze_predators = session.world.freeze_predators;
+                self.control_panel.freeze_prey = session.world.freeze_prey;
+                self.world = World::from_snapshot(session.world);
+                self.control_panel.params = session.params;
+                self.control_panel.viz_settings = session.viz_settings;
+                self.statistics = session.statistics;
+                self.context_target = None;
+                self.selected = None;
+                self.stop_status = None;
+                self.tick_rate_meter.reset();
+                self.export_status = Some(format!("Session loaded from {}", SESSION_PATH));
+            }
+            Err(e) => self.export_status = Some(format!("Session load failed: {}", e)),
+        }
+    }
+
+    /// Save a Markdown report of the run so far
+    fn export_report(&mut self) {
+        let summary = RunSummary {
+            ticks: self.world.tick(),
+            final_predators: self.world.predator_count(),
+            final_prey: self.world.prey_count(),
+        };
+        let report = run_report_markdown(self.world.parameters(), &self.statistics.stats(), &summary);
+        let path = self.export_path(REPORT_EXPORT_PREFIX, "md");
+        self.export_status = Some(match std::fs::write(&path, report) {
+            Ok(()) => format!("Exported to {}", path),
+            Err(e) => format!("Export failed: {}", e),
+        });
+    }
+}
+
 impl eframe::App for PredatorPreyApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Handle reset
         if self.reset_requested {
-            let mut params = self.control_panel.params.clone();
-            // Use slider values for initial counts
-            params.predator.initial_count = self.control_panel.spawn_predators;
-            params.prey.initial_count = self.control_panel.spawn_prey;
-            self.world = World::new(params);
+            // Resetting in place keeps the run count for seed advancing
+            self.world.update_parameters(self.control_panel.reset_parameters());
+            self.world.reset();
             self.reset_requested = false;
             self.statistics.clear();
+            self.stop_status = None;
+            self.selected = None;
+            self.tick_rate_meter.reset();
+            if let Some(comparison) = &mut self.comparison {
+                *comparison = ComparisonRun::new(comparison.world.parameters().clone());
+            }
         }
         
         // Handle spawn requests
         if self.control_panel.spawn_predators_requested {
-            let _spawned = self.world.spawn_predators(self.control_panel.spawn_predators);
+            let _spawned = self.world.spawn_predators(self.control_panel.params.predator.initial_count);
             // Could show a message if spawned < requested
         }
         if self.control_panel.spawn_prey_requested {
-            let _spawned = self.world.spawn_prey(self.control_panel.spawn_prey);
+            let _spawned = self.world.spawn_prey(self.control_panel.params.prey.initial_count);
         }
         
         // Update simulation
@@ -76,14 +300,49 @@ impl eframe::App for PredatorPreyApp {
             let current_time = ctx.input(|i| i.time);
             let dt = current_time - self.last_update_time;
             
+            // Ease toward the slider's speed rather than jumping to it
+            self.effective_speed = ease_toward(
+                self.effective_speed,
+                self.control_panel.speed_multiplier,
+                self.control_panel.speed_smoothing,
+                dt,
+            );
+            
             // Update multiple times per frame based on tick rate and speed multiplier
-            let effective_dt = dt * self.control_panel.speed_multiplier;
+            let effective_dt = dt * self.effective_speed;
             let tick_rate = self.control_panel.params.simulation.tick_rate;
-            let ticks_per_frame = (effective_dt * tick_rate).max(0.0).min(10.0) as u32; // Cap at 10 ticks per frame
+            let mut ticks_per_frame = (effective_dt * tick_rate).max(0.0).min(10.0) as u32; // Cap at 10 ticks per frame
+            
+            // Never run past the stop tick
+            if let Some(stop) = self.stop_at_tick {
+                let remaining = stop.saturating_sub(self.world.tick());
+                ticks_per_frame = ticks_per_frame.min(remaining.min(u32::MAX as u64) as u32);
+            }
             
             for _ in 0..ticks_per_frame {
                 self.world.update();
             }
+            if let Some(comparison) = &mut self.comparison {
+                for _ in 0..ticks_per_frame {
+                    comparison.world.update();
+                }
+                comparison.statistics.record(
+                    comparison.world.predator_count(),
+                    comparison.world.prey_count(),
+                    comparison.world.average_predator_energy(),
+                    comparison.world.average_prey_energy(),
+                    comparison.world.average_predator_speed(),
+                    comparison.world.average_prey_speed(),
+                );
+            }
+            self.tick_rate_meter.record(current_time, ticks_per_frame as u64);
+            
+            if let Some(stop) = self.stop_at_tick {
+                if self.world.tick() >= stop {
+                    self.control_panel.paused = true;
+                    self.stop_status = Some(format!("Reached stop tick {}", stop));
+                }
+            }
             
             self.last_update_time = current_time;
             
@@ -93,46 +352,255 @@ impl eframe::App for PredatorPreyApp {
                 self.world.prey_count(),
                 self.world.average_predator_energy(),
                 self.world.average_prey_energy(),
+                self.world.average_predator_speed(),
+                self.world.average_prey_speed(),
             );
+            self.statistics.record_energy(self.world.energy_totals());
+        } else {
+            // Time spent paused must not count against the measured tick rate
+            self.tick_rate_meter.reset();
         }
         
         // Update parameters if changed
         self.world.update_parameters(self.control_panel.params.clone());
+        self.world.set_frozen(AgentType::Predator, self.control_panel.freeze_predators);
+        self.world.set_frozen(AgentType::Prey, self.control_panel.freeze_prey);
+        
+        // Label the window with the current configuration
+        let hash = self.config_hash();
+        if self.title_hash.as_ref() != Some(&hash) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(format!("{} [{}]", WINDOW_TITLE, hash)));
+            self.title_hash = Some(hash);
+        }
+        
+        // Selection clears once the agent dies
+        self.selected = self.selected.filter(|id| self.world.agent(*id).is_some());
+        
+        // Follow camera: keep the selected agent centered (at the current zoom);
+        // losing the selection stops following
+        let followed = self.selected.and_then(|id| self.world.agent(id)).map(|agent| agent.position());
+        let viz_settings = &mut self.control_panel.viz_settings;
+        match followed {
+            Some(position) if viz_settings.follow_selected => {
+                let world_params = &self.world.parameters().world;
+                viz_settings.camera.center_on(position, world_params.width, world_params.height);
+            }
+            _ => viz_settings.follow_selected = false,
+        }
         
         // Main UI
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.heading("Predator-Prey Simulation");
             
-            // Get available space for canvas
+            // Get available space for canvas (split in half when comparing)
             let available_rect = ui.available_rect_before_wrap();
-            let canvas_rect = egui::Rect::from_min_max(
-                available_rect.min,
-                available_rect.max,
-            );
+            let canvas_rect = if self.comparison.is_some() {
+                egui::Rect::from_min_max(
+                    available_rect.min,
+                    egui::Pos2::new(available_rect.center().x - SPLIT_GAP_PX / 2.0, available_rect.max.y),
+                )
+            } else {
+                egui::Rect::from_min_max(
+                    available_rect.min,
+                    available_rect.max,
+                )
+            };
             
             // Create a painter for the canvas
-            let painter = ui.painter();
+            let painter = ui.painter().clone();
             
             // Draw background
             painter.rect_filled(
                 canvas_rect,
                 0.0,
-                predator_prey_sim::utils::color::Colors::background().to_egui_color32(),
+                Colors::background_for(self.layout.theme).to_egui_color32(),
             );
             
             // Render the simulation
+            let render_start = std::time::Instant::now();
             render_world(
                 &painter,
                 &self.world,
                 &self.control_panel.viz_settings,
                 canvas_rect,
+                self.layout.theme,
             );
+            self.render_ms = Some(render_start.elapsed().as_secs_f64() * 1000.0);
+            
+            // Render the comparison world on the right half
+            if let Some(comparison) = &self.comparison {
+                let comparison_rect = egui::Rect::from_min_max(
+                    egui::Pos2::new(available_rect.center().x + SPLIT_GAP_PX / 2.0, available_rect.min.y),
+                    available_rect.max,
+                );
+                painter.rect_filled(
+                    comparison_rect,
+                    0.0,
+                    Colors::background_for(self.layout.theme).to_egui_color32(),
+                );
+                // The followed agent lives in world A; B keeps the plain view
+                let camera = std::mem::take(&mut self.control_panel.viz_settings.camera);
+                render_world(
+                    &painter,
+                    &comparison.world,
+                    &self.control_panel.viz_settings,
+                    comparison_rect,
+                    self.layout.theme,
+                );
+                self.control_panel.viz_settings.camera = camera;
+                let text_color = Colors::text_for(self.layout.theme).to_egui_color32();
+                for (rect, label) in [(canvas_rect, "A"), (comparison_rect, "B")] {
+                    painter.text(
+                        rect.left_top() + egui::vec2(6.0, 4.0),
+                        egui::Align2::LEFT_TOP,
+                        label,
+                        egui::FontId::proportional(16.0),
+                        text_color,
+                    );
+                }
+            }
             
             // Handle canvas interactions (if needed)
-            let response = ui.allocate_rect(canvas_rect, egui::Sense::click());
+            let response = ui.allocate_rect(canvas_rect, egui::Sense::click_and_drag());
+            // Left-click drops an agent in spawn mode; otherwise it selects the
+            // agent under the cursor (or clears the selection)
             if response.clicked() {
-                // Could handle clicking on agents, spawning, etc.
+                if let Some(pos) = response.interact_pointer_pos() {
+                    let world_params = &self.world.parameters().world;
+                    let camera = &self.control_panel.viz_settings.camera;
+                    let world_pos = camera.screen_to_world(pos, canvas_rect, world_params.width, world_params.height);
+                    match self.control_panel.spawn_mode {
+                        SpawnMode::Predator => {
+                            self.world.spawn_predator_at(world_pos);
+                        }
+                        SpawnMode::Prey => {
+                            self.world.spawn_prey_at(world_pos);
+                        }
+                        SpawnMode::Off => {
+                            self.selected = agent_under_cursor(&self.world, pos, canvas_rect, camera, PICK_RADIUS_PX);
+                        }
+                    }
+                }
+            }
+            
+            // Scroll to zoom about the cursor; drag to pan (middle button, or
+            // the primary one while no brush is active)
+            let brush = self.control_panel.energy_brush;
+            {
+                let (width, height) = (self.world.parameters().world.width, self.world.parameters().world.height);
+                let camera = &mut self.control_panel.viz_settings.camera;
+                if let Some(pos) = response.hover_pos() {
+                    let factor = ui.input(|i| {
+                        i.events.iter().fold(1.0, |factor, event| match event {
+                            egui::Event::Scroll(delta) => factor * (delta.y * ZOOM_PER_SCROLL_POINT).exp(),
+                            egui::Event::Zoom(zoom) => factor * zoom,
+                            _ => factor,
+                        })
+                    });
+                    if factor != 1.0 {
+                        camera.zoom_at(pos, factor, canvas_rect, width, height);
+                    }
+                }
+                let panning = response.dragged_by(egui::PointerButton::Middle)
+                    || (brush.mode == BrushMode::Off && response.dragged_by(egui::PointerButton::Primary));
+                if panning {
+                    camera.pan(response.drag_delta(), canvas_rect, width, height);
+                    // Dragging the view away ends following
+                    self.control_panel.viz_settings.follow_selected = false;
+                }
+            }
+            
+            // Dragging with an energy brush heals or drains the agents under it
+            if brush.mode != BrushMode::Off {
+                if let Some(pos) = response.hover_pos() {
+                    let world_params = &self.world.parameters().world;
+                    let camera = &self.control_panel.viz_settings.camera;
+                    let center = camera.screen_to_world(pos, canvas_rect, world_params.width, world_params.height);
+                    let radius_px = brush.radius * camera.view_rect(canvas_rect).width() as f64 / world_params.width;
+                    painter.circle_stroke(pos, radius_px as f32, egui::Stroke::new(1.0, egui::Color32::from_rgb(120, 120, 120)));
+                    if response.is_pointer_button_down_on() && ui.input(|i| i.pointer.primary_down()) {
+                        brush.apply(&mut self.world, center);
+                    }
+                }
+            }
+            
+            // Hovering near an agent highlights it and shows a tooltip (no selection)
+            let hovered = response
+                .hover_pos()
+                .and_then(|pos| agent_under_cursor(&self.world, pos, canvas_rect, &self.control_panel.viz_settings.camera, PICK_RADIUS_PX));
+            // Debug draw of the selected (or else hovered) agent's perceived neighbors
+            if self.control_panel.viz_settings.debug_neighbors {
+                if let Some(id) = self.selected.or(hovered) {
+                    render_neighbor_lines(&painter, &self.world, id, &self.control_panel.viz_settings.camera, canvas_rect, self.layout.theme);
+                }
+            }
+            
+            if let Some(id) = self.selected {
+                render_selection_highlight(
+                    &painter,
+                    &self.world,
+                    id,
+                    &self.control_panel.viz_settings,
+                    canvas_rect,
+                    self.layout.theme,
+                );
+            }
+            
+            if let Some(agent) = hovered.and_then(|id| self.world.agent(id)) {
+                render_hover_highlight(
+                    &painter,
+                    &self.world,
+                    agent.id(),
+                    &self.control_panel.viz_settings,
+                    canvas_rect,
+                    self.layout.theme,
+                );
+                let kind = match agent.agent_type() {
+                    AgentType::Predator => "Predator",
+                    AgentType::Prey => "Prey",
+                };
+                let text = format!("{} #{}\nEnergy: {:.1}", kind, agent.id(), agent.energy());
+                response.clone().on_hover_text_at_pointer(text);
+            }
+            
+            // Right-click an agent for live "god mode" actions
+            if response.secondary_clicked() {
+                self.context_target = response
+                    .interact_pointer_pos()
+                    .and_then(|pos| agent_under_cursor(&self.world, pos, canvas_rect, &self.control_panel.viz_settings.camera, PICK_RADIUS_PX));
             }
+            let world = &mut self.world;
+            let context_target = self.context_target;
+            response.context_menu(|ui| {
+                let Some(id) = context_target.filter(|id| world.agent_type_of(*id).is_some()) else {
+                    ui.label("No agent here");
+                    return;
+                };
+                
+                if ui.button("💀 Kill").clicked() {
+                    world.kill_agent(id);
+                    ui.close_menu();
+                }
+                if ui.button("⚡ Max Energy").clicked() {
+                    let max_energy = match world.agent_type_of(id) {
+                        Some(AgentType::Predator) => world.parameters().predator.initial_energy,
+                        _ => world.parameters().prey.initial_energy,
+                    };
+                    world.set_agent_energy(id, max_energy);
+                    ui.close_menu();
+                }
+                if ui.button("🐣 Force Reproduce").clicked() {
+                    world.force_reproduce(id);
+                    ui.close_menu();
+                }
+                if world.agent_type_of(id) == Some(AgentType::Prey)
+                    && world.parameters().prey.infection_enabled
+                    && ui.button("🦠 Infect").clicked()
+                {
+                    world.infect_prey(id);
+                    ui.close_menu();
+                }
+            });
         });
         
         // Control panel side panel
@@ -156,8 +624,70 @@ impl eframe::App for PredatorPreyApp {
                 if spawn_prey {
                     self.control_panel.spawn_prey_requested = true;
                 }
+                
+                ui.separator();
+                
+                let mut dark_mode = self.layout.theme == Theme::Dark;
+                if ui.checkbox(&mut dark_mode, "🌙 Dark Mode").changed() {
+                    self.layout.theme = if dark_mode { Theme::Dark } else { Theme::Light };
+                    ctx.set_visuals(self.layout.visuals());
+                }
+                
+                let mut split_view = self.comparison.is_some();
+                if ui.checkbox(&mut split_view, "Split view (A/B)")
+                    .on_hover_text("Run a second world (B) beside this one. B keeps the current parameters; the controls only change A.")
+                    .changed()
+                {
+                    self.comparison = split_view.then(|| ComparisonRun::new(self.control_panel.reset_parameters()));
+                    // Restart A as well so both runs start together
+                    self.reset_requested = split_view;
+                }
+                
+                ui.horizontal(|ui| {
+                    let mut stop_enabled = self.stop_at_tick.is_some();
+                    if ui.checkbox(&mut stop_enabled, "Stop at tick").changed() {
+                        self.stop_at_tick = stop_enabled.then_some(DEFAULT_STOP_TICK);
+                    }
+                    if let Some(stop) = &mut self.stop_at_tick {
+                        ui.add(egui::DragValue::new(stop).speed(10.0));
+                    }
+                });
+                
+                ui.horizontal(|ui| {
+                    if ui.button("💾 Save Session").clicked() {
+                        self.save_session();
+                    }
+                    if ui.button("📂 Load Session").clicked() {
+                        self.load_session();
+                    }
+                });
             });
         
+        // Inspector for the selected agent (floating)
+        if let Some(agent) = self.selected.and_then(|id| self.world.agent(id)) {
+            let mut open = true;
+            egui::Window::new("Selected Agent")
+                .open(&mut open)
+                .collapsible(true)
+                .resizable(false)
+                .default_pos([10.0, 330.0])
+                .show(ctx, |ui| {
+                    let kind = match agent.agent_type() {
+                        AgentType::Predator => "Predator",
+                        AgentType::Prey => "Prey",
+                    };
+                    let velocity = agent.velocity();
+                    ui.label(format!("{} #{}", kind, agent.id()));
+                    ui.label(format!("Energy: {:.1}", agent.energy()));
+                    ui.label(format!("Age: {}", agent.age()));
+                    ui.label(format!("Speed: {:.2} (max {:.2})", velocity.magnitude(), agent.max_speed()));
+                    ui.label(format!("Velocity: ({:.2}, {:.2})", velocity.x, velocity.y));
+                });
+            if !open {
+                self.selected = None;
+            }
+        }
+        
         // Statistics window (floating)
         if self.layout.show_statistics {
             egui::Window::new("Statistics")
@@ -179,6 +709,49 @@ impl eframe::App for PredatorPreyApp {
                     
                     ui.separator();
                     
+                    ui.label(egui::RichText::new("Mean Speed").heading());
+                    ui.label(format!("Predators: {:.2}", self.world.average_predator_speed()));
+                    ui.label(format!("Prey: {:.2}", self.world.average_prey_speed()));
+                    
+                    ui.separator();
+                    
+                    ui.label(egui::RichText::new("Timing").heading());
+                    ui.label(format!("dt: {:.5} s", self.world.parameters().simulation.dt));
+                    let target_rate = self.control_panel.params.simulation.tick_rate
+                        * self.effective_speed;
+                    match self.tick_rate_meter.rate() {
+                        Some(rate) if !self.control_panel.paused => {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("Ticks/s: {:.1} (target {:.1})", rate, target_rate));
+                                if is_tick_rate_low(rate, target_rate) {
+                                    ui.label(egui::RichText::new("⚠").color(egui::Color32::YELLOW))
+                                        .on_hover_text("Simulation is running slower than the configured tick rate");
+                                }
+                            });
+                        }
+                        _ => {
+                            ui.label(format!("Ticks/s: - (target {:.1})", target_rate));
+                        }
+                    }
+                    if let Some(render_ms) = self.render_ms {
+                        ui.label(format!("Render: {:.2} ms", render_ms));
+                    }
+                    if ui.button("Stress Test")
+                        .on_hover_text("Fill the world up to the max agents limit")
+                        .clicked()
+                    {
+                        let spawned = fill_to_max_agents(&mut self.world);
+                        self.stop_status = Some(format!("Stress test: spawned {} agents", spawned));
+                    }
+                    
+                    ui.separator();
+                    
+                    ui.label(egui::RichText::new("Clustering (Moran's I)").heading());
+                    ui.label(format!("Predators: {:.3}", self.world.morans_i(AgentType::Predator)));
+                    ui.label(format!("Prey: {:.3}", self.world.morans_i(AgentType::Prey)));
+                    
+                    ui.separator();
+                    
                     let stats = self.statistics.stats();
                     if stats.data_points > 0 {
                         ui.label(egui::RichText::new("Historical Stats").heading());
@@ -186,6 +759,8 @@ impl eframe::App for PredatorPreyApp {
                         ui.label(format!("Avg Prey: {:.1}", stats.average_prey));
                         ui.label(format!("Max Predators: {}", stats.max_predators));
                         ui.label(format!("Max Prey: {}", stats.max_prey));
+                        ui.label(format!("Avg Predator Speed: {:.2}", stats.average_predator_speed));
+                        ui.label(format!("Avg Prey Speed: {:.2}", stats.average_prey_speed));
                         ui.label(format!("Data Points: {}", stats.data_points));
                     }
                     
@@ -193,13 +768,60 @@ impl eframe::App for PredatorPreyApp {
                     
                     ui.label(egui::RichText::new("Simulation").heading());
                     ui.label(format!("Tick Rate: {:.1} Hz", self.control_panel.params.simulation.tick_rate));
-                    ui.label(format!("Speed: {:.1}x", self.control_panel.speed_multiplier));
+                    ui.label(format!("Speed: {:.1}x", self.effective_speed));
+                    ui.label(format!("Tick: {}", self.world.tick()));
                     ui.label(format!("Status: {}", if self.control_panel.paused { "Paused" } else { "Running" }));
+                    if let Some(status) = &self.stop_status {
+                        ui.label(status);
+                    }
+                    
+                    // Run a fork of the live world ahead without touching it
+                    ui.horizontal(|ui| {
+                        if ui.button("🔮 Preview").on_hover_text("Predict the outcome a number of ticks ahead").clicked() {
+                            let mut fork = self.world.fork();
+                            let outcome = run_headless(&mut fork, self.preview_ticks);
+                            self.preview_status = Some(format!(
+                                "In {} ticks: {} predators, {} prey ({})",
+                                outcome.ticks,
+                                fork.predator_count(),
+                                fork.prey_count(),
+                                match outcome.reason {
+                                    StopReason::ReachedStopTick => "no extinction",
+                                    StopReason::Extinction => "extinction",
+                                },
+                            ));
+                        }
+                        ui.add(egui::DragValue::new(&mut self.preview_ticks).speed(10.0).suffix(" ticks"));
+                    });
+                    if let Some(status) = &self.preview_status {
+                        ui.label(status);
+                    }
                     
                     ui.separator();
                     
-                    if ui.button("Clear Statistics").clicked() {
-                        self.statistics.clear();
+                    ui.horizontal(|ui| {
+                        if ui.button("Clear Statistics").clicked() {
+                            self.statistics.clear();
+                        }
+                        if ui.button("Export CSV").clicked() {
+                            self.export_csv();
+                        }
+                        if ui.button("Export Phase").clicked() {
+                            self.export_phase_csv();
+                        }
+                        if ui.button("Export Density").clicked() {
+                            self.export_density_grid();
+                        }
+                        if ui.button("Export Edges").clicked() {
+                            self.export_perception_edges();
+                        }
+                        if ui.button("Save Report").clicked() {
+                            self.export_report();
+                        }
+                    });
+                    
+                    if let Some(status) = &self.export_status {
+                        ui.label(status);
                     }
                 });
         }
@@ -212,7 +834,24 @@ impl eframe::App for PredatorPreyApp {
                 .default_pos([270.0, 10.0])
                 .default_size([600.0, 300.0])
                 .show(ctx, |ui| {
-                    ui.label("Population Over Time");
+                    ui.horizontal(|ui| {
+                        ui.label("Population Over Time");
+                        let mut show_capacity = self.carrying_capacity.is_some();
+                        if ui.checkbox(&mut show_capacity, "Carrying capacity").changed() {
+                            self.carrying_capacity = show_capacity.then_some(DEFAULT_CARRYING_CAPACITY);
+                        }
+                        if let Some(capacity) = &mut self.carrying_capacity {
+                            ui.add(egui::DragValue::new(capacity).speed(1.0).clamp_range(0.0..=5000.0));
+                        }
+                        ui.checkbox(&mut self.show_energy_stack, "Energy stack");
+                    });
+                    
+                    let style = GraphStyle {
+                        reference_lines: self
+                            .carrying_capacity
+                            .map(|capacity| vec![(capacity, Colors::prey())])
+                            .unwrap_or_default(),
+                    };
                     
                     let available = ui.available_rect_before_wrap();
                     let graph_rect = egui::Rect::from_min_max(
@@ -220,13 +859,54 @@ impl eframe::App for PredatorPreyApp {
                         egui::Pos2::new(available.max.x, available.max.y),
                     );
                     
-                    let painter = ui.painter();
-                    render_population_graph(&painter, &self.statistics, graph_rect);
+                    let painter = ui.painter().clone();
+                    if self.show_energy_stack {
+                        render_energy_stack(&painter, &self.statistics, graph_rect);
+                    } else {
+                        render_population_graph(&painter, &self.statistics, &style, graph_rect);
+                    }
                     
-                    ui.allocate_rect(graph_rect, egui::Sense::click());
+                    let response = ui.allocate_rect(graph_rect, egui::Sense::hover());
+                    if let Some(cursor) = response.hover_pos() {
+                        if let Some(point) = render_hover_guide(&painter, &self.statistics, graph_rect, cursor.x) {
+                            response.on_hover_ui_at_pointer(|ui| {
+                                ui.label(format!("Tick: {}", point.tick));
+                                ui.label(format!("Predators: {}", point.predator_count));
+                                ui.label(format!("Prey: {}", point.prey_count));
+                                ui.label(format!("Mean speed: {:.2} / {:.2} (predators / prey)", point.predator_speed, point.prey_speed));
+                            });
+                        }
+                    }
                 });
         }
         
+        // Population graph of the comparison world
+        if let Some(comparison) = &self.comparison {
+            if self.show_graph {
+                egui::Window::new("Population Graph (B)")
+                    .collapsible(true)
+                    .resizable(true)
+                    .default_pos([270.0, 330.0])
+                    .default_size([600.0, 300.0])
+                    .show(ctx, |ui| {
+                        ui.label(format!(
+                            "Predators: {}  Prey: {}  Tick: {}",
+                            comparison.world.predator_count(),
+                            comparison.world.prey_count(),
+                            comparison.world.tick(),
+                        ));
+                        
+                        let available = ui.available_rect_before_wrap();
+                        let graph_rect = egui::Rect::from_min_max(
+                            egui::Pos2::new(available.min.x, available.min.y + 30.0),
+                            egui::Pos2::new(available.max.x, available.max.y),
+                        );
+                        render_population_graph(ui.painter(), &comparison.statistics, &GraphStyle::default(), graph_rect);
+                        ui.allocate_rect(graph_rect, egui::Sense::hover());
+                    });
+            }
+        }
+        
         // Request repaint for animation
         ctx.request_repaint();
     }
@@ -1,12 +1,77 @@
 // Base agent trait and implementation
 
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use crate::config::parameters::ReproductionStrategy;
 use crate::utils::math::Vector2;
 
-/// Unique identifier for agents
-pub type AgentId = u32;
+/// Unique identifier for agents.
+///
+/// Flat ids are a plain sequence number. Structured ids additionally encode
+/// the species in the top two bits and a generation counter in the 30 bits
+/// above the 32-bit sequence number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AgentId(pub u64);
+
+/// Bit offset of the species tag in a structured id
+const ID_TYPE_SHIFT: u32 = 62;
+/// Bit offset of the generation counter in a structured id
+const ID_GENERATION_SHIFT: u32 = 32;
+/// Highest generation a structured id can hold; deeper lineages saturate
+pub const MAX_ID_GENERATION: u32 = (1 << (ID_TYPE_SHIFT - ID_GENERATION_SHIFT)) - 1;
+
+impl AgentId {
+    /// Id carrying only a sequence number
+    pub fn flat(sequence: u64) -> Self {
+        Self(sequence)
+    }
+
+    /// Id encoding species, generation (saturating at `MAX_ID_GENERATION`) and sequence number
+    pub fn structured(agent_type: AgentType, generation: u32, index: u32) -> Self {
+        let tag: u64 = match agent_type {
+            AgentType::Predator => 1,
+            AgentType::Prey => 2,
+        };
+        let generation = generation.min(MAX_ID_GENERATION) as u64;
+        Self(tag << ID_TYPE_SHIFT | generation << ID_GENERATION_SHIFT | index as u64)
+    }
+
+    /// Species encoded in the id (None for flat ids)
+    pub fn agent_type(&self) -> Option<AgentType> {
+        match self.0 >> ID_TYPE_SHIFT {
+            1 => Some(AgentType::Predator),
+            2 => Some(AgentType::Prey),
+            _ => None,
+        }
+    }
+
+    /// Generation encoded in the id (0 for founders and for flat ids)
+    pub fn generation(&self) -> u32 {
+        if self.agent_type().is_none() {
+            return 0;
+        }
+        ((self.0 >> ID_GENERATION_SHIFT) as u32) & MAX_ID_GENERATION
+    }
+
+    /// Sequence number of the id
+    pub fn index(&self) -> u64 {
+        if self.agent_type().is_none() {
+            self.0
+        } else {
+            self.0 & u32::MAX as u64
+        }
+    }
+}
+
+impl std::fmt::Display for AgentId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
 /// Agent type enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AgentType {
     Predator,
     Prey,
@@ -36,12 +101,29 @@ pub trait Agent {
     fn age(&self) -> u32;
 
     /// Update the agent - called each simulation tick
-    fn update(&mut self, world_state: &WorldState) -> AgentAction;
+    fn update(&mut self, world_state: &WorldState) -> AgentAction {
+        self.update_with_rng(world_state, &mut rand::thread_rng())
+    }
+
+    /// Update the agent, drawing all of its randomness from `rng` (the world
+    /// passes its own RNG; tests can pass a seeded one)
+    fn update_with_rng(&mut self, world_state: &WorldState, rng: &mut dyn RngCore) -> AgentAction;
 
     /// Get the agent's maximum speed
     fn max_speed(&self) -> f64;
 }
 
+/// What an agent can perceive about one of its neighbors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NeighborInfo {
+    pub id: AgentId,
+    pub position: Vector2,
+    pub velocity: Vector2,
+    pub energy: f64,
+    /// Distance from the perceiving agent
+    pub distance: f64,
+}
+
 /// Information about the world state that agents can query
 #[derive(Debug, Clone)]
 pub struct WorldState {
@@ -52,11 +134,28 @@ pub struct WorldState {
     /// Boundary type (wraparound or walls)
     pub boundary_type: crate::config::parameters::BoundaryType,
     /// Nearby predators (within perception range)
-    pub nearby_predators: Vec<(AgentId, Vector2, f64)>, // (id, position, distance)
+    pub nearby_predators: Vec<NeighborInfo>,
     /// Nearby prey (within perception range)
-    pub nearby_prey: Vec<(AgentId, Vector2, f64)>, // (id, position, distance)
+    pub nearby_prey: Vec<NeighborInfo>,
+    /// Positions of recent prey alarm calls within hearing range
+    pub alarm_positions: Vec<Vector2>,
+    /// Predator scent gradient at the agent, toward stronger scent
+    /// (zero where the scent is too faint to notice or trails are off)
+    pub scent_gradient: Vector2,
     /// Delta time (time step)
     pub dt: f64,
+    /// Multiplier on the agent's max speed at its position (e.g. from temperature)
+    pub speed_scale: f64,
+    /// Integration scheme used to advance positions
+    pub integration: crate::config::parameters::IntegrationScheme,
+    /// Whether offspring offsets are hashed from (id, age) instead of random
+    pub deterministic_offspring: bool,
+}
+
+/// Order neighbor entries by distance, breaking ties by the lower `AgentId`
+/// so neighbor selection doesn't depend on iteration order
+pub fn compare_neighbors(a: &NeighborInfo, b: &NeighborInfo) -> std::cmp::Ordering {
+    a.distance.total_cmp(&b.distance).then(a.id.cmp(&b.id))
 }
 
 /// Action that an agent can take during an update
@@ -72,16 +171,30 @@ pub enum AgentAction {
     Reproduce { position: Vector2, energy: f64 },
 }
 
+/// Fraction of its velocity an idle agent keeps each tick
+const IDLE_VELOCITY_RETENTION: f64 = 0.95;
+
+/// Largest change (radians) of the wander heading in one tick
+const WANDER_MAX_TURN: f64 = 0.3;
+
 /// Base agent data structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BaseAgent {
     pub id: AgentId,
     pub agent_type: AgentType,
     pub position: Vector2,
     pub velocity: Vector2,
+    /// Velocity used by the previous position update (start-of-tick velocity)
+    pub previous_velocity: Vector2,
     pub energy: f64,
     pub age: u32,
     pub max_speed: f64,
+    /// Fraction of the next offspring paid for so far (budding only)
+    #[serde(default)]
+    pub reproduction_progress: f64,
+    /// Heading (radians) of the idle wander, picked at random on first use
+    #[serde(default)]
+    pub wander_heading: Option<f64>,
 }
 
 impl BaseAgent {
@@ -98,26 +211,79 @@ impl BaseAgent {
             agent_type,
             position,
             velocity: Vector2::zero(),
+            previous_velocity: Vector2::zero(),
             energy: initial_energy,
             age: 0,
             max_speed,
+            reproduction_progress: 0.0,
+            wander_heading: None,
+        }
+    }
+
+    /// Velocity for a tick with nothing to chase or flee. The current velocity
+    /// decays; with `wander_strength` > 0 it eases toward a wander heading that
+    /// turns a little each tick, at `wander_strength` times max speed.
+    pub fn idle_velocity(&mut self, wander_strength: f64, rng: &mut dyn RngCore) -> Vector2 {
+        let decayed = self.velocity.scale(IDLE_VELOCITY_RETENTION);
+        if wander_strength <= 0.0 {
+            return decayed;
+        }
+        let heading = match self.wander_heading {
+            Some(heading) => heading + rng.gen_range(-WANDER_MAX_TURN..=WANDER_MAX_TURN),
+            None => rng.gen::<f64>() * std::f64::consts::PI * 2.0,
+        };
+        self.wander_heading = Some(heading);
+        let wander = crate::utils::math::from_angle(heading, wander_strength * self.max_speed);
+        decayed.add(&wander.scale(1.0 - IDLE_VELOCITY_RETENTION))
+    }
+
+    /// Spawn position for an offspring: within 20 units of the agent,
+    /// clamped to stay 10 units inside the world
+    pub fn offspring_position(&self, world_state: &WorldState, rng: &mut dyn RngCore) -> Vector2 {
+        let offset = if world_state.deterministic_offspring {
+            crate::utils::math::deterministic_offset(self.id.0, self.age, 20.0)
+        } else {
+            let angle = rng.gen::<f64>() * std::f64::consts::PI * 2.0;
+            let distance = rng.gen::<f64>() * 20.0;
+            crate::utils::math::from_angle(angle, distance)
+        };
+        let spawn_pos = self.position.add(&offset);
+
+        Vector2 {
+            x: spawn_pos.x.max(10.0).min(world_state.width - 10.0),
+            y: spawn_pos.y.max(10.0).min(world_state.height - 10.0),
         }
     }
 
     /// Update the agent's position based on velocity
     pub fn update_position(&mut self, world_state: &WorldState) {
         let dt = world_state.dt;
-        let new_position = self.position.add(&self.velocity.scale(dt));
+        // Local conditions can hold the agent below its nominal max speed
+        self.velocity = self.velocity.limit(self.max_speed * world_state.speed_scale);
+        let step_velocity = match world_state.integration {
+            crate::config::parameters::IntegrationScheme::Euler => self.previous_velocity,
+            crate::config::parameters::IntegrationScheme::SemiImplicitEuler => self.velocity,
+        };
+        let new_position = self.position.add(&step_velocity.scale(dt));
+        self.previous_velocity = self.velocity;
+        self.position = Self::apply_boundary(new_position, world_state);
+    }
+
+    /// Move instantly by `offset` (no velocity involved), respecting the boundary
+    pub fn displace(&mut self, offset: Vector2, world_state: &WorldState) {
+        self.position = Self::apply_boundary(self.position.add(&offset), world_state);
+    }
 
-        // Apply boundary conditions
-        self.position = match world_state.boundary_type {
+    /// Wrap or clamp a position according to the boundary type
+    fn apply_boundary(position: Vector2, world_state: &WorldState) -> Vector2 {
+        match world_state.boundary_type {
             crate::config::parameters::BoundaryType::Wraparound => {
-                crate::utils::math::wrap_position(new_position, world_state.width, world_state.height)
+                crate::utils::math::wrap_position(position, world_state.width, world_state.height)
             }
             crate::config::parameters::BoundaryType::Walls => {
-                crate::utils::math::clamp_position(new_position, world_state.width, world_state.height)
+                crate::utils::math::clamp_position(position, world_state.width, world_state.height)
             }
-        };
+        }
     }
 
     /// Set the velocity, ensuring it doesn't exceed max_speed
@@ -135,6 +301,39 @@ impl BaseAgent {
         self.energy += amount;
     }
 
+    /// Pay for an offspring under `strategy`; returns whether one is due this tick.
+    /// Threshold splits off `cost` once energy reaches `threshold`; budding moves all
+    /// energy above `budding_threshold` into progress and completes at 1.0.
+    pub fn try_reproduce(
+        &mut self,
+        strategy: ReproductionStrategy,
+        threshold: f64,
+        budding_threshold: f64,
+        cost: f64,
+    ) -> bool {
+        match strategy {
+            ReproductionStrategy::Threshold => {
+                if self.energy < threshold {
+                    return false;
+                }
+                self.consume_energy(cost);
+                true
+            }
+            ReproductionStrategy::Budding => {
+                let surplus = self.energy - budding_threshold;
+                if surplus > 0.0 && cost > 0.0 {
+                    self.energy = budding_threshold;
+                    self.reproduction_progress += surplus / cost;
+                }
+                if self.reproduction_progress < 1.0 {
+                    return false;
+                }
+                self.reproduction_progress -= 1.0;
+                true
+            }
+        }
+    }
+
     /// Increment age
     pub fn increment_age(&mut self) {
         self.age += 1;
@@ -144,4 +343,9 @@ impl BaseAgent {
     pub fn check_alive(&self) -> bool {
         self.energy > 0.0
     }
+
+    /// Whether the agent has reached `max_age` (never, without a limit)
+    pub fn outlived(&self, max_age: Option<u32>) -> bool {
+        max_age.is_some_and(|max| self.age >= max)
+    }
 }
@@ -5,4 +5,8 @@ pub mod predator;
 pub mod prey;
 pub mod world;
 pub mod rules;
+pub mod runner;
+pub mod spatial;
+pub mod scent;
+pub mod snapshot;
 
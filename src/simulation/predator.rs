@@ -1,14 +1,27 @@
 // Predator agent implementation
 
+use serde::{Deserialize, Serialize};
 use crate::simulation::agent::*;
-use crate::config::parameters::PredatorParameters;
-use crate::utils::math::{Vector2, distance, from_angle};
+use crate::config::parameters::{BoundaryType, PredatorParameters, PursuitMode};
+use crate::utils::math::{Vector2, distance_torus, torus_delta};
+
+/// Ticks without a capture after which a learning predator narrows its perception
+const LEARNING_PATIENCE_TICKS: u32 = 300;
+
+/// Half-angle (radians) of a prey's forward arc, within which it sees attackers coming
+const PREY_FORWARD_ARC_HALF_ANGLE: f64 = std::f64::consts::FRAC_PI_3;
 
 /// Predator agent
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Predator {
     base: BaseAgent,
     params: PredatorParameters,
+    /// Ticks since the last successful capture (drives perception learning)
+    ticks_since_capture: u32,
+    /// Learned state (perception radius, ticks since capture) from just before
+    /// this tick's capture, restored if the world forfeits that capture
+    #[serde(skip)]
+    before_capture: Option<(f64, u32)>,
 }
 
 impl Predator {
@@ -22,21 +35,152 @@ impl Predator {
             params.max_speed,
         );
 
-        Self { base, params }
+        Self {
+            base,
+            params,
+            ticks_since_capture: 0,
+            before_capture: None,
+        }
+    }
+
+    /// Create a new predator starting with `energy` instead of `initial_energy`
+    pub fn new_with_energy(id: AgentId, position: Vector2, params: PredatorParameters, energy: f64) -> Self {
+        let mut agent = Self::new(id, position, params);
+        agent.set_energy(energy);
+        agent
+    }
+
+    /// Set the energy directly (clamped at zero)
+    pub fn set_energy(&mut self, energy: f64) {
+        self.base.energy = energy.max(0.0);
+    }
+
+    /// Fraction of the next budded offspring paid for so far
+    pub fn reproduction_progress(&self) -> f64 {
+        self.base.reproduction_progress
+    }
+
+    /// Undo a capture that failed after all (the prey escaped or another
+    /// predator got it first): give back its energy and its learning step
+    pub fn forfeit_capture(&mut self) {
+        self.base.consume_energy(self.params.energy_gain_from_prey);
+        if let Some((perception_radius, ticks_since_capture)) = self.before_capture.take() {
+            self.params.perception_radius = perception_radius;
+            self.ticks_since_capture = ticks_since_capture;
+        }
+    }
+
+    /// This predator's own (possibly learned) perception radius
+    pub fn perception_radius(&self) -> f64 {
+        self.params.perception_radius
+    }
+
+    /// Scale the perception radius by `factor`, kept within the learning bounds
+    fn adjust_perception(&mut self, factor: f64) {
+        self.params.perception_radius = (self.params.perception_radius * factor)
+            .max(self.params.min_perception)
+            .min(self.params.max_perception);
+    }
+
+    /// Update learned perception: widen after a capture, narrow after a long
+    /// unsuccessful stretch
+    fn learn(&mut self, captured: bool) {
+        if self.params.learning_rate <= 0.0 {
+            return;
+        }
+
+        if captured {
+            self.ticks_since_capture = 0;
+            self.adjust_perception(1.0 + self.params.learning_rate);
+        } else {
+            self.ticks_since_capture += 1;
+            if self.ticks_since_capture >= LEARNING_PATIENCE_TICKS {
+                self.ticks_since_capture = 0;
+                self.adjust_perception(1.0 - self.params.learning_rate);
+            }
+        }
     }
 
-    /// Find the nearest prey within perception radius
-    fn find_nearest_prey(&self, world_state: &WorldState) -> Option<(AgentId, Vector2, f64)> {
+    /// Choose which visible prey to hunt.
+    /// By default this is simply the nearest prey. With `prefer_high_energy_prey`,
+    /// prey are ranked by `energy^satiety / (distance + 1)`, where satiety is the
+    /// predator's own energy relative to its reproduction threshold: a hungry
+    /// predator takes the closest prey, a sated one holds out for richer prey.
+    fn choose_target(&self, world_state: &WorldState) -> Option<NeighborInfo> {
+        if !self.params.prefer_high_energy_prey {
+            return world_state
+                .nearby_prey
+                .iter()
+                .min_by(|a, b| compare_neighbors(a, b))
+                .copied();
+        }
+
+        let satiety = (self.base.energy / self.params.reproduction_threshold).clamp(0.0, 1.0);
+        let value = |prey: &NeighborInfo| prey.energy.max(0.0).powf(satiety) / (prey.distance + 1.0);
+
         world_state
             .nearby_prey
             .iter()
-            .min_by(|(_, _, d1), (_, _, d2)| d1.partial_cmp(d2).unwrap())
+            .max_by(|a, b| value(a).total_cmp(&value(b)).then(b.id.cmp(&a.id)))
             .copied()
     }
 
+    /// Find the nearest recent alarm call this predator can hear
+    fn find_nearest_alarm(&self, world_state: &WorldState) -> Option<Vector2> {
+        world_state
+            .alarm_positions
+            .iter()
+            .map(|pos| (*pos, distance_torus(&self.base.position, pos, world_state.width, world_state.height)))
+            .min_by(|(_, d1), (_, d2)| d1.total_cmp(d2))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Point to steer toward when chasing a prey. Under `Intercept` the prey's
+    /// position is extrapolated by the time needed to close the distance.
+    fn pursuit_target(&self, prey: &NeighborInfo) -> Vector2 {
+        match self.params.pursuit {
+            PursuitMode::DirectSeek => prey.position,
+            PursuitMode::Intercept => {
+                let closing_speed = self.base.max_speed;
+                if closing_speed <= 0.0 {
+                    return prey.position;
+                }
+                let lead_time = prey.distance / closing_speed;
+                prey.position.add(&prey.velocity.scale(lead_time))
+            }
+        }
+    }
+
+    /// Whether this predator may capture the prey from where it is. With
+    /// `rear_attack_only`, a moving prey that has the predator inside its
+    /// forward arc sees it coming and escapes capture.
+    fn can_capture(&self, prey: &NeighborInfo, world_state: &WorldState) -> bool {
+        if prey.distance > self.params.capture_distance {
+            return false;
+        }
+        if !self.params.rear_attack_only || prey.velocity.magnitude() == 0.0 {
+            return true;
+        }
+
+        let to_predator = torus_delta(&prey.position, &self.base.position, world_state.width, world_state.height);
+        if to_predator.magnitude() == 0.0 {
+            return true;
+        }
+        let cos_angle = prey.velocity.normalize().dot(&to_predator.normalize());
+        cos_angle < PREY_FORWARD_ARC_HALF_ANGLE.cos()
+    }
+
+    /// Shortest offset from this predator to `target` under the boundary topology
+    fn offset_to(&self, target: Vector2, world_state: &WorldState) -> Vector2 {
+        match world_state.boundary_type {
+            BoundaryType::Wraparound => torus_delta(&self.base.position, &target, world_state.width, world_state.height),
+            BoundaryType::Walls => target.subtract(&self.base.position),
+        }
+    }
+
     /// Calculate steering force toward a target
-    fn seek(&self, target: Vector2) -> Vector2 {
-        let desired = target.subtract(&self.base.position);
+    fn seek(&self, target: Vector2, world_state: &WorldState) -> Vector2 {
+        let desired = self.offset_to(target, world_state);
         let distance = desired.magnitude();
 
         if distance > 0.0 {
@@ -70,7 +214,7 @@ impl Agent for Predator {
     }
 
     fn is_alive(&self) -> bool {
-        self.base.check_alive()
+        self.base.check_alive() && !self.base.outlived(self.params.max_age)
     }
 
     fn age(&self) -> u32 {
@@ -81,53 +225,57 @@ impl Agent for Predator {
         self.base.max_speed
     }
 
-    fn update(&mut self, world_state: &WorldState) -> AgentAction {
+    fn update_with_rng(&mut self, world_state: &WorldState, rng: &mut dyn rand::RngCore) -> AgentAction {
         // Consume energy each tick
         self.base.consume_energy(self.params.energy_per_tick * world_state.dt);
         self.base.increment_age();
 
-        // If dead, no action
-        if !self.base.check_alive() {
+        // If dead (starved or of old age), no action
+        if !self.is_alive() {
             return AgentAction::None;
         }
 
-        // Try to find and chase nearest prey
-        if let Some((prey_id, prey_pos, distance)) = self.find_nearest_prey(world_state) {
-            // If within capture distance, consume the prey
-            if distance <= self.params.capture_distance {
+        self.learn(false);
+        self.before_capture = None;
+
+        // Try to find and chase a prey
+        if let Some(prey) = self.choose_target(world_state) {
+            // If within capture distance (and, optionally, unseen), consume the prey
+            if self.can_capture(&prey, world_state) {
                 self.base.add_energy(self.params.energy_gain_from_prey);
-                return AgentAction::Consumed { target_id: prey_id };
+                self.before_capture = Some((self.params.perception_radius, self.ticks_since_capture));
+                self.learn(true);
+                return AgentAction::Consumed { target_id: prey.id };
             }
 
             // Otherwise, move toward the prey
-            let desired_velocity = self.seek(prey_pos);
+            let desired_velocity = self.seek(self.pursuit_target(&prey), world_state);
+            self.base.set_velocity(desired_velocity);
+        } else if let Some(alarm_pos) = self
+            .find_nearest_alarm(world_state)
+            .filter(|_| self.params.alarm_attracts_predators)
+        {
+            // No prey in sight, but a prey alarm call gives away a position
+            let desired_velocity = self.seek(alarm_pos, world_state);
             self.base.set_velocity(desired_velocity);
         } else {
-            // No prey nearby - random wander or slow down
-            // Simple implementation: slow down
-            self.base.set_velocity(self.base.velocity.scale(0.95));
+            // No prey nearby - wander or slow down
+            let idle = self.base.idle_velocity(self.params.wander_strength, rng);
+            self.base.set_velocity(idle);
         }
 
         // Check for reproduction
-        if self.base.energy >= self.params.reproduction_threshold {
-            // Spawn near current position with some random offset
-            use rand::Rng;
-            let mut rng = rand::thread_rng();
-            let angle = rng.gen::<f64>() * std::f64::consts::PI * 2.0;
-            let distance = rng.gen::<f64>() * 20.0;
-            let offset = from_angle(angle, distance);
-            let spawn_pos = self.base.position.add(&offset);
-            
-            // Clamp spawn position to world bounds
-            let spawn_pos = Vector2 {
-                x: spawn_pos.x.max(10.0).min(world_state.width - 10.0),
-                y: spawn_pos.y.max(10.0).min(world_state.height - 10.0),
-            };
-            
-            self.base.consume_energy(self.params.reproduction_cost);
+        if self.base.try_reproduce(
+            self.params.reproduction_strategy,
+            self.params.reproduction_threshold,
+            self.params.budding_threshold,
+            self.params.reproduction_cost,
+        ) {
+            // Spawn near current position
+            let spawn_pos = self.base.offspring_position(world_state, rng);
             return AgentAction::Reproduce {
                 position: spawn_pos,
-                energy: self.params.initial_energy,
+                energy: self.params.newborn_energy(),
             };
         }
 
@@ -137,7 +285,3 @@ impl Agent for Predator {
         AgentAction::None
     }
 }
-
-// Add fastrand dependency for random numbers
-// For now, we'll use a simple approach. We'll need to add fastrand to Cargo.toml
-// Or we can use a seeded RNG. Let me check if we can use rand instead.
@@ -1,14 +1,45 @@
 // Prey agent implementation
 
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
 use crate::simulation::agent::*;
-use crate::config::parameters::PreyParameters;
-use crate::utils::math::{Vector2, from_angle};
+use crate::config::parameters::{BoundaryType, PreyParameters};
+use crate::utils::math::{Vector2, from_angle, torus_delta};
+
+/// Fraction of max speed used when fleeing from a remembered (no longer seen) threat
+const REMEMBERED_THREAT_URGENCY: f64 = 0.5;
+
+/// Distance from a wall within which a prey counts as cornered against it
+const WALL_MARGIN: f64 = 15.0;
+
+/// Disease state of a prey (SIR model)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum InfectionState {
+    #[default]
+    Susceptible,
+    Infected { ticks_left: u32 },
+    /// Immune after recovering
+    Recovered,
+}
 
 /// Prey agent
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Prey {
     base: BaseAgent,
     params: PreyParameters,
+    /// Last threat position and the number of ticks it will still be remembered
+    last_threat: Option<(Vector2, u32)>,
+    /// Whether the prey fled from a visible predator this tick
+    alarmed: bool,
+    infection: InfectionState,
+    /// Ticks since this prey last ate food (saturates; starts as never fed)
+    ticks_since_fed: u32,
+    /// Ticks until the prey can dash again (0 = ready)
+    #[serde(default)]
+    dash_cooldown_remaining: u32,
+    /// Last position where the prey felt safe (calm, with no predator in sight)
+    #[serde(default)]
+    safe_spot: Option<Vector2>,
 }
 
 impl Prey {
@@ -22,31 +53,341 @@ impl Prey {
             params.max_speed,
         );
 
-        Self { base, params }
+        Self {
+            base,
+            params,
+            last_threat: None,
+            alarmed: false,
+            infection: InfectionState::Susceptible,
+            ticks_since_fed: u32::MAX,
+            dash_cooldown_remaining: 0,
+            safe_spot: None,
+        }
+    }
+
+    /// Eat a food item
+    pub fn feed(&mut self, energy: f64) {
+        self.base.add_energy(energy);
+        self.ticks_since_fed = 0;
+    }
+
+    /// Ticks since the prey last ate (`u32::MAX` if it never has)
+    pub fn ticks_since_fed(&self) -> u32 {
+        self.ticks_since_fed
+    }
+
+    /// Whether recent feeding allows reproduction (always, if the window is off)
+    fn is_well_fed(&self) -> bool {
+        self.params.well_fed_window == 0 || self.ticks_since_fed < self.params.well_fed_window
+    }
+
+    /// Chance that a breeding attempt goes ahead given the visible crowd
+    /// (1 with crowding suppression off)
+    pub fn breeding_probability(&self, world_state: &WorldState) -> f64 {
+        if self.params.crowding_suppression <= 0.0 {
+            return 1.0;
+        }
+        let crowding = (world_state.nearby_prey.len() as f64 / self.params.crowding_cap.max(1) as f64).min(1.0);
+        (1.0 - self.params.crowding_suppression * crowding).max(0.0)
+    }
+
+    /// Current disease state
+    pub fn infection(&self) -> InfectionState {
+        self.infection
+    }
+
+    /// Whether the prey is currently infected
+    pub fn is_infected(&self) -> bool {
+        matches!(self.infection, InfectionState::Infected { .. })
+    }
+
+    /// Infect a susceptible prey. Returns false if it is already infected or immune.
+    pub fn infect(&mut self) -> bool {
+        if self.infection != InfectionState::Susceptible {
+            return false;
+        }
+        self.infection = InfectionState::Infected { ticks_left: self.params.infection_duration.max(1) };
+        true
+    }
+
+    /// Advance the disease: drain extra energy and recover when it runs its course
+    fn progress_infection(&mut self, dt: f64) {
+        if let InfectionState::Infected { ticks_left } = self.infection {
+            self.base.consume_energy(self.params.infection_energy_loss * dt);
+            self.infection = if ticks_left > 1 {
+                InfectionState::Infected { ticks_left: ticks_left - 1 }
+            } else {
+                InfectionState::Recovered
+            };
+        }
+    }
+
+    /// Whether the prey fled from a visible predator on its last update
+    /// (it raises an alarm call)
+    pub fn is_alarmed(&self) -> bool {
+        self.alarmed
+    }
+
+    /// Base energy regeneration rate (before crowding)
+    pub fn energy_regeneration(&self) -> f64 {
+        self.params.energy_regeneration
+    }
+
+    /// Change the base energy regeneration rate
+    pub fn set_energy_regeneration(&mut self, rate: f64) {
+        self.params.energy_regeneration = rate.max(0.0);
+    }
+
+    /// Create a new prey starting with `energy` instead of `initial_energy`
+    pub fn new_with_energy(id: AgentId, position: Vector2, params: PreyParameters, energy: f64) -> Self {
+        let mut agent = Self::new(id, position, params);
+        agent.set_energy(energy);
+        agent
+    }
+
+    /// Set the energy directly (clamped at zero)
+    pub fn set_energy(&mut self, energy: f64) {
+        self.base.energy = energy.max(0.0);
+    }
+
+    /// Set the velocity directly (limited to max speed)
+    pub fn set_velocity(&mut self, velocity: Vector2) {
+        self.base.set_velocity(velocity);
+    }
+
+    /// Ticks until the prey can dash again (0 = ready)
+    pub fn dash_cooldown_remaining(&self) -> u32 {
+        self.dash_cooldown_remaining
+    }
+
+    /// Fraction of the next budded offspring paid for so far
+    pub fn reproduction_progress(&self) -> f64 {
+        self.base.reproduction_progress
+    }
+
+    /// Remembered safe spot, if any
+    pub fn safe_spot(&self) -> Option<Vector2> {
+        self.safe_spot
+    }
+
+    /// Energy relative to the reproduction threshold, clamped to 0..1
+    fn energy_fraction(&self) -> f64 {
+        if self.params.reproduction_threshold <= 0.0 {
+            return 1.0;
+        }
+        (self.base.energy / self.params.reproduction_threshold).clamp(0.0, 1.0)
+    }
+
+    /// Shortest offset from this prey to `target` under the boundary topology
+    fn offset_to(&self, target: Vector2, world_state: &WorldState) -> Vector2 {
+        match world_state.boundary_type {
+            BoundaryType::Wraparound => torus_delta(&self.base.position, &target, world_state.width, world_state.height),
+            BoundaryType::Walls => target.subtract(&self.base.position),
+        }
+    }
+
+    /// Cohesion part of flocking: toward the center of the visible prey,
+    /// scaled by the energy curve (zero with no neighbors or cohesion off)
+    pub fn cohesion_steering(&self, world_state: &WorldState) -> Vector2 {
+        let neighbors = &world_state.nearby_prey;
+        if self.params.cohesion_weight <= 0.0 || neighbors.is_empty() {
+            return Vector2::zero();
+        }
+
+        let to_center = neighbors
+            .iter()
+            .fold(Vector2::zero(), |sum, n| sum.add(&self.offset_to(n.position, world_state)))
+            .scale(1.0 / neighbors.len() as f64);
+        let weight = self.params.cohesion_weight * self.params.cohesion_energy_curve.at(self.energy_fraction());
+        to_center.normalize().scale(self.base.max_speed * weight)
+    }
+
+    /// Steering away from stronger predator scent (zero where none is smelled)
+    fn scent_flee(&self, world_state: &WorldState) -> Vector2 {
+        let gradient = world_state.scent_gradient;
+        if self.params.scent_flee_weight <= 0.0 || gradient.magnitude_squared() == 0.0 {
+            return Vector2::zero();
+        }
+        gradient.normalize().scale(-self.base.max_speed * self.params.scent_flee_weight)
+    }
+
+    /// Boids steering among visible prey: cohesion, alignment and separation
+    fn flocking(&self, world_state: &WorldState) -> Vector2 {
+        let neighbors = &world_state.nearby_prey;
+        if neighbors.is_empty() {
+            return Vector2::zero();
+        }
+
+        let mut steering = self.cohesion_steering(world_state);
+
+        if self.params.alignment_weight > 0.0 {
+            let mean_velocity = neighbors
+                .iter()
+                .fold(Vector2::zero(), |sum, n| sum.add(&n.velocity))
+                .scale(1.0 / neighbors.len() as f64);
+            steering = steering.add(&mean_velocity.subtract(&self.base.velocity).scale(self.params.alignment_weight));
+        }
+
+        if self.params.separation_weight > 0.0 && self.params.separation_distance > 0.0 {
+            // Closer neighbors push harder
+            let push = neighbors
+                .iter()
+                .filter(|n| n.distance > 0.0 && n.distance < self.params.separation_distance)
+                .fold(Vector2::zero(), |sum, n| {
+                    let away = self.offset_to(n.position, world_state).normalize().scale(-1.0);
+                    sum.add(&away.scale(1.0 - n.distance / self.params.separation_distance))
+                });
+            steering = steering.add(&push.scale(self.base.max_speed * self.params.separation_weight));
+        }
+
+        steering
+    }
+
+    /// Calm-state drift: below comfort energy, steer back toward the safe spot;
+    /// otherwise (or with no spot yet) remember the current position as safe.
+    /// Returns the velocity to add (zero when home range is off).
+    fn home_range_pull(&mut self, world_state: &WorldState) -> Vector2 {
+        if self.params.home_range_weight <= 0.0 {
+            return Vector2::zero();
+        }
+
+        let position = self.base.position;
+        match self.safe_spot {
+            Some(spot) if self.base.energy < self.params.comfort_energy => {
+                let to_spot = self.offset_to(spot, world_state);
+                to_spot.normalize().scale(self.base.max_speed * self.params.home_range_weight)
+            }
+            _ => {
+                self.safe_spot = Some(position);
+                Vector2::zero()
+            }
+        }
+    }
+
+    /// Startle response: jump `dash_distance` straight away from a predator
+    /// inside the trigger range, if the dash is off cooldown
+    fn try_dash(&mut self, predator: &NeighborInfo, world_state: &WorldState, rng: &mut dyn RngCore) {
+        if self.params.dash_distance <= 0.0
+            || self.dash_cooldown_remaining > 0
+            || predator.distance > self.params.dash_trigger_range
+        {
+            return;
+        }
+
+        let direction = self.flee(predator.position, world_state, rng).normalize();
+        self.base.displace(direction.scale(self.params.dash_distance), world_state);
+        self.dash_cooldown_remaining = self.params.dash_cooldown;
     }
 
     /// Find the nearest predator within detection radius
-    fn find_nearest_predator(&self, world_state: &WorldState) -> Option<(AgentId, Vector2, f64)> {
+    fn find_nearest_predator(&self, world_state: &WorldState) -> Option<NeighborInfo> {
         world_state
             .nearby_predators
             .iter()
-            .min_by(|(_, _, d1), (_, _, d2)| d1.partial_cmp(d2).unwrap())
+            .min_by(|a, b| compare_neighbors(a, b))
             .copied()
     }
 
+    /// Regeneration rate after local competition: scales linearly down to zero
+    /// as the number of nearby prey approaches `density_cap`
+    fn effective_regeneration(&self, world_state: &WorldState) -> f64 {
+        if self.params.density_cap <= 0.0 {
+            return self.params.energy_regeneration;
+        }
+
+        let crowding = world_state.nearby_prey.len() as f64 / self.params.density_cap;
+        self.params.energy_regeneration * (1.0 - crowding).max(0.0)
+    }
+
+    /// Keep fleeing from a remembered threat at reduced urgency.
+    /// Returns false if there is nothing (left) to remember.
+    fn flee_remembered_threat(&mut self, world_state: &WorldState, rng: &mut dyn RngCore) -> bool {
+        let Some((threat_pos, ticks_left)) = self.last_threat else {
+            return false;
+        };
+
+        let flee_velocity = self.flee(threat_pos, world_state, rng).scale(REMEMBERED_THREAT_URGENCY);
+        self.base.set_velocity(flee_velocity);
+        self.last_threat = if ticks_left > 1 {
+            Some((threat_pos, ticks_left - 1))
+        } else {
+            None
+        };
+        true
+    }
+
     /// Calculate flee velocity away from a threat
-    fn flee(&self, threat: Vector2) -> Vector2 {
-        let away = self.base.position.subtract(&threat);
+    fn flee(&self, threat: Vector2, world_state: &WorldState, rng: &mut dyn RngCore) -> Vector2 {
+        let away = self.offset_to(threat, world_state).scale(-1.0);
         let distance = away.magnitude();
 
-        if distance > 0.0 {
+        let flee_velocity = if distance > 0.0 {
             // Normalize and scale by max speed
             away.normalize().scale(self.base.max_speed)
         } else {
             // If at same position, move in random direction
-            use rand::Rng;
-            let mut rng = rand::thread_rng();
             from_angle(rng.gen::<f64>() * std::f64::consts::PI * 2.0, self.base.max_speed)
+        };
+
+        self.avoid_walls(flee_velocity, world_state, rng)
+    }
+
+    /// How dangerous a visible predator is: closer and faster-approaching
+    /// predators weigh more; one receding fast enough weighs nothing
+    fn threat_weight(&self, predator: &NeighborInfo, world_state: &WorldState) -> f64 {
+        let toward_prey = self.offset_to(predator.position, world_state).scale(-1.0).normalize();
+        let approach_speed = predator.velocity.dot(&toward_prey);
+        let urgency = (1.0 + self.params.threat_speed_weight * approach_speed).max(0.0);
+        urgency / predator.distance.max(1.0)
+    }
+
+    /// Flee velocity away from every visible predator, each repulsion scaled by
+    /// its threat weight; falls back to fleeing `nearest` if they cancel out
+    fn flee_weighted_threats(&self, nearest: &NeighborInfo, world_state: &WorldState, rng: &mut dyn RngCore) -> Vector2 {
+        let away = world_state.nearby_predators.iter().fold(Vector2::zero(), |sum, predator| {
+            let direction = self.offset_to(predator.position, world_state).scale(-1.0).normalize();
+            sum.add(&direction.scale(self.threat_weight(predator, world_state)))
+        });
+        if away.magnitude() <= 0.0 {
+            return self.flee(nearest.position, world_state, rng);
+        }
+        self.avoid_walls(away.normalize().scale(self.base.max_speed), world_state, rng)
+    }
+
+    /// Under walls, drop the component of a flee velocity that presses into a
+    /// nearby wall so the prey dodges along it instead
+    fn avoid_walls(&self, flee_velocity: Vector2, world_state: &WorldState, rng: &mut dyn RngCore) -> Vector2 {
+        if !self.params.dodge_along_walls || world_state.boundary_type != BoundaryType::Walls {
+            return flee_velocity;
+        }
+
+        let pos = self.base.position;
+        let into_x = (pos.x <= WALL_MARGIN && flee_velocity.x < 0.0)
+            || (pos.x >= world_state.width - WALL_MARGIN && flee_velocity.x > 0.0);
+        let into_y = (pos.y <= WALL_MARGIN && flee_velocity.y < 0.0)
+            || (pos.y >= world_state.height - WALL_MARGIN && flee_velocity.y > 0.0);
+
+        // Not pressing into a wall, or pinned in a corner with nowhere to dodge
+        if into_x == into_y {
+            return flee_velocity;
+        }
+
+        let tangent = if into_x {
+            Vector2::new(0.0, flee_velocity.y)
+        } else {
+            Vector2::new(flee_velocity.x, 0.0)
+        };
+
+        if tangent.magnitude() > 0.0 {
+            tangent.normalize().scale(self.base.max_speed)
+        } else {
+            // Threat is dead ahead of the wall: pick a side at random
+            let side = if rng.gen::<bool>() { 1.0 } else { -1.0 };
+            if into_x {
+                Vector2::new(0.0, side * self.base.max_speed)
+            } else {
+                Vector2::new(side * self.base.max_speed, 0.0)
+            }
         }
     }
 }
@@ -73,7 +414,7 @@ impl Agent for Prey {
     }
 
     fn is_alive(&self) -> bool {
-        self.base.check_alive()
+        self.base.check_alive() && !self.base.outlived(self.params.max_age)
     }
 
     fn age(&self) -> u32 {
@@ -84,53 +425,75 @@ impl Agent for Prey {
         self.base.max_speed
     }
 
-    fn update(&mut self, world_state: &WorldState) -> AgentAction {
+    fn update_with_rng(&mut self, world_state: &WorldState, rng: &mut dyn RngCore) -> AgentAction {
         // Regenerate energy naturally
-        self.base.add_energy(self.params.energy_regeneration * world_state.dt);
+        self.base.add_energy(self.effective_regeneration(world_state) * world_state.dt);
+        self.progress_infection(world_state.dt);
+        self.ticks_since_fed = self.ticks_since_fed.saturating_add(1);
+        self.dash_cooldown_remaining = self.dash_cooldown_remaining.saturating_sub(1);
         self.base.increment_age();
 
-        // If dead, no action
-        if !self.base.check_alive() {
+        // If dead (starved or of old age), no action
+        if !self.is_alive() {
             return AgentAction::None;
         }
 
+        self.alarmed = false;
+
         // Check for nearby predators
-        if let Some((_, predator_pos, distance)) = self.find_nearest_predator(world_state) {
+        if let Some(predator) = self.find_nearest_predator(world_state) {
+            self.try_dash(&predator, world_state, rng);
+
             // If predator is within flee distance, flee
-            if distance <= self.params.flee_distance {
-                let flee_velocity = self.flee(predator_pos);
+            if predator.distance <= self.params.flee_distance {
+                let flee_velocity = if self.params.threat_speed_weight > 0.0 {
+                    self.flee_weighted_threats(&predator, world_state, rng)
+                } else {
+                    self.flee(predator.position, world_state, rng)
+                };
                 self.base.set_velocity(flee_velocity);
+                self.alarmed = true;
                 // Extra energy loss when fleeing
                 self.base.consume_energy(self.params.energy_loss_fleeing * world_state.dt);
-            } else {
-                // Predator nearby but not immediate threat - slow movement
-                self.base.set_velocity(self.base.velocity.scale(0.9));
+                // Remember the threat for a few ticks after losing sight of it
+                if self.params.memory_ticks > 0 {
+                    self.last_threat = Some((predator.position, self.params.memory_ticks));
+                }
+            } else if !self.flee_remembered_threat(world_state, rng) {
+                // Predator nearby but not immediate threat - slow movement,
+                // closing ranks with the herd
+                let flock = self.flocking(world_state);
+                self.base.set_velocity(self.base.velocity.scale(0.9).add(&flock));
             }
-        } else {
-            // No predators nearby - can move more freely (simple wander or slow down)
-            self.base.set_velocity(self.base.velocity.scale(0.95));
-        }
-
-        // Check for reproduction
-        if self.base.energy >= self.params.reproduction_threshold {
-            // Spawn near current position with some random offset
-            use rand::Rng;
-            let mut rng = rand::thread_rng();
-            let angle = rng.gen::<f64>() * std::f64::consts::PI * 2.0;
-            let distance = rng.gen::<f64>() * 20.0;
-            let offset = from_angle(angle, distance);
-            let spawn_pos = self.base.position.add(&offset);
-            
-            // Clamp spawn position to world bounds
-            let spawn_pos = Vector2 {
-                x: spawn_pos.x.max(10.0).min(world_state.width - 10.0),
-                y: spawn_pos.y.max(10.0).min(world_state.height - 10.0),
-            };
-            
-            self.base.consume_energy(self.params.reproduction_cost);
+        } else if !self.flee_remembered_threat(world_state, rng) {
+            // No predators nearby - can move more freely (wander or slow down),
+            // drifting back home if it has a home range, keeping with the herd
+            // and shying away from any predator scent
+            let pull = self.home_range_pull(world_state);
+            let flock = self.flocking(world_state);
+            let scent = self.scent_flee(world_state);
+            let idle = self.base.idle_velocity(self.params.wander_strength, rng);
+            self.base.set_velocity(idle.add(&pull).add(&flock).add(&scent));
+        }
+
+        // Check for reproduction (stored energy alone isn't enough without recent food,
+        // juveniles can't reproduce at all, and crowds may suppress breeding)
+        let breeding_probability = self.breeding_probability(world_state);
+        if self.is_well_fed()
+            && self.base.age >= self.params.maturity_age
+            && (breeding_probability >= 1.0 || rng.gen::<f64>() < breeding_probability)
+            && self.base.try_reproduce(
+                self.params.reproduction_strategy,
+                self.params.reproduction_threshold,
+                self.params.budding_threshold,
+                self.params.reproduction_cost,
+            )
+        {
+            // Spawn near current position
+            let spawn_pos = self.base.offspring_position(world_state, rng);
             return AgentAction::Reproduce {
                 position: spawn_pos,
-                energy: self.params.initial_energy,
+                energy: self.params.newborn_energy(),
             };
         }
 
@@ -0,0 +1,337 @@
+// Headless simulation running (no GUI)
+
+use std::io::Write;
+use std::time::Instant;
+use crate::config::parameters::Parameters;
+use crate::simulation::world::World;
+use crate::simulation::snapshot::{CheckpointConfig, Checkpointer};
+use crate::ui::statistics::Statistics;
+use crate::utils::export::RunSummary;
+
+/// Why a headless run stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The requested number of ticks was reached
+    ReachedStopTick,
+    /// A species present at the start of the run died out
+    Extinction,
+}
+
+/// Result of a headless run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunOutcome {
+    /// Number of updates performed by this run
+    pub ticks: u64,
+    pub reason: StopReason,
+}
+
+impl RunOutcome {
+    /// Human-readable status line
+    pub fn status(&self) -> String {
+        match self.reason {
+            StopReason::ReachedStopTick => format!("Reached stop tick after {} ticks", self.ticks),
+            StopReason::Extinction => format!("Stopped early on extinction after {} ticks", self.ticks),
+        }
+    }
+}
+
+/// Category a finished run falls into, for labelling datasets of runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// Both species are still alive and neither is pinned against the agent cap
+    StableCoexistence,
+    /// Prey died out while predators survive
+    PreyExtinction,
+    /// Predators died out while prey survive
+    PredatorExtinction,
+    /// Neither species survived
+    BothExtinct,
+    /// Both species survive, but the population ran into the agent cap
+    Explosion,
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            Outcome::StableCoexistence => "stable coexistence",
+            Outcome::PreyExtinction => "prey extinction",
+            Outcome::PredatorExtinction => "predator extinction",
+            Outcome::BothExtinct => "both extinct",
+            Outcome::Explosion => "explosion (cap-bound)",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Thresholds `classify_outcome_with` labels runs by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutcomeThresholds {
+    /// Agent cap the run was limited by
+    pub agent_cap: usize,
+    /// A population counts as cap-bound once it reaches this fraction of `agent_cap`
+    pub cap_fraction: f64,
+}
+
+impl Default for OutcomeThresholds {
+    fn default() -> Self {
+        Self::for_parameters(&Parameters::default())
+    }
+}
+
+impl OutcomeThresholds {
+    /// Thresholds for a run with the given parameters (cap from `max_agents`)
+    pub fn for_parameters(params: &Parameters) -> Self {
+        Self {
+            agent_cap: params.simulation.max_agents as usize,
+            cap_fraction: 0.95,
+        }
+    }
+
+    /// Smallest population that counts as cap-bound
+    fn cap_bound(&self) -> f64 {
+        self.agent_cap as f64 * self.cap_fraction
+    }
+}
+
+/// Label a run with the default thresholds (see `classify_outcome_with`)
+pub fn classify_outcome(stats: &Statistics, summary: &RunSummary) -> Outcome {
+    classify_outcome_with(stats, summary, &OutcomeThresholds::default())
+}
+
+/// Label a run from its final populations and trajectory statistics.
+/// Extinctions are decided by the final populations alone. A run where both
+/// species survive is an explosion if the final total, or the peak of either
+/// species, reached `cap_fraction` of the agent cap; otherwise it is stable
+/// coexistence.
+pub fn classify_outcome_with(stats: &Statistics, summary: &RunSummary, thresholds: &OutcomeThresholds) -> Outcome {
+    match (summary.final_predators, summary.final_prey) {
+        (0, 0) => Outcome::BothExtinct,
+        (_, 0) => Outcome::PreyExtinction,
+        (0, _) => Outcome::PredatorExtinction,
+        (predators, prey) => {
+            let peak = stats.max_predators.max(stats.max_prey);
+            let cap_bound = thresholds.cap_bound();
+            if (predators + prey) as f64 >= cap_bound || peak as f64 >= cap_bound {
+                Outcome::Explosion
+            } else {
+                Outcome::StableCoexistence
+            }
+        }
+    }
+}
+
+/// Sustained simulation performance measured by `stress_test`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StressReport {
+    /// Agents alive when timing started
+    pub agents: usize,
+    /// Number of timed updates
+    pub ticks: u64,
+    pub ticks_per_second: f64,
+    /// Mean wall-clock time of one update, in milliseconds
+    pub mean_tick_ms: f64,
+}
+
+impl StressReport {
+    /// Human-readable status line
+    pub fn status(&self) -> String {
+        format!(
+            "{} agents: {:.1} ticks/s ({:.3} ms/tick over {} ticks)",
+            self.agents, self.ticks_per_second, self.mean_tick_ms, self.ticks
+        )
+    }
+}
+
+/// Top the world up to `max_agents`, split evenly between predators and prey.
+/// Returns the number of agents spawned.
+pub fn fill_to_max_agents(world: &mut World) -> u32 {
+    let room = (world.parameters().simulation.max_agents as usize).saturating_sub(world.total_agents()) as u32;
+    let predators = world.spawn_predators(room / 2);
+    predators + world.spawn_prey(room - predators)
+}
+
+/// Fill the world to `max_agents` and time `ticks` updates, to find the
+/// performance ceiling of the machine
+pub fn stress_test(world: &mut World, ticks: u64) -> StressReport {
+    fill_to_max_agents(world);
+    let agents = world.total_agents();
+
+    let start = Instant::now();
+    for _ in 0..ticks {
+        world.update();
+    }
+    let seconds = start.elapsed().as_secs_f64().max(f64::MIN_POSITIVE);
+
+    StressReport {
+        agents,
+        ticks,
+        ticks_per_second: ticks as f64 / seconds,
+        mean_tick_ms: if ticks > 0 { seconds * 1000.0 / ticks as f64 } else { 0.0 },
+    }
+}
+
+/// Drives a `World` without any GUI, for scripts, CI and servers
+#[derive(Clone)]
+pub struct Simulation {
+    world: World,
+}
+
+impl Simulation {
+    /// Start a fresh world with `params`
+    pub fn new(params: Parameters) -> Self {
+        Self::from_world(World::new(params))
+    }
+
+    /// Drive an existing world
+    pub fn from_world(world: World) -> Self {
+        Self { world }
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    pub fn into_world(self) -> World {
+        self.world
+    }
+
+    /// Advance exactly `ticks` updates
+    pub fn run_for(&mut self, ticks: u64) {
+        for _ in 0..ticks {
+            self.world.update();
+        }
+    }
+
+    /// Advance until `done` holds for the world, checking before every
+    /// update, and return the number of updates made. Never returns if
+    /// `done` never holds.
+    pub fn run_until(&mut self, mut done: impl FnMut(&World) -> bool) -> u64 {
+        let mut ticks = 0;
+        while !done(&self.world) {
+            self.world.update();
+            ticks += 1;
+        }
+        ticks
+    }
+}
+
+/// Advance the world by up to `max_ticks` updates, stopping early if a
+/// species that was present at the start goes extinct
+pub fn run_headless(world: &mut World, max_ticks: u64) -> RunOutcome {
+    run_headless_with(world, max_ticks, |_| {})
+}
+
+/// Like `run_headless`, but calls `on_tick` with the world after every update
+/// (e.g. to dump frames or log per-tick output)
+pub fn run_headless_with(
+    world: &mut World,
+    max_ticks: u64,
+    mut on_tick: impl FnMut(&World),
+) -> RunOutcome {
+    let had_predators = world.predator_count() > 0;
+    let had_prey = world.prey_count() > 0;
+
+    for tick in 0..max_ticks {
+        world.update();
+        on_tick(world);
+
+        let extinct = (had_predators && world.predator_count() == 0)
+            || (had_prey && world.prey_count() == 0);
+        if extinct {
+            return RunOutcome {
+                ticks: tick + 1,
+                reason: StopReason::Extinction,
+            };
+        }
+    }
+
+    RunOutcome {
+        ticks: max_ticks,
+        reason: StopReason::ReachedStopTick,
+    }
+}
+
+/// Like `run_headless`, but writes every agent's state after each update to
+/// a JSON Lines file (see `World::dump_agents_jsonl`), replacing any earlier
+/// contents so two runs never mix in one file
+pub fn run_headless_to_jsonl(world: &mut World, max_ticks: u64, path: &str) -> std::io::Result<RunOutcome> {
+    let file = std::fs::File::create(path)?;
+    let mut writer = std::io::BufWriter::new(file);
+    let mut result = Ok(());
+
+    let outcome = run_headless_with(world, max_ticks, |world| {
+        if result.is_ok() {
+            result = writer.write_all(world.dump_agents_jsonl().as_bytes());
+        }
+    });
+
+    result?;
+    writer.flush()?;
+    Ok(outcome)
+}
+
+/// Like `run_headless`, but writes a numbered snapshot every
+/// `checkpoint.interval` ticks, keeping only the last `checkpoint.keep_last`
+pub fn run_headless_with_checkpoints(
+    world: &mut World,
+    max_ticks: u64,
+    checkpoint: CheckpointConfig,
+) -> Result<RunOutcome, Box<dyn std::error::Error>> {
+    let mut checkpointer = Checkpointer::new(checkpoint)?;
+    let mut result = Ok(());
+
+    let outcome = run_headless_with(world, max_ticks, |world| {
+        if result.is_ok() {
+            result = checkpointer.maybe_write(world).map(|_| ());
+        }
+    });
+
+    result?;
+    Ok(outcome)
+}
+
+/// Like `run_headless`, but broadcasts a state frame to `server`'s clients
+/// after every update, sleeping `pace` between updates (zero = full speed)
+#[cfg(feature = "net")]
+pub fn run_headless_serving(
+    world: &mut World,
+    max_ticks: u64,
+    server: &mut crate::utils::net::StateServer,
+    pace: std::time::Duration,
+) -> std::io::Result<RunOutcome> {
+    let mut result = Ok(());
+
+    let outcome = run_headless_with(world, max_ticks, |world| {
+        if result.is_ok() {
+            result = server.broadcast(world);
+        }
+        if !pace.is_zero() {
+            std::thread::sleep(pace);
+        }
+    });
+
+    result?;
+    Ok(outcome)
+}
+
+/// Run two parameter sets from the same seed for up to `ticks` updates each
+/// (stopping early on extinction) and summarize both, for A/B comparisons
+pub fn compare(params_a: Parameters, params_b: Parameters, seed: u64, ticks: u64) -> (RunSummary, RunSummary) {
+    (run_seeded(params_a, seed, ticks), run_seeded(params_b, seed, ticks))
+}
+
+/// Headless run of a fresh world seeded with `seed`
+fn run_seeded(mut params: Parameters, seed: u64, ticks: u64) -> RunSummary {
+    params.simulation.seed = Some(seed);
+    let mut world = World::new(params);
+    let outcome = run_headless(&mut world, ticks);
+    RunSummary {
+        ticks: outcome.ticks,
+        final_predators: world.predator_count(),
+        final_prey: world.prey_count(),
+    }
+}
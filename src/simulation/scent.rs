@@ -0,0 +1,110 @@
+// Predator scent trails on a coarse grid
+
+use serde::{Deserialize, Serialize};
+use crate::utils::math::Vector2;
+
+/// Scent per grid cell: deposited where predators walk, fading everywhere each tick
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScentField {
+    cols: usize,
+    rows: usize,
+    cell_size: f64,
+    values: Vec<f64>,
+}
+
+impl ScentField {
+    /// Scentless field covering a `width` x `height` world
+    pub fn new(width: f64, height: f64, cell_size: f64) -> Self {
+        let mut field = Self::default();
+        field.fit(width, height, cell_size);
+        field
+    }
+
+    /// Reshape (and clear) the grid if the world or cell size changed
+    pub fn fit(&mut self, width: f64, height: f64, cell_size: f64) {
+        let cell_size = cell_size.max(1.0);
+        let cols = (width / cell_size).ceil().max(1.0) as usize;
+        let rows = (height / cell_size).ceil().max(1.0) as usize;
+        if cols != self.cols || rows != self.rows || cell_size != self.cell_size {
+            *self = Self { cols, rows, cell_size, values: vec![0.0; cols * rows] };
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    /// Scent in cell (col, row)
+    pub fn value(&self, col: usize, row: usize) -> f64 {
+        self.values[row * self.cols + col]
+    }
+
+    /// Strongest scent anywhere (0 for an empty field)
+    pub fn max(&self) -> f64 {
+        self.values.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// Cell containing `pos`, clamped into the grid (None for an empty field)
+    fn cell_of(&self, pos: Vector2) -> Option<(usize, usize)> {
+        if self.values.is_empty() {
+            return None;
+        }
+        let col = ((pos.x / self.cell_size).floor().max(0.0) as usize).min(self.cols - 1);
+        let row = ((pos.y / self.cell_size).floor().max(0.0) as usize).min(self.rows - 1);
+        Some((col, row))
+    }
+
+    /// Scent in the cell containing `pos`
+    pub fn at(&self, pos: Vector2) -> f64 {
+        self.cell_of(pos).map_or(0.0, |(col, row)| self.value(col, row))
+    }
+
+    /// Add `amount` of scent to the cell containing `pos`
+    pub fn deposit(&mut self, pos: Vector2, amount: f64) {
+        if let Some((col, row)) = self.cell_of(pos) {
+            self.values[row * self.cols + col] += amount;
+        }
+    }
+
+    /// Keep `retention` (0..1) of the scent in every cell
+    pub fn decay(&mut self, retention: f64) {
+        let retention = retention.clamp(0.0, 1.0);
+        for value in &mut self.values {
+            *value *= retention;
+        }
+    }
+
+    /// Remove all scent
+    pub fn clear(&mut self) {
+        self.values.fill(0.0);
+    }
+
+    /// Central-difference scent gradient at `pos` (per unit distance), pointing
+    /// toward stronger scent. With `wrap` the grid's edges meet as on a torus;
+    /// otherwise the edge cell stands in for its missing neighbor.
+    pub fn gradient(&self, pos: Vector2, wrap: bool) -> Vector2 {
+        let Some((col, row)) = self.cell_of(pos) else {
+            return Vector2::zero();
+        };
+        let step = |index: usize, len: usize, forward: bool| -> usize {
+            match (forward, wrap) {
+                (true, true) => (index + 1) % len,
+                (false, true) => (index + len - 1) % len,
+                (true, false) => (index + 1).min(len - 1),
+                (false, false) => index.saturating_sub(1),
+            }
+        };
+        let span = 2.0 * self.cell_size;
+        let dx = self.value(step(col, self.cols, true), row) - self.value(step(col, self.cols, false), row);
+        let dy = self.value(col, step(row, self.rows, true)) - self.value(col, step(row, self.rows, false));
+        Vector2::new(dx / span, dy / span)
+    }
+}
@@ -0,0 +1,111 @@
+// World snapshots (save/restore full simulation state) and checkpointing
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::config::parameters::{Obstacle, Parameters};
+use crate::simulation::predator::Predator;
+use crate::simulation::prey::Prey;
+use crate::simulation::scent::ScentField;
+use crate::simulation::world::World;
+use crate::utils::math::Vector2;
+use crate::utils::rng::WorldRng;
+
+/// Complete simulation state at one tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub tick: u64,
+    /// Sequence number the next allocated agent id will carry
+    pub next_id: u64,
+    pub params: Parameters,
+    pub predators: Vec<Predator>,
+    pub prey: Vec<Prey>,
+    /// Recent prey alarm calls: (position, ticks left)
+    pub alarms: Vec<(Vector2, u32)>,
+    pub food: Vec<Vector2>,
+    /// World RNG state, so a restored world draws the same numbers
+    /// (None in older snapshots: reseeded from the parameters)
+    #[serde(default)]
+    pub rng: Option<WorldRng>,
+    #[serde(default)]
+    pub freeze_predators: bool,
+    #[serde(default)]
+    pub freeze_prey: bool,
+    /// Auto-balance multiplier of prey regeneration
+    #[serde(default = "default_regeneration_factor")]
+    pub regeneration_factor: f64,
+    /// Obstacles placed by the world's obstacle generator
+    #[serde(default)]
+    pub generated_obstacles: Vec<Obstacle>,
+    /// Predator scent trails
+    #[serde(default)]
+    pub scent: ScentField,
+}
+
+fn default_regeneration_factor() -> f64 {
+    1.0
+}
+
+impl WorldSnapshot {
+    /// Write the snapshot as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Read a snapshot written by `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// Periodic snapshot schedule for long runs: every `interval` ticks a
+/// numbered snapshot is written to `dir`, keeping only the last `keep_last`
+#[derive(Debug, Clone)]
+pub struct CheckpointConfig {
+    pub dir: PathBuf,
+    pub interval: u64,
+    pub keep_last: usize,
+}
+
+/// Writes and rotates checkpoints according to a `CheckpointConfig`
+pub struct Checkpointer {
+    config: CheckpointConfig,
+    written: VecDeque<PathBuf>,
+}
+
+impl Checkpointer {
+    /// Create a checkpointer, creating its directory if needed
+    pub fn new(config: CheckpointConfig) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&config.dir)?;
+        Ok(Self {
+            config,
+            written: VecDeque::new(),
+        })
+    }
+
+    /// File a checkpoint for the given tick is written to
+    pub fn path_for(&self, tick: u64) -> PathBuf {
+        self.config.dir.join(format!("checkpoint_{:08}.json", tick))
+    }
+
+    /// Write a checkpoint if the world's tick is on the interval, removing the
+    /// oldest ones beyond `keep_last`. Returns the path written, if any.
+    pub fn maybe_write(&mut self, world: &World) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+        if self.config.interval == 0 || !world.tick().is_multiple_of(self.config.interval) {
+            return Ok(None);
+        }
+
+        let path = self.path_for(world.tick());
+        world.snapshot().save(&path)?;
+        self.written.push_back(path.clone());
+
+        while self.written.len() > self.config.keep_last {
+            if let Some(old) = self.written.pop_front() {
+                std::fs::remove_file(old)?;
+            }
+        }
+        Ok(Some(path))
+    }
+}
@@ -0,0 +1,342 @@
+// Spatial partitioning helpers
+
+use crate::utils::math::Vector2;
+
+/// Map a world position to its (col, row) cell on a `cols` x `rows` grid
+/// covering the world rectangle. Positions on or past the far edges are
+/// clamped into the last cell.
+pub fn cell_index(pos: Vector2, cols: usize, rows: usize, width: f64, height: f64) -> (usize, usize) {
+    let col = ((pos.x / width) * cols as f64).floor().max(0.0) as usize;
+    let row = ((pos.y / height) * rows as f64).floor().max(0.0) as usize;
+    (col.min(cols.saturating_sub(1)), row.min(rows.saturating_sub(1)))
+}
+
+/// Uniform bucket grid over a toroidal world for neighbor queries.
+/// Cells live in one dense `Vec` indexed `row * cols + col` and hold the
+/// indices of the positions inserted into them. With cells at least as large
+/// as the query radius, a query only visits the 3x3 block around it.
+#[derive(Debug, Clone, Default)]
+pub struct SpatialGrid {
+    cols: usize,
+    rows: usize,
+    width: f64,
+    height: f64,
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialGrid {
+    /// Build a grid over `positions` with cells no smaller than `cell_size`
+    pub fn new(positions: impl IntoIterator<Item = Vector2>, width: f64, height: f64, cell_size: f64) -> Self {
+        let mut grid = Self::default();
+        grid.rebuild(positions, width, height, cell_size);
+        grid
+    }
+
+    /// Refill the grid in place, keeping the cell allocations where possible
+    pub fn rebuild(&mut self, positions: impl IntoIterator<Item = Vector2>, width: f64, height: f64, cell_size: f64) {
+        let cells_along = |extent: f64| {
+            if cell_size > 0.0 {
+                ((extent / cell_size).floor() as usize).max(1)
+            } else {
+                1
+            }
+        };
+        self.cols = cells_along(width);
+        self.rows = cells_along(height);
+        self.width = width;
+        self.height = height;
+
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        self.cells.resize_with(self.cols * self.rows, Vec::new);
+
+        for (index, pos) in positions.into_iter().enumerate() {
+            let (col, row) = cell_index(pos, self.cols, self.rows, width, height);
+            self.cells[row * self.cols + col].push(index);
+        }
+    }
+
+    /// Call `f` with the index of every position that may lie within `radius`
+    /// of `pos` (torus distance); each index is visited at most once. Callers
+    /// filter by exact distance.
+    pub fn for_each_candidate(&self, pos: Vector2, radius: f64, mut f: impl FnMut(usize)) {
+        if self.cells.is_empty() {
+            return;
+        }
+        let (col, row) = cell_index(pos, self.cols, self.rows, self.width, self.height);
+        let reach_cols = Self::reach(radius, self.width / self.cols as f64, self.cols);
+        let reach_rows = Self::reach(radius, self.height / self.rows as f64, self.rows);
+
+        for dr in 0..(2 * reach_rows + 1).min(self.rows) {
+            let r = (row + self.rows - reach_rows + dr) % self.rows;
+            for dc in 0..(2 * reach_cols + 1).min(self.cols) {
+                let c = (col + self.cols - reach_cols + dc) % self.cols;
+                for &index in &self.cells[r * self.cols + c] {
+                    f(index);
+                }
+            }
+        }
+    }
+
+    /// Number of neighboring cells (per side) a radius can reach into
+    fn reach(radius: f64, cell_extent: f64, cells: usize) -> usize {
+        ((radius / cell_extent).ceil().max(0.0) as usize).min(cells)
+    }
+}
+
+/// Deepest a quadtree subdivides; stops coincident points splitting forever
+const QUADTREE_MAX_DEPTH: usize = 16;
+
+/// Node of a `QuadTree`: a leaf holding point indices, or four children
+#[derive(Debug, Clone)]
+struct QuadNode {
+    min: Vector2,
+    max: Vector2,
+    /// Index of the first of four consecutive child nodes
+    children: Option<usize>,
+    items: Vec<usize>,
+}
+
+impl QuadNode {
+    fn leaf(min: Vector2, max: Vector2) -> Self {
+        Self { min, max, children: None, items: Vec::new() }
+    }
+
+    fn overlaps(&self, min: Vector2, max: Vector2) -> bool {
+        self.min.x <= max.x && min.x <= self.max.x && self.min.y <= max.y && min.y <= self.max.y
+    }
+}
+
+/// Point quadtree over a toroidal world for neighbor queries. Leaves split
+/// into quadrants once they hold more than `capacity` points, so dense
+/// clumps get fine cells while empty space stays one coarse node.
+#[derive(Debug, Clone, Default)]
+pub struct QuadTree {
+    width: f64,
+    height: f64,
+    capacity: usize,
+    nodes: Vec<QuadNode>,
+    /// Inserted positions clamped into the world, indexed like the input
+    points: Vec<Vector2>,
+}
+
+impl QuadTree {
+    /// Build a tree over `positions` with at most `capacity` points per leaf
+    pub fn new(positions: impl IntoIterator<Item = Vector2>, width: f64, height: f64, capacity: usize) -> Self {
+        let mut tree = Self::default();
+        tree.rebuild(positions, width, height, capacity);
+        tree
+    }
+
+    /// Empty the tree and refill it, keeping the node and point allocations
+    pub fn rebuild(&mut self, positions: impl IntoIterator<Item = Vector2>, width: f64, height: f64, capacity: usize) {
+        self.width = width;
+        self.height = height;
+        self.capacity = capacity.max(1);
+        self.nodes.clear();
+        self.nodes.push(QuadNode::leaf(Vector2::zero(), Vector2::new(width, height)));
+        self.points.clear();
+        for pos in positions {
+            self.insert(pos);
+        }
+    }
+
+    /// Add a point and return its index (one past the previous point)
+    pub fn insert(&mut self, pos: Vector2) -> usize {
+        let index = self.points.len();
+        self.points.push(Vector2::new(pos.x.clamp(0.0, self.width), pos.y.clamp(0.0, self.height)));
+        if self.nodes.is_empty() {
+            self.nodes.push(QuadNode::leaf(Vector2::zero(), Vector2::new(self.width, self.height)));
+        }
+        let (mut node, mut depth) = (0, 0);
+        while let Some(first) = self.nodes[node].children {
+            node = first + self.quadrant(node, self.points[index]);
+            depth += 1;
+        }
+        self.nodes[node].items.push(index);
+        self.split_if_full(node, depth);
+        index
+    }
+
+    /// Drop point `index` from its leaf so queries no longer report it.
+    /// Returns false if it was never inserted or is already removed. Indices
+    /// are not reused and emptied leaves are not merged until `rebuild`.
+    pub fn remove(&mut self, index: usize) -> bool {
+        let Some(&pos) = self.points.get(index) else {
+            return false;
+        };
+        let mut node = 0;
+        while let Some(first) = self.nodes[node].children {
+            node = first + self.quadrant(node, pos);
+        }
+        let items = &mut self.nodes[node].items;
+        match items.iter().position(|&i| i == index) {
+            Some(slot) => {
+                items.swap_remove(slot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of nodes, leaves and branches alike
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Call `f` with the index of every point that may lie within `radius`
+    /// of `pos` (torus distance); each index is visited at most once. Callers
+    /// filter by exact distance.
+    pub fn for_each_candidate(&self, pos: Vector2, radius: f64, mut f: impl FnMut(usize)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        // The query square, cut where it crosses a world edge into disjoint
+        // pieces that each lie inside the world
+        let xs = Self::wrapped_spans(pos.x, radius, self.width);
+        let ys = Self::wrapped_spans(pos.y, radius, self.height);
+        let mut stack = Vec::new();
+        for &(x0, x1) in &xs {
+            for &(y0, y1) in &ys {
+                let (min, max) = (Vector2::new(x0, y0), Vector2::new(x1, y1));
+                stack.push(0);
+                while let Some(node) = stack.pop() {
+                    let node = &self.nodes[node];
+                    if !node.overlaps(min, max) {
+                        continue;
+                    }
+                    match node.children {
+                        Some(first) => stack.extend(first..first + 4),
+                        None => {
+                            for &index in &node.items {
+                                let p = self.points[index];
+                                if p.x >= x0 && p.x <= x1 && p.y >= y0 && p.y <= y1 {
+                                    f(index);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// `[center - radius, center + radius]` folded onto `[0, extent]` as at
+    /// most two disjoint spans
+    fn wrapped_spans(center: f64, radius: f64, extent: f64) -> Vec<(f64, f64)> {
+        let (low, high) = (center - radius, center + radius);
+        if high - low >= extent {
+            vec![(0.0, extent)]
+        } else if low < 0.0 {
+            vec![(0.0, high), (low + extent, extent)]
+        } else if high > extent {
+            vec![(low, extent), (0.0, high - extent)]
+        } else {
+            vec![(low, high)]
+        }
+    }
+
+    /// Which child (0..4, x-major) of `node` contains `pos`
+    fn quadrant(&self, node: usize, pos: Vector2) -> usize {
+        let node = &self.nodes[node];
+        let mid_x = (node.min.x + node.max.x) / 2.0;
+        let mid_y = (node.min.y + node.max.y) / 2.0;
+        usize::from(pos.x >= mid_x) + 2 * usize::from(pos.y >= mid_y)
+    }
+
+    /// Turn leaf `node` into a branch if it holds more than `capacity`
+    /// points, handing them to four new leaves that split in turn
+    fn split_if_full(&mut self, node: usize, depth: usize) {
+        if self.nodes[node].items.len() <= self.capacity.max(1) || depth >= QUADTREE_MAX_DEPTH {
+            return;
+        }
+        let QuadNode { min, max, .. } = self.nodes[node];
+        let mid = Vector2::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+        let first = self.nodes.len();
+        self.nodes.push(QuadNode::leaf(min, mid));
+        self.nodes.push(QuadNode::leaf(Vector2::new(mid.x, min.y), Vector2::new(max.x, mid.y)));
+        self.nodes.push(QuadNode::leaf(Vector2::new(min.x, mid.y), Vector2::new(mid.x, max.y)));
+        self.nodes.push(QuadNode::leaf(mid, max));
+
+        let items = std::mem::take(&mut self.nodes[node].items);
+        self.nodes[node].children = Some(first);
+        for index in items {
+            let child = first + self.quadrant(node, self.points[index]);
+            self.nodes[child].items.push(index);
+        }
+        for child in first..first + 4 {
+            self.split_if_full(child, depth + 1);
+        }
+    }
+}
+
+/// Count positions per cell. The result is indexed `grid[row][col]`.
+pub fn density_grid(
+    positions: impl IntoIterator<Item = Vector2>,
+    cols: usize,
+    rows: usize,
+    width: f64,
+    height: f64,
+) -> Vec<Vec<usize>> {
+    let mut grid = vec![vec![0; cols]; rows];
+    if cols == 0 || rows == 0 {
+        return grid;
+    }
+
+    for pos in positions {
+        let (col, row) = cell_index(pos, cols, rows, width, height);
+        grid[row][col] += 1;
+    }
+    grid
+}
+
+/// Moran's I spatial autocorrelation of presence (cell occupied or not) on a
+/// grid indexed `grid[row][col]`, with rook (4-neighbour) adjacency weights.
+/// With `wrap` the grid edges are adjacent, as on a torus. Positive values
+/// mean occupied cells cluster, values near zero mean a random pattern.
+/// Returns 0.0 when presence does not vary (empty, full or degenerate grid).
+pub fn morans_i(grid: &[Vec<usize>], wrap: bool) -> f64 {
+    let rows = grid.len();
+    let cols = grid.first().map_or(0, |row| row.len());
+    let n = rows * cols;
+    if n < 2 {
+        return 0.0;
+    }
+
+    let presence = |row: usize, col: usize| if grid[row][col] > 0 { 1.0 } else { 0.0 };
+    let mean = grid.iter().flatten().filter(|&&count| count > 0).count() as f64 / n as f64;
+
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    let mut weight_sum = 0.0;
+    for row in 0..rows {
+        for col in 0..cols {
+            let deviation = presence(row, col) - mean;
+            denominator += deviation * deviation;
+
+            let mut neighbors = Vec::with_capacity(4);
+            if wrap {
+                neighbors.push(((row + rows - 1) % rows, col));
+                neighbors.push(((row + 1) % rows, col));
+                neighbors.push((row, (col + cols - 1) % cols));
+                neighbors.push((row, (col + 1) % cols));
+            } else {
+                if row > 0 { neighbors.push((row - 1, col)); }
+                if row + 1 < rows { neighbors.push((row + 1, col)); }
+                if col > 0 { neighbors.push((row, col - 1)); }
+                if col + 1 < cols { neighbors.push((row, col + 1)); }
+            }
+
+            for (r, c) in neighbors.into_iter().filter(|&cell| cell != (row, col)) {
+                numerator += deviation * (presence(r, c) - mean);
+                weight_sum += 1.0;
+            }
+        }
+    }
+
+    if denominator == 0.0 || weight_sum == 0.0 {
+        return 0.0;
+    }
+    (n as f64 / weight_sum) * (numerator / denominator)
+}
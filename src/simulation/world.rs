@@ -2,162 +2,736 @@
 
 use crate::simulation::agent::*;
 use crate::simulation::predator::Predator;
-use crate::simulation::prey::Prey;
+use crate::simulation::prey::{InfectionState, Prey};
 use crate::config::parameters::*;
-use crate::utils::math::{Vector2, distance_torus};
+use crate::simulation::scent::ScentField;
+use crate::simulation::snapshot::WorldSnapshot;
+use crate::simulation::spatial::{QuadTree, SpatialGrid};
+use crate::utils::rng::{RngAlgorithm, WorldRng};
+use crate::utils::math::{Vector2, clamp_position, distance, distance_torus, distance_torus_squared, fnv1a_64, gaussian, torus_mean, wrap_position};
+
+/// Number of ticks a prey alarm call stays audible to predators
+const ALARM_DURATION_TICKS: u32 = 30;
+
+/// Attempts at finding a free (obstacle-less) spawn position before giving up
+const MAX_SPAWN_ATTEMPTS: u32 = 100;
+
+/// Distance within which a prey eats a food item
+const FOOD_EAT_DISTANCE: f64 = 5.0;
+
+/// Upper bound on food items lying around at once
+const MAX_FOOD_ITEMS: usize = 1000;
+
+/// Bounds on the auto-balance multiplier of prey energy regeneration
+const MIN_REGENERATION_FACTOR: f64 = 0.1;
+const MAX_REGENERATION_FACTOR: f64 = 10.0;
+
+/// Grid resolution (cells per side) used for Moran's I
+const MORANS_I_GRID_CELLS: usize = 10;
+
+/// Per-tick working buffers, kept between ticks so `World::update` doesn't
+/// allocate in steady state
+#[derive(Clone, Default)]
+struct ScratchBuffers {
+    predator_states: Vec<WorldState>,
+    prey_states: Vec<WorldState>,
+    predator_actions: Vec<(usize, AgentAction)>,
+    prey_actions: Vec<(usize, AgentAction)>,
+    consumed_ids: Vec<AgentId>,
+    new_predators: Vec<Predator>,
+    new_prey: Vec<Prey>,
+    infected_positions: Vec<Vector2>,
+    neighbor_grids: NeighborGrids,
+    /// Agents whose actions were resolved last tick, in resolution order
+    update_order: Vec<AgentId>,
+}
+
+/// Spatial index of both populations, rebuilt once per tick
+#[derive(Clone, Default)]
+struct NeighborGrids {
+    predators: NeighborIndex,
+    prey: NeighborIndex,
+}
+
+/// One population's positions in the structure picked by `spatial_index_kind`
+#[derive(Clone)]
+enum NeighborIndex {
+    Grid(SpatialGrid),
+    QuadTree(QuadTree),
+}
+
+impl Default for NeighborIndex {
+    fn default() -> Self {
+        Self::Grid(SpatialGrid::default())
+    }
+}
+
+impl NeighborIndex {
+    /// Refill with `positions`, switching structure if `params` asks for another one
+    fn rebuild(&mut self, positions: impl IntoIterator<Item = Vector2>, params: &Parameters, cell_size: f64) {
+        let (width, height) = (params.world.width, params.world.height);
+        let quadtree = params.simulation.spatial_index
+            && params.simulation.spatial_index_kind == SpatialIndexKind::QuadTree;
+        match self {
+            Self::QuadTree(tree) if quadtree => {
+                tree.rebuild(positions, width, height, params.simulation.quadtree_capacity as usize);
+            }
+            Self::Grid(grid) if !quadtree => grid.rebuild(positions, width, height, cell_size),
+            _ if quadtree => {
+                *self = Self::QuadTree(QuadTree::new(positions, width, height, params.simulation.quadtree_capacity as usize));
+            }
+            _ => *self = Self::Grid(SpatialGrid::new(positions, width, height, cell_size)),
+        }
+    }
+
+    fn for_each_candidate(&self, pos: Vector2, radius: f64, f: impl FnMut(usize)) {
+        match self {
+            Self::Grid(grid) => grid.for_each_candidate(pos, radius, f),
+            Self::QuadTree(tree) => tree.for_each_candidate(pos, radius, f),
+        }
+    }
+}
+
+/// Something that happened to an agent during a tick, reported to the observer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimEvent {
+    /// A new agent was born next to its parent
+    Born { id: AgentId, parent_id: AgentId, agent_type: AgentType },
+    /// An agent ran out of energy or was removed to stay within `max_agents`
+    Died { id: AgentId, agent_type: AgentType },
+    /// A predator caught and ate a prey
+    Consumed { predator_id: AgentId, prey_id: AgentId },
+}
+
+/// Total energy held by each category at one moment
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct EnergyTotals {
+    pub predators: f64,
+    pub prey: f64,
+    /// Energy lying around uneaten, `food_energy` per food item
+    pub food: f64,
+}
+
+impl EnergyTotals {
+    /// Sum over all categories
+    pub fn total(&self) -> f64 {
+        self.predators + self.prey + self.food
+    }
+}
+
+/// Callback invoked for every `SimEvent`
+pub type SimObserver = Box<dyn FnMut(&SimEvent) + Send>;
 
 /// World manages all agents and the simulation environment
 pub struct World {
     predators: Vec<Predator>,
     prey: Vec<Prey>,
     params: Parameters,
-    next_id: AgentId,
+    /// Sequence number of the next allocated agent id
+    next_id: u64,
+    tick: u64,
+    /// Recent prey alarm calls: (position, ticks left)
+    alarms: Vec<(Vector2, u32)>,
+    scratch: ScratchBuffers,
+    /// Food items lying in the world
+    food: Vec<Vector2>,
+    /// Frozen populations are not updated (they stay put but can still be eaten)
+    freeze_predators: bool,
+    freeze_prey: bool,
+    /// Source of all randomness drawn by the world itself
+    rng: WorldRng,
+    /// Auto-balance multiplier applied to the configured prey regeneration
+    regeneration_factor: f64,
+    /// Obstacles placed by `params.world.obstacle_generator` (kept out of the
+    /// parameters so live parameter edits don't wipe them)
+    generated_obstacles: Vec<Obstacle>,
+    /// Predator scent trails (empty unless `params.world.scent_trails` is set)
+    scent: ScentField,
+    /// Receives births, deaths and consumptions as they happen
+    observer: Option<SimObserver>,
+    /// Resets since creation (not part of snapshots)
+    run_number: u64,
+}
+
+/// Deep copy of the whole world, RNG included. The observer is not copied:
+/// a clone starts without one.
+impl Clone for World {
+    fn clone(&self) -> Self {
+        Self {
+            predators: self.predators.clone(),
+            prey: self.prey.clone(),
+            params: self.params.clone(),
+            next_id: self.next_id,
+            tick: self.tick,
+            alarms: self.alarms.clone(),
+            scratch: self.scratch.clone(),
+            food: self.food.clone(),
+            freeze_predators: self.freeze_predators,
+            freeze_prey: self.freeze_prey,
+            rng: self.rng.clone(),
+            regeneration_factor: self.regeneration_factor,
+            generated_obstacles: self.generated_obstacles.clone(),
+            scent: self.scent.clone(),
+            observer: None,
+            run_number: self.run_number,
+        }
+    }
 }
 
 impl World {
     /// Create a new world with the given parameters
     pub fn new(params: Parameters) -> Self {
+        let rng = WorldRng::new(params.simulation.rng_algorithm, params.simulation.seed);
+        Self::with_rng(params, rng)
+    }
+
+    /// Create a world seeded with `seed`, overriding `params.simulation.seed`.
+    /// `reset` replays the same run.
+    pub fn new_seeded(mut params: Parameters, seed: u64) -> Self {
+        params.simulation.seed = Some(seed);
+        Self::new(params)
+    }
+
+    /// Create a new world drawing its randomness from `rng`
+    pub fn with_rng(params: Parameters, rng: WorldRng) -> Self {
         let mut world = Self {
             predators: Vec::new(),
             prey: Vec::new(),
             params,
             next_id: 1,
+            tick: 0,
+            alarms: Vec::new(),
+            scratch: ScratchBuffers::default(),
+            food: Vec::new(),
+            freeze_predators: false,
+            freeze_prey: false,
+            rng,
+            regeneration_factor: 1.0,
+            generated_obstacles: Vec::new(),
+            scent: ScentField::default(),
+            observer: None,
+            run_number: 0,
         };
 
+        world.generate_obstacles();
         world.initialize_agents();
         world
     }
 
+    /// Replace the generated obstacles with a fresh layout from the obstacle generator
+    fn generate_obstacles(&mut self) {
+        self.generated_obstacles = match self.params.world.obstacle_generator {
+            Some(generator) => generator.generate(self.params.world.width, self.params.world.height, &mut self.rng),
+            None => Vec::new(),
+        };
+    }
+
+    /// Hand-placed and generated obstacles
+    pub fn obstacles(&self) -> impl Iterator<Item = &Obstacle> {
+        self.params.world.obstacles.iter().chain(&self.generated_obstacles)
+    }
+
+    /// Whether a position lies outside every obstacle, generated or not
+    fn is_free(&self, pos: Vector2) -> bool {
+        self.params.world.is_free(pos) && !self.generated_obstacles.iter().any(|o| o.contains(pos))
+    }
+
     /// Initialize agents according to parameters
     fn initialize_agents(&mut self) {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        if let SpawnPattern::Lattice { interleave } = self.params.world.spawn_pattern {
+            self.initialize_lattice(interleave);
+            return;
+        }
+
+        let mut rng = self.take_rng();
 
         // Spawn predators
         for _ in 0..self.params.predator.initial_count {
-            let x = rng.gen_range(0.0..self.params.world.width);
-            let y = rng.gen_range(0.0..self.params.world.height);
-            let predator = Predator::new(
-                self.next_id,
-                Vector2::new(x, y),
-                self.params.predator.clone(),
-            );
+            let Some(position) = self.random_spawn_position(&mut rng) else {
+                continue;
+            };
+            let id = self.allocate_id(AgentType::Predator, None);
+            let predator = Predator::new(id, position, self.params.predator.clone());
             self.predators.push(predator);
-            self.next_id += 1;
         }
 
         // Spawn prey
         for _ in 0..self.params.prey.initial_count {
-            let x = rng.gen_range(0.0..self.params.world.width);
-            let y = rng.gen_range(0.0..self.params.world.height);
-            let prey = Prey::new(self.next_id, Vector2::new(x, y), self.params.prey.clone());
+            let Some(position) = self.random_spawn_position(&mut rng) else {
+                continue;
+            };
+            let id = self.allocate_id(AgentType::Prey, None);
+            let prey = Prey::new(id, position, self.params.prey.clone());
             self.prey.push(prey);
-            self.next_id += 1;
         }
+
+        self.rng = rng;
+    }
+
+    /// Place the initial agents on an evenly spaced grid, skipping cells inside
+    /// obstacles. Interleaving spreads predators evenly among the prey.
+    fn initialize_lattice(&mut self, interleave: bool) {
+        let predator_count = self.params.predator.initial_count as usize;
+        let total = predator_count + self.params.prey.initial_count as usize;
+        let positions = lattice_positions(total, self.params.world.width, self.params.world.height);
+
+        for (slot, position) in positions.into_iter().enumerate() {
+            let is_predator = if interleave {
+                // Slot is a predator when the running predator quota steps up
+                (slot + 1) * predator_count / total > slot * predator_count / total
+            } else {
+                slot < predator_count
+            };
+            if !self.is_free(position) {
+                continue;
+            }
+
+            if is_predator {
+                let id = self.allocate_id(AgentType::Predator, None);
+                self.predators.push(Predator::new(id, position, self.params.predator.clone()));
+            } else {
+                let id = self.allocate_id(AgentType::Prey, None);
+                self.prey.push(Prey::new(id, position, self.params.prey.clone()));
+            }
+        }
+    }
+
+    /// Take the world RNG out (leaving a placeholder) so it can be used while
+    /// `self` is borrowed; the caller must put it back into `self.rng`
+    fn take_rng(&mut self) -> WorldRng {
+        std::mem::replace(&mut self.rng, WorldRng::new(RngAlgorithm::ChaCha8, Some(0)))
     }
 
     /// Update the world one simulation step
     pub fn update(&mut self) {
-        // Build spatial index for efficient neighbor queries
-        // For now, we'll use a simple approach and optimize later if needed
-        
-        // Prepare world state for each agent
-        let world_state = self.build_world_state();
+        // Reuse last tick's buffers instead of allocating fresh ones
+        let mut scratch = std::mem::take(&mut self.scratch);
+        let mut rng = self.take_rng();
+
+        // Build each agent's view of its surroundings before anyone moves.
+        // Frozen populations are skipped entirely: no views, no update, no actions.
+        self.rebuild_neighbor_grids(&mut scratch.neighbor_grids);
+        let grids = &scratch.neighbor_grids;
+        let predator_count = if self.freeze_predators { 0 } else { self.predators.len() };
+        scratch.predator_states.resize_with(predator_count, || self.empty_world_state());
+        for (state, p) in scratch.predator_states.iter_mut().zip(&self.predators) {
+            self.fill_world_state(state, p.id(), p.position(), p.perception_radius(), grids, &mut rng);
+        }
+        let prey_count = if self.freeze_prey { 0 } else { self.prey.len() };
+        scratch.prey_states.resize_with(prey_count, || self.empty_world_state());
+        for (state, p) in scratch.prey_states.iter_mut().zip(&self.prey) {
+            self.fill_world_state(state, p.id(), p.position(), self.params.prey.detection_radius, grids, &mut rng);
+        }
 
         // Update all predators
-        let mut predator_actions = Vec::new();
-        for (i, predator) in self.predators.iter_mut().enumerate() {
-            let action = predator.update(&world_state);
-            predator_actions.push((i, action));
+        scratch.predator_actions.clear();
+        for (i, (predator, world_state)) in self.predators.iter_mut().zip(&scratch.predator_states).enumerate() {
+            let action = predator.update_with_rng(world_state, &mut rng);
+            scratch.predator_actions.push((i, action));
         }
 
         // Update all prey
-        let mut prey_actions = Vec::new();
-        for (i, prey) in self.prey.iter_mut().enumerate() {
-            let action = prey.update(&world_state);
-            prey_actions.push((i, action));
+        scratch.prey_actions.clear();
+        for (i, (prey, world_state)) in self.prey.iter_mut().zip(&scratch.prey_states).enumerate() {
+            let action = prey.update_with_rng(world_state, &mut rng);
+            scratch.prey_actions.push((i, action));
         }
 
+        if self.params.world.enable_food {
+            self.spawn_food(&mut rng);
+            if !self.freeze_prey {
+                self.feed_prey();
+            }
+        }
+
+        if self.params.prey.infection_enabled {
+            self.spread_infection(&mut scratch.infected_positions, &mut rng);
+        }
+
+        // Age out old alarm calls and record new ones from fleeing prey
+        self.alarms.retain_mut(|(_, ticks_left)| {
+            *ticks_left -= 1;
+            *ticks_left > 0
+        });
+        if self.params.predator.alarm_attracts_predators {
+            self.alarms.extend(
+                self.prey
+                    .iter()
+                    .filter(|p| p.is_alarmed())
+                    .map(|p| (p.position(), ALARM_DURATION_TICKS)),
+            );
+        }
+
+        self.lay_scent();
+
+        // Resolve actions in a seeded random order so insertion order gives no
+        // agent a lasting edge (e.g. in contested captures)
+        if self.params.simulation.shuffle_update_order {
+            use rand::seq::SliceRandom;
+            scratch.predator_actions.shuffle(&mut rng);
+            scratch.prey_actions.shuffle(&mut rng);
+        }
+        scratch.update_order.clear();
+        scratch.update_order.extend(scratch.predator_actions.iter().map(|(i, _)| self.predators[*i].id()));
+        scratch.update_order.extend(scratch.prey_actions.iter().map(|(i, _)| self.prey[*i].id()));
+
         // Process actions
-        self.process_actions(predator_actions, prey_actions);
+        self.process_actions(&mut scratch, &mut rng);
+        self.scratch = scratch;
+        self.rng = rng;
 
         // Remove dead agents
+        if let Some(observer) = &mut self.observer {
+            let dead_predators = self.predators.iter().filter(|p| !p.is_alive()).map(|p| (p.id(), AgentType::Predator));
+            let dead_prey = self.prey.iter().filter(|p| !p.is_alive()).map(|p| (p.id(), AgentType::Prey));
+            for (id, agent_type) in dead_predators.chain(dead_prey) {
+                observer(&SimEvent::Died { id, agent_type });
+            }
+        }
         self.predators.retain(|p| p.is_alive());
         self.prey.retain(|p| p.is_alive());
 
         // Limit total agents
         self.enforce_max_agents();
+
+        if self.params.simulation.auto_balance.enabled {
+            self.auto_balance();
+        } else if self.regeneration_factor != 1.0 {
+            self.end_auto_balance();
+        }
+
+        self.tick += 1;
+    }
+
+    /// Nudge prey regeneration toward the auto-balance target population and
+    /// apply it to every living prey
+    fn auto_balance(&mut self) {
+        let balance = &self.params.simulation.auto_balance;
+        let target = balance.target_prey.max(1) as f64;
+        let deviation = ((target - self.prey.len() as f64) / target).clamp(-1.0, 1.0);
+        self.regeneration_factor = (self.regeneration_factor * (1.0 + balance.gain * deviation))
+            .clamp(MIN_REGENERATION_FACTOR, MAX_REGENERATION_FACTOR);
+
+        let rate = self.params.prey.energy_regeneration * self.regeneration_factor;
+        for prey in &mut self.prey {
+            prey.set_energy_regeneration(rate);
+        }
     }
 
-    /// Build world state information for agents
-    fn build_world_state(&self) -> WorldState {
-        let mut nearby_predators = Vec::new();
-        let mut nearby_prey = Vec::new();
+    /// Drop the auto-balance multiplier and give every living prey the base
+    /// regeneration rate again
+    fn end_auto_balance(&mut self) {
+        self.regeneration_factor = 1.0;
+        let rate = self.params.prey.energy_regeneration;
+        for prey in &mut self.prey {
+            prey.set_energy_regeneration(rate);
+        }
+    }
 
-        // Build lists of nearby agents for each agent
-        // This is O(n²) but acceptable for moderate numbers of agents
-        // Can be optimized with spatial partitioning later
+    /// Current auto-balance multiplier of prey regeneration (1 when untouched)
+    pub fn regeneration_factor(&self) -> f64 {
+        self.regeneration_factor
+    }
 
-        // Use torus distance for proper wraparound behavior
+    /// Fade the scent field, then add fresh scent under every predator
+    fn lay_scent(&mut self) {
+        let Some(trails) = self.params.world.scent_trails else {
+            return;
+        };
+        self.scent.fit(self.params.world.width, self.params.world.height, trails.cell_size);
+        self.scent.decay(trails.retention);
+        for predator in &self.predators {
+            self.scent.deposit(predator.position(), trails.deposit);
+        }
+    }
+
+    /// Scatter new food items at random free positions
+    fn spawn_food(&mut self, rng: &mut WorldRng) {
+        use rand::Rng;
+        let area = self.params.world.width * self.params.world.height;
+        let expected = (self.params.world.food_spawn_rate * area / 10_000.0).max(0.0);
+
+        // Whole part always spawns, the fraction spawns with that probability
+        let mut count = expected.floor() as usize;
+        if rng.gen::<f64>() < expected.fract() {
+            count += 1;
+        }
+
+        for _ in 0..count.min(MAX_FOOD_ITEMS.saturating_sub(self.food.len())) {
+            if let Some(position) = self.random_free_position(rng) {
+                self.food.push(position);
+            }
+        }
+    }
+
+    /// Let each prey eat at most one food item within reach
+    fn feed_prey(&mut self) {
         let world_width = self.params.world.width;
         let world_height = self.params.world.height;
+        let food_energy = self.params.world.food_energy;
 
-        for prey in &self.prey {
-            // Find nearby predators
-            for predator in &self.predators {
-                let dist = distance_torus(&prey.position(), &predator.position(), world_width, world_height);
-                if dist <= self.params.predator.perception_radius.max(self.params.prey.detection_radius) {
-                    nearby_predators.push((predator.id(), predator.position(), dist));
-                }
+        for prey in &mut self.prey {
+            let reachable = self
+                .food
+                .iter()
+                .position(|f| distance_torus(&prey.position(), f, world_width, world_height) <= FOOD_EAT_DISTANCE);
+            if let Some(index) = reachable {
+                self.food.swap_remove(index);
+                prey.feed(food_energy);
             }
         }
+    }
 
-        for predator in &self.predators {
-            // Find nearby prey
-            for prey in &self.prey {
-                let dist = distance_torus(&predator.position(), &prey.position(), world_width, world_height);
-                if dist <= self.params.predator.perception_radius {
-                    nearby_prey.push((prey.id(), prey.position(), dist));
-                }
+    /// Infect susceptible prey near infected ones, each with probability
+    /// `infection_rate`. Only prey infected before this call can pass it on.
+    fn spread_infection(&mut self, infected_positions: &mut Vec<Vector2>, rng: &mut WorldRng) {
+        use rand::Rng;
+        let world_width = self.params.world.width;
+        let world_height = self.params.world.height;
+        let radius = self.params.prey.infection_radius;
+        let rate = self.params.prey.infection_rate;
+
+        infected_positions.clear();
+        infected_positions.extend(self.prey.iter().filter(|p| p.is_infected()).map(|p| p.position()));
+        if infected_positions.is_empty() {
+            return;
+        }
+
+        for prey in self.prey.iter_mut().filter(|p| p.infection() == InfectionState::Susceptible) {
+            let exposed = infected_positions
+                .iter()
+                .any(|pos| distance_torus(&prey.position(), pos, world_width, world_height) <= radius);
+            if exposed && rng.gen::<f64>() < rate {
+                prey.infect();
             }
         }
+    }
 
+    /// A world state with no neighbors or alarms
+    fn empty_world_state(&self) -> WorldState {
         WorldState {
             width: self.params.world.width,
             height: self.params.world.height,
             boundary_type: self.params.world.boundary_type,
-            nearby_predators,
-            nearby_prey,
+            nearby_predators: Vec::new(),
+            nearby_prey: Vec::new(),
+            alarm_positions: Vec::new(),
+            scent_gradient: Vector2::zero(),
             dt: self.params.simulation.dt,
+            speed_scale: 1.0,
+            integration: self.params.simulation.integration,
+            deterministic_offspring: self.params.simulation.deterministic_offspring,
         }
     }
 
-    /// Process agent actions (consumption, reproduction, etc.)
-    fn process_actions(
-        &mut self,
-        predator_actions: Vec<(usize, AgentAction)>,
-        prey_actions: Vec<(usize, AgentAction)>,
+    /// Build the world state seen by one agent: all other agents within `radius` of it
+    fn build_world_state(&self, id: AgentId, position: Vector2, radius: f64) -> WorldState {
+        let mut grids = NeighborGrids::default();
+        self.rebuild_neighbor_grids(&mut grids);
+        // Inspection must not advance the world RNG, so any sensor noise here is off the record
+        let mut state = self.empty_world_state();
+        self.fill_world_state(&mut state, id, position, radius, &grids, &mut rand::thread_rng());
+        state
+    }
+
+    /// Index both populations, in a grid with cells sized for the largest
+    /// perception radius in use or in quadtrees
+    fn rebuild_neighbor_grids(&self, grids: &mut NeighborGrids) {
+        // Without the index, a single cell makes every agent a candidate
+        let cell_size = if self.params.simulation.spatial_index {
+            self.predators
+                .iter()
+                .map(|p| p.perception_radius())
+                .fold(self.params.prey.detection_radius, f64::max)
+        } else {
+            0.0
+        };
+
+        grids.predators.rebuild(self.predators.iter().map(|p| p.position()), &self.params, cell_size);
+        grids.prey.rebuild(self.prey.iter().map(|p| p.position()), &self.params, cell_size);
+    }
+
+    /// Overwrite `state` in place with the view of one agent, reusing its buffers
+    fn fill_world_state(
+        &self,
+        state: &mut WorldState,
+        id: AgentId,
+        position: Vector2,
+        radius: f64,
+        grids: &NeighborGrids,
+        rng: &mut impl rand::Rng,
     ) {
+        // Use torus distance for proper wraparound behavior
+        let world_width = self.params.world.width;
+        let world_height = self.params.world.height;
+
+        state.width = world_width;
+        state.height = world_height;
+        state.boundary_type = self.params.world.boundary_type;
+        // Time zones stretch or shrink the tick for agents inside them
+        state.dt = self.params.simulation.dt * self.params.world.dt_scale_at(position);
+        // Cold regions of a temperature gradient cap the agent's speed
+        state.speed_scale = self.params.world.speed_scale_at(position);
+        state.integration = self.params.simulation.integration;
+        state.deterministic_offspring = self.params.simulation.deterministic_offspring;
+
+        // Only agents in index cells the radius can reach are distance-checked
+        state.nearby_predators.clear();
+        grids.predators.for_each_candidate(position, radius, |index| {
+            let p = &self.predators[index];
+            let neighbor = Self::neighbor_info(p, position, world_width, world_height);
+            if p.id() != id && neighbor.distance <= radius {
+                state.nearby_predators.push(neighbor);
+            }
+        });
+
+        state.nearby_prey.clear();
+        grids.prey.for_each_candidate(position, radius, |index| {
+            let p = &self.prey[index];
+            let neighbor = Self::neighbor_info(p, position, world_width, world_height);
+            if p.id() != id && neighbor.distance <= radius {
+                state.nearby_prey.push(neighbor);
+            }
+        });
+
+        // Perception is imprecise: jitter what each agent sees, never the true positions
+        let noise = self.params.simulation.sensor_noise;
+        if noise > 0.0 {
+            for neighbor in state.nearby_predators.iter_mut().chain(state.nearby_prey.iter_mut()) {
+                let noisy = Vector2::new(
+                    neighbor.position.x + gaussian(rng, noise),
+                    neighbor.position.y + gaussian(rng, noise),
+                );
+                neighbor.position = match state.boundary_type {
+                    BoundaryType::Wraparound => wrap_position(noisy, world_width, world_height),
+                    BoundaryType::Walls => clamp_position(noisy, world_width, world_height),
+                };
+                neighbor.distance = distance_torus(&position, &neighbor.position, world_width, world_height);
+            }
+        }
+
+        // Nearest first, ties broken by id, so ordering is reproducible.
+        // Ids are unique, so the order is total and an (in-place) unstable sort suffices.
+        state.nearby_predators.sort_unstable_by(compare_neighbors);
+        state.nearby_prey.sort_unstable_by(compare_neighbors);
+
+        state.alarm_positions.clear();
+        state.alarm_positions.extend(
+            self.alarms
+                .iter()
+                .map(|(pos, _)| *pos)
+                .filter(|pos| distance_torus(&position, pos, world_width, world_height) <= self.params.predator.alarm_radius),
+        );
+
+        state.scent_gradient = match self.params.world.scent_trails {
+            Some(trails) if self.scent.at(position) >= trails.detection_threshold => self
+                .scent
+                .gradient(position, self.params.world.boundary_type == BoundaryType::Wraparound),
+            _ => Vector2::zero(),
+        };
+    }
+
+    /// Describe an agent as seen from `from`
+    fn neighbor_info(agent: &impl Agent, from: Vector2, world_width: f64, world_height: f64) -> NeighborInfo {
+        NeighborInfo {
+            id: agent.id(),
+            position: agent.position(),
+            velocity: agent.velocity(),
+            energy: agent.energy(),
+            distance: distance_torus(&from, &agent.position(), world_width, world_height),
+        }
+    }
+
+    /// The world state an agent currently perceives, or None if no agent has that id
+    pub fn view_of(&self, id: AgentId) -> Option<WorldState> {
+        if let Some(p) = self.predators.iter().find(|p| p.id() == id) {
+            return Some(self.build_world_state(id, p.position(), p.perception_radius()));
+        }
+        self.prey
+            .iter()
+            .find(|p| p.id() == id)
+            .map(|p| self.build_world_state(id, p.position(), self.params.prey.detection_radius))
+    }
+
+    /// Ids and perceived positions of every agent the given agent currently
+    /// perceives (predators first, then prey; nearest first within each).
+    /// Empty if no agent has that id.
+    pub fn neighbors_of(&self, id: AgentId) -> Vec<(AgentId, Vector2)> {
+        self.view_of(id)
+            .map(|view| {
+                view.nearby_predators
+                    .iter()
+                    .chain(&view.nearby_prey)
+                    .map(|n| (n.id, n.position))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Directed perception graph: an edge (a, b) for every agent b that agent a
+    /// currently perceives, predators' edges first
+    pub fn perception_edges(&self) -> Vec<(AgentId, AgentId)> {
+        let mut grids = NeighborGrids::default();
+        self.rebuild_neighbor_grids(&mut grids);
+        let mut state = self.empty_world_state();
+        // As in `build_world_state`, inspection must not advance the world RNG
+        let mut rng = rand::thread_rng();
+
+        let observers = self
+            .predators
+            .iter()
+            .map(|p| (p.id(), p.position(), p.perception_radius()))
+            .chain(self.prey.iter().map(|p| (p.id(), p.position(), self.params.prey.detection_radius)));
+
+        let mut edges = Vec::new();
+        for (id, position, radius) in observers {
+            self.fill_world_state(&mut state, id, position, radius, &grids, &mut rng);
+            edges.extend(state.nearby_predators.iter().chain(&state.nearby_prey).map(|n| (id, n.id)));
+        }
+        edges
+    }
+
+    /// Process agent actions (consumption, reproduction, etc.)
+    fn process_actions(&mut self, scratch: &mut ScratchBuffers, rng: &mut WorldRng) {
+        use rand::Rng;
+
         // Process predator actions
-        let mut consumed_prey_ids = Vec::new();
-        let mut new_predators = Vec::new();
+        scratch.consumed_ids.clear();
+        scratch.new_predators.clear();
+        // Births honored so far this tick (predators are served first)
+        let mut births = 0;
 
-        for (_idx, action) in predator_actions {
+        for (idx, action) in &scratch.predator_actions {
             match action {
                 AgentAction::Consumed { target_id } => {
-                    consumed_prey_ids.push(target_id);
+                    // Contested prey: the first predator in resolution order wins
+                    if scratch.consumed_ids.contains(target_id) {
+                        self.predators[*idx].forfeit_capture();
+                        continue;
+                    }
+                    // Safety in numbers: prey among many others are harder to single out
+                    let probability = self.capture_probability(*target_id);
+                    if probability >= 1.0 || rng.gen::<f64>() < probability {
+                        scratch.consumed_ids.push(*target_id);
+                        let predator_id = self.predators[*idx].id();
+                        self.emit(SimEvent::Consumed { predator_id, prey_id: *target_id });
+                    } else {
+                        self.predators[*idx].forfeit_capture();
+                    }
                 }
-                AgentAction::Reproduce { position, energy: _ } => {
-                    if self.params.simulation.enable_reproduction {
-                        let new_predator = Predator::new(
-                            self.next_id,
-                            position,
-                            self.params.predator.clone(),
-                        );
-                        // Set energy manually (we'd need to expose this in BaseAgent)
-                        // For now, new agents start with initial_energy from params
-                        new_predators.push(new_predator);
-                        self.next_id += 1;
+                AgentAction::Reproduce { position, energy } => {
+                    let population = self.predators.len() + scratch.new_predators.len();
+                    if self.params.simulation.enable_reproduction
+                        && self.birth_allowed(births)
+                        && self.below_type_cap(AgentType::Predator, population)
+                    {
+                        births += 1;
+                        let position = self.free_or(*position, self.predators[*idx].position());
+                        let parent_id = self.predators[*idx].id();
+                        let id = self.allocate_id(AgentType::Predator, Some(parent_id));
+                        let energy = self.offspring_energy(*energy);
+                        scratch.new_predators.push(Predator::new_with_energy(id, position, self.params.predator.clone(), energy));
+                        self.emit(SimEvent::Born { id, parent_id, agent_type: AgentType::Predator });
                     }
                 }
                 _ => {}
@@ -165,18 +739,22 @@ impl World {
         }
 
         // Process prey actions
-        let mut new_prey = Vec::new();
-        for (_idx, action) in prey_actions {
+        scratch.new_prey.clear();
+        for (idx, action) in &scratch.prey_actions {
             match action {
-                AgentAction::Reproduce { position, energy: _ } => {
-                    if self.params.simulation.enable_reproduction {
-                        let new_prey_agent = Prey::new(
-                            self.next_id,
-                            position,
-                            self.params.prey.clone(),
-                        );
-                        new_prey.push(new_prey_agent);
-                        self.next_id += 1;
+                AgentAction::Reproduce { position, energy } => {
+                    let population = self.prey.len() + scratch.new_prey.len();
+                    if self.params.simulation.enable_reproduction
+                        && self.birth_allowed(births)
+                        && self.below_type_cap(AgentType::Prey, population)
+                    {
+                        births += 1;
+                        let position = self.free_or(*position, self.prey[*idx].position());
+                        let parent_id = self.prey[*idx].id();
+                        let id = self.allocate_id(AgentType::Prey, Some(parent_id));
+                        let energy = self.offspring_energy(*energy);
+                        scratch.new_prey.push(Prey::new_with_energy(id, position, self.params.prey.clone(), energy));
+                        self.emit(SimEvent::Born { id, parent_id, agent_type: AgentType::Prey });
                     }
                 }
                 _ => {}
@@ -184,31 +762,26 @@ impl World {
         }
 
         // Remove consumed prey
-        self.prey.retain(|p| !consumed_prey_ids.contains(&p.id()));
+        let consumed_ids = &scratch.consumed_ids;
+        self.prey.retain(|p| !consumed_ids.contains(&p.id()));
 
         // Add new agents
-        self.predators.extend(new_predators);
-        self.prey.extend(new_prey);
+        self.predators.append(&mut scratch.new_predators);
+        self.prey.append(&mut scratch.new_prey);
     }
 
     /// Enforce maximum agent limit
     fn enforce_max_agents(&mut self) {
-        let total = self.predators.len() + self.prey.len();
-        if total > self.params.simulation.max_agents as usize {
-            // Remove oldest agents first (simple FIFO)
-            let to_remove = total - self.params.simulation.max_agents as usize;
-            
-            // Remove predators first if needed
-            if self.predators.len() > to_remove {
-                self.predators.drain(0..to_remove);
-            } else {
-                let remove_prey = to_remove - self.predators.len();
-                self.predators.clear();
-                if self.prey.len() > remove_prey {
-                    self.prey.drain(0..remove_prey);
-                } else {
-                    self.prey.clear();
-                }
+        let max_agents = self.params.simulation.max_agents as usize;
+        let excess = (self.predators.len() + self.prey.len()).saturating_sub(max_agents);
+        // Remove oldest agents first (simple FIFO), predators before prey
+        let remove_predators = excess.min(self.predators.len());
+        let remove_prey = (excess - remove_predators).min(self.prey.len());
+        let removed_predators = self.predators.drain(..remove_predators).map(|p| (p.id(), AgentType::Predator));
+        let removed_prey = self.prey.drain(..remove_prey).map(|p| (p.id(), AgentType::Prey));
+        if let Some(observer) = &mut self.observer {
+            for (id, agent_type) in removed_predators.chain(removed_prey) {
+                observer(&SimEvent::Died { id, agent_type });
             }
         }
     }
@@ -245,75 +818,682 @@ impl World {
         // New agents will use the new parameters
     }
 
+    /// Capture the complete simulation state
+    pub fn snapshot(&self) -> WorldSnapshot {
+        WorldSnapshot {
+            tick: self.tick,
+            next_id: self.next_id,
+            params: self.params.clone(),
+            predators: self.predators.clone(),
+            prey: self.prey.clone(),
+            alarms: self.alarms.clone(),
+            food: self.food.clone(),
+            rng: Some(self.rng.clone()),
+            freeze_predators: self.freeze_predators,
+            freeze_prey: self.freeze_prey,
+            regeneration_factor: self.regeneration_factor,
+            generated_obstacles: self.generated_obstacles.clone(),
+            scent: self.scent.clone(),
+        }
+    }
+
+    /// Hash of the complete simulation state (the serialized snapshot):
+    /// equal hashes mean identical worlds
+    pub fn state_hash(&self) -> u64 {
+        let json = serde_json::to_vec(&self.snapshot()).unwrap_or_default();
+        fnv1a_64(&json)
+    }
+
+    /// Rebuild a world from a snapshot
+    pub fn from_snapshot(snapshot: WorldSnapshot) -> Self {
+        let rng = snapshot.rng.unwrap_or_else(|| {
+            WorldRng::new(snapshot.params.simulation.rng_algorithm, snapshot.params.simulation.seed)
+        });
+        Self {
+            predators: snapshot.predators,
+            prey: snapshot.prey,
+            params: snapshot.params,
+            next_id: snapshot.next_id,
+            tick: snapshot.tick,
+            alarms: snapshot.alarms,
+            scratch: ScratchBuffers::default(),
+            food: snapshot.food,
+            freeze_predators: snapshot.freeze_predators,
+            freeze_prey: snapshot.freeze_prey,
+            rng,
+            regeneration_factor: snapshot.regeneration_factor,
+            generated_obstacles: snapshot.generated_obstacles,
+            scent: snapshot.scent,
+            observer: None,
+            run_number: 0,
+        }
+    }
+
+    /// The complete simulation state as JSON (a serialized `WorldSnapshot`);
+    /// serialization errors are passed on rather than turned into an empty string
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.snapshot())
+    }
+
+    /// Rebuild a world from `to_json` output, id counter included, so agents
+    /// spawned afterwards never reuse an id
+    pub fn from_json(json: &str) -> Result<World, serde_json::Error> {
+        serde_json::from_str(json).map(Self::from_snapshot)
+    }
+
+    /// Independent copy to run forward as a "what-if" preview: it draws the
+    /// same random numbers, so it predicts exactly what this world will do
+    /// (until either is changed from outside). Events are not reported.
+    pub fn fork(&self) -> World {
+        self.clone()
+    }
+
+    /// Call `observer` for every birth, death and consumption from now on
+    /// (replacing any previous observer)
+    pub fn set_observer(&mut self, observer: impl FnMut(&SimEvent) + Send + 'static) {
+        self.observer = Some(Box::new(observer));
+    }
+
+    /// Stop reporting events
+    pub fn clear_observer(&mut self) {
+        self.observer = None;
+    }
+
+    /// Report an event to the observer, if any
+    fn emit(&mut self, event: SimEvent) {
+        if let Some(observer) = &mut self.observer {
+            observer(&event);
+        }
+    }
+
+    /// Food items currently lying in the world
+    pub fn food(&self) -> &[Vector2] {
+        &self.food
+    }
+
+    /// Agents whose actions the last update resolved, in order (predators, then prey)
+    pub fn update_order(&self) -> &[AgentId] {
+        &self.scratch.update_order
+    }
+
+    /// Predator scent trails (an empty field unless scent trails are enabled)
+    pub fn scent(&self) -> &ScentField {
+        &self.scent
+    }
+
+    /// Freeze or unfreeze one population: frozen agents neither move,
+    /// consume nor reproduce, but remain in the world (and edible)
+    pub fn set_frozen(&mut self, agent_type: AgentType, frozen: bool) {
+        match agent_type {
+            AgentType::Predator => self.freeze_predators = frozen,
+            AgentType::Prey => self.freeze_prey = frozen,
+        }
+    }
+
+    /// Whether a population is currently frozen
+    pub fn is_frozen(&self, agent_type: AgentType) -> bool {
+        match agent_type {
+            AgentType::Predator => self.freeze_predators,
+            AgentType::Prey => self.freeze_prey,
+        }
+    }
+
     /// Get current parameters
     pub fn parameters(&self) -> &Parameters {
         &self.params
     }
 
-    /// Reset the world (clear all agents and reinitialize)
+    /// Number of resets since the world was created
+    pub fn run_number(&self) -> u64 {
+        self.run_number
+    }
+
+    /// Seed the current run's RNG was started from (None = fresh entropy).
+    /// With `advance_seed_on_reset` this is the configured seed plus the run number.
+    pub fn current_seed(&self) -> Option<u64> {
+        self.reset_seed()
+    }
+
+    /// Seed for the RNG of the current run (None = fresh entropy)
+    fn reset_seed(&self) -> Option<u64> {
+        let simulation = &self.params.simulation;
+        simulation.seed.map(|seed| {
+            if simulation.advance_seed_on_reset {
+                seed.wrapping_add(self.run_number)
+            } else {
+                seed
+            }
+        })
+    }
+
+    /// Get the number of simulation ticks run since creation or the last reset
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Reset the world (clear all agents and reinitialize). A seeded world
+    /// restarts its RNG from the seed (advanced by the run number if
+    /// `advance_seed_on_reset` is set), so the new run is reproducible.
     pub fn reset(&mut self) {
+        self.run_number += 1;
+        self.rng = WorldRng::new(self.params.simulation.rng_algorithm, self.reset_seed());
         self.predators.clear();
         self.prey.clear();
         self.next_id = 1;
+        self.tick = 0;
+        self.alarms.clear();
+        self.food.clear();
+        self.scent.clear();
+        self.regeneration_factor = 1.0;
+        self.generate_obstacles();
         self.initialize_agents();
     }
 
     /// Spawn additional predators at random positions
     pub fn spawn_predators(&mut self, count: u32) -> u32 {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let mut rng = self.take_rng();
         let mut spawned = 0;
         
         for _ in 0..count {
             // Check max agents limit
-            if self.total_agents() >= self.params.simulation.max_agents as usize {
+            if self.total_agents() >= self.params.simulation.max_agents as usize
+                || !self.below_type_cap(AgentType::Predator, self.predators.len())
+            {
                 break;
             }
             
-            let x = rng.gen_range(0.0..self.params.world.width);
-            let y = rng.gen_range(0.0..self.params.world.height);
-            let predator = Predator::new(
-                self.next_id,
-                Vector2::new(x, y),
-                self.params.predator.clone(),
-            );
+            let Some(position) = self.random_spawn_position(&mut rng) else {
+                continue;
+            };
+            let id = self.allocate_id(AgentType::Predator, None);
+            let predator = Predator::new(id, position, self.params.predator.clone());
             self.predators.push(predator);
-            self.next_id += 1;
             spawned += 1;
         }
         
+        self.rng = rng;
         spawned
     }
 
     /// Spawn additional prey at random positions
     pub fn spawn_prey(&mut self, count: u32) -> u32 {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        let mut rng = self.take_rng();
         let mut spawned = 0;
         
         for _ in 0..count {
             // Check max agents limit
-            if self.total_agents() >= self.params.simulation.max_agents as usize {
+            if self.total_agents() >= self.params.simulation.max_agents as usize
+                || !self.below_type_cap(AgentType::Prey, self.prey.len())
+            {
                 break;
             }
             
-            let x = rng.gen_range(0.0..self.params.world.width);
-            let y = rng.gen_range(0.0..self.params.world.height);
-            let prey = Prey::new(self.next_id, Vector2::new(x, y), self.params.prey.clone());
+            let Some(position) = self.random_spawn_position(&mut rng) else {
+                continue;
+            };
+            let id = self.allocate_id(AgentType::Prey, None);
+            let prey = Prey::new(id, position, self.params.prey.clone());
             self.prey.push(prey);
-            self.next_id += 1;
             spawned += 1;
         }
         
+        self.rng = rng;
         spawned
     }
 
+    /// Spawn one predator at `pos` (kept inside the world); None if the
+    /// population limits are already reached
+    pub fn spawn_predator_at(&mut self, pos: Vector2) -> Option<AgentId> {
+        if !self.has_room_for(AgentType::Predator) {
+            return None;
+        }
+        let id = self.allocate_id(AgentType::Predator, None);
+        let position = self.inside_world(pos);
+        self.predators.push(Predator::new(id, position, self.params.predator.clone()));
+        Some(id)
+    }
+
+    /// Spawn one prey at `pos` (kept inside the world); None if the
+    /// population limits are already reached
+    pub fn spawn_prey_at(&mut self, pos: Vector2) -> Option<AgentId> {
+        if !self.has_room_for(AgentType::Prey) {
+            return None;
+        }
+        let id = self.allocate_id(AgentType::Prey, None);
+        let position = self.inside_world(pos);
+        self.prey.push(Prey::new(id, position, self.params.prey.clone()));
+        Some(id)
+    }
+
+    /// Look up any agent by id
+    pub fn agent(&self, id: AgentId) -> Option<&dyn Agent> {
+        if let Some(p) = self.predators.iter().find(|p| p.id() == id) {
+            return Some(p);
+        }
+        self.prey.iter().find(|p| p.id() == id).map(|p| p as &dyn Agent)
+    }
+
+    /// Get the type of the agent with the given id, if it exists
+    pub fn agent_type_of(&self, id: AgentId) -> Option<AgentType> {
+        if self.predators.iter().any(|p| p.id() == id) {
+            Some(AgentType::Predator)
+        } else if self.prey.iter().any(|p| p.id() == id) {
+            Some(AgentType::Prey)
+        } else {
+            None
+        }
+    }
+
+    /// Find the agent nearest to a world position within the given radius
+    pub fn agent_at(&self, pos: Vector2, radius: f64) -> Option<AgentId> {
+        let world_width = self.params.world.width;
+        let world_height = self.params.world.height;
+
+        self.predators
+            .iter()
+            .map(|p| Self::neighbor_info(p, pos, world_width, world_height))
+            .chain(self.prey.iter().map(|p| Self::neighbor_info(p, pos, world_width, world_height)))
+            .filter(|n| n.distance <= radius)
+            .min_by(compare_neighbors)
+            .map(|n| n.id)
+    }
+
+    /// Ids of all agents within `radius` of a world point (torus distance),
+    /// nearest first
+    pub fn agents_within(&self, pos: Vector2, radius: f64) -> Vec<AgentId> {
+        let world_width = self.params.world.width;
+        let world_height = self.params.world.height;
+
+        let mut hits: Vec<NeighborInfo> = self
+            .predators
+            .iter()
+            .map(|p| Self::neighbor_info(p, pos, world_width, world_height))
+            .chain(self.prey.iter().map(|p| Self::neighbor_info(p, pos, world_width, world_height)))
+            .filter(|n| n.distance <= radius)
+            .collect();
+        hits.sort_unstable_by(compare_neighbors);
+        hits.into_iter().map(|n| n.id).collect()
+    }
+
+    /// The predator nearest to a world point (ties go to the lower id), or None
+    /// without predators. Distance follows the boundary: torus distance under
+    /// wraparound, straight-line distance between walls.
+    pub fn nearest_predator(&self, point: Vector2) -> Option<AgentId> {
+        self.nearest_predator_in(&self.territory_grid(), point)
+    }
+
+    /// Predator territories on a `cols` x `rows` grid over the world: the
+    /// nearest predator to each cell center, indexed `row * cols + col`
+    pub fn predator_territories(&self, cols: usize, rows: usize) -> Vec<Option<AgentId>> {
+        let grid = self.territory_grid();
+        let cell_width = self.params.world.width / cols.max(1) as f64;
+        let cell_height = self.params.world.height / rows.max(1) as f64;
+        (0..rows * cols)
+            .map(|i| {
+                let (row, col) = (i / cols, i % cols);
+                let center = Vector2::new((col as f64 + 0.5) * cell_width, (row as f64 + 0.5) * cell_height);
+                self.nearest_predator_in(&grid, center)
+            })
+            .collect()
+    }
+
+    /// Predator index with cells sized for about one predator each
+    fn territory_grid(&self) -> SpatialGrid {
+        let area = self.params.world.width * self.params.world.height;
+        let cell_size = (area / self.predators.len().max(1) as f64).sqrt();
+        SpatialGrid::new(
+            self.predators.iter().map(|p| p.position()),
+            self.params.world.width,
+            self.params.world.height,
+            cell_size,
+        )
+    }
+
+    /// Nearest predator to `point`, searching `grid` in growing radii until
+    /// the best hit is closer than the searched radius
+    fn nearest_predator_in(&self, grid: &SpatialGrid, point: Vector2) -> Option<AgentId> {
+        let world_width = self.params.world.width;
+        let world_height = self.params.world.height;
+        let full_radius = world_width.hypot(world_height);
+        let mut radius = (world_width * world_height / self.predators.len().max(1) as f64).sqrt();
+
+        loop {
+            let mut best: Option<(f64, AgentId)> = None;
+            grid.for_each_candidate(point, radius, |index| {
+                let predator = &self.predators[index];
+                let separation = match self.params.world.boundary_type {
+                    BoundaryType::Wraparound => {
+                        distance_torus(&point, &predator.position(), world_width, world_height)
+                    }
+                    BoundaryType::Walls => distance(&point, &predator.position()),
+                };
+                let candidate = (separation, predator.id());
+                if best.is_none_or(|b| candidate.0.total_cmp(&b.0).then(candidate.1.cmp(&b.1)).is_lt()) {
+                    best = Some(candidate);
+                }
+            });
+            match best {
+                // Anything closer would have been a candidate at this radius
+                Some((distance, id)) if distance <= radius => return Some(id),
+                _ if radius >= full_radius => return best.map(|(_, id)| id),
+                _ => radius *= 2.0,
+            }
+        }
+    }
+
+    /// Remove an agent immediately. Returns false if no agent has that id.
+    pub fn kill_agent(&mut self, id: AgentId) -> bool {
+        let before = self.total_agents();
+        self.predators.retain(|p| p.id() != id);
+        self.prey.retain(|p| p.id() != id);
+        self.total_agents() < before
+    }
+
+    /// Infect a prey with the disease. Returns false if there is no such prey
+    /// or it is already infected or immune.
+    pub fn infect_prey(&mut self, id: AgentId) -> bool {
+        self.prey
+            .iter_mut()
+            .find(|p| p.id() == id)
+            .is_some_and(|p| p.infect())
+    }
+
+    /// Set an agent's energy. Returns false if no agent has that id.
+    pub fn set_agent_energy(&mut self, id: AgentId, energy: f64) -> bool {
+        if let Some(predator) = self.predators.iter_mut().find(|p| p.id() == id) {
+            predator.set_energy(energy);
+            true
+        } else if let Some(prey) = self.prey.iter_mut().find(|p| p.id() == id) {
+            prey.set_energy(energy);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Immediately spawn one offspring next to an agent, at no cost to the parent.
+    /// Returns the offspring's id, or None if the parent doesn't exist or the
+    /// world is already at `max_agents` (or the parent's type at its own cap).
+    pub fn force_reproduce(&mut self, id: AgentId) -> Option<AgentId> {
+        if self.total_agents() >= self.params.simulation.max_agents as usize {
+            return None;
+        }
+        let agent_type = self.agent_type_of(id)?;
+        let population = match agent_type {
+            AgentType::Predator => self.predators.len(),
+            AgentType::Prey => self.prey.len(),
+        };
+        if !self.below_type_cap(agent_type, population) {
+            return None;
+        }
+
+        let child_id = self.allocate_id(agent_type, Some(id));
+        let mut rng = self.take_rng();
+        if let Some(parent) = self.predators.iter().find(|p| p.id() == id) {
+            let position = self.offspring_position(parent.position(), &mut rng);
+            let energy = self.offspring_energy(self.params.predator.newborn_energy());
+            self.predators.push(Predator::new_with_energy(child_id, position, self.params.predator.clone(), energy));
+        } else if let Some(parent) = self.prey.iter().find(|p| p.id() == id) {
+            let position = self.offspring_position(parent.position(), &mut rng);
+            let energy = self.offspring_energy(self.params.prey.newborn_energy());
+            self.prey.push(Prey::new_with_energy(child_id, position, self.params.prey.clone(), energy));
+        } else {
+            self.rng = rng;
+            return None;
+        }
+        self.rng = rng;
+
+        Some(child_id)
+    }
+
+    /// Chance that a capture of `target_id` succeeds under the dilution effect
+    fn capture_probability(&self, target_id: AgentId) -> f64 {
+        let factor = self.params.predator.dilution_factor;
+        if factor <= 0.0 {
+            return 1.0;
+        }
+        let Some(target) = self.prey.iter().find(|p| p.id() == target_id) else {
+            return 1.0;
+        };
+
+        let world_width = self.params.world.width;
+        let world_height = self.params.world.height;
+        let radius = self.params.prey.detection_radius;
+        let others = self
+            .prey
+            .iter()
+            .filter(|p| p.id() != target_id)
+            .filter(|p| distance_torus(&target.position(), &p.position(), world_width, world_height) <= radius)
+            .count();
+        1.0 / (1.0 + factor * others as f64)
+    }
+
+    /// Next agent id; structured ids record the species and one generation
+    /// past `parent` (founders are generation 0)
+    fn allocate_id(&mut self, agent_type: AgentType, parent: Option<AgentId>) -> AgentId {
+        let sequence = self.next_id;
+        self.next_id += 1;
+        if self.params.simulation.structured_ids {
+            let generation = parent.map_or(0, |p| p.generation() + 1);
+            AgentId::structured(agent_type, generation, sequence as u32)
+        } else {
+            AgentId::flat(sequence)
+        }
+    }
+
+    /// Energy a newborn starts with: what its parent granted, bounded by
+    /// `max_offspring_energy` if set
+    fn offspring_energy(&self, granted: f64) -> f64 {
+        let cap = self.params.simulation.max_offspring_energy;
+        if cap > 0.0 {
+            granted.min(cap)
+        } else {
+            granted
+        }
+    }
+
+    /// Whether another birth fits under `max_births_per_tick` this tick
+    fn birth_allowed(&self, births: u32) -> bool {
+        let max = self.params.simulation.max_births_per_tick;
+        max == 0 || births < max
+    }
+
+    /// Whether a population of `count` may still grow under its per-type cap
+    fn below_type_cap(&self, agent_type: AgentType, count: usize) -> bool {
+        let cap = match agent_type {
+            AgentType::Predator => self.params.simulation.max_predators,
+            AgentType::Prey => self.params.simulation.max_prey,
+        };
+        cap == 0 || count < cap as usize
+    }
+
+    /// Whether `max_agents` and the per-species cap allow another agent of this type
+    fn has_room_for(&self, agent_type: AgentType) -> bool {
+        let count = match agent_type {
+            AgentType::Predator => self.predators.len(),
+            AgentType::Prey => self.prey.len(),
+        };
+        self.total_agents() < self.params.simulation.max_agents as usize && self.below_type_cap(agent_type, count)
+    }
+
+    /// Map a point into the world according to the boundary type
+    fn inside_world(&self, pos: Vector2) -> Vector2 {
+        let (width, height) = (self.params.world.width, self.params.world.height);
+        match self.params.world.boundary_type {
+            BoundaryType::Wraparound => wrap_position(pos, width, height),
+            BoundaryType::Walls => clamp_position(pos, width, height),
+        }
+    }
+
+    /// Pick a spawn position near a parent, kept inside the world and out of
+    /// obstacles (falls back to the parent's own position)
+    fn offspring_position(&self, parent: Vector2, rng: &mut WorldRng) -> Vector2 {
+        use rand::Rng;
+        for _ in 0..MAX_SPAWN_ATTEMPTS {
+            let angle = rng.gen::<f64>() * std::f64::consts::PI * 2.0;
+            let distance = rng.gen::<f64>() * 20.0;
+            let spawn_pos = parent.add(&crate::utils::math::from_angle(angle, distance));
+            let spawn_pos = Vector2 {
+                x: spawn_pos.x.max(10.0).min(self.params.world.width - 10.0),
+                y: spawn_pos.y.max(10.0).min(self.params.world.height - 10.0),
+            };
+            if self.is_free(spawn_pos) {
+                return spawn_pos;
+            }
+        }
+        parent
+    }
+
+    /// `position` if it is outside all obstacles, otherwise `fallback`
+    fn free_or(&self, position: Vector2, fallback: Vector2) -> Vector2 {
+        if self.is_free(position) {
+            position
+        } else {
+            fallback
+        }
+    }
+
+    /// Sample a uniformly random position outside all obstacles, resampling up
+    /// to `MAX_SPAWN_ATTEMPTS` times. None if no free position was found.
+    fn random_free_position(&self, rng: &mut impl rand::Rng) -> Option<Vector2> {
+        (0..MAX_SPAWN_ATTEMPTS)
+            .map(|_| {
+                Vector2::new(
+                    rng.gen_range(0.0..self.params.world.width),
+                    rng.gen_range(0.0..self.params.world.height),
+                )
+            })
+            .find(|pos| self.is_free(*pos))
+    }
+
+    /// Sample a random free position for a new agent, at least `spawn_margin`
+    /// from every edge, that also keeps at least `min_spawn_separation` from
+    /// every existing agent, resampling up to `MAX_SPAWN_ATTEMPTS` times.
+    /// None if no such position was found.
+    fn random_spawn_position(&self, rng: &mut impl rand::Rng) -> Option<Vector2> {
+        let world_width = self.params.world.width;
+        let world_height = self.params.world.height;
+        let separation = self.params.world.min_spawn_separation;
+        let min_distance_squared = separation * separation;
+        let far_enough = |pos: &Vector2| {
+            separation <= 0.0
+                || self
+                    .predators
+                    .iter()
+                    .map(|p| p.position())
+                    .chain(self.prey.iter().map(|p| p.position()))
+                    .all(|other| distance_torus_squared(pos, &other, world_width, world_height) >= min_distance_squared)
+        };
+
+        // A margin wider than half the world collapses onto the center line
+        let margin = self.params.world.spawn_margin.max(0.0);
+        let within = |extent: f64| {
+            let m = margin.min(extent / 2.0);
+            (m, (extent - m).max(m + f64::EPSILON))
+        };
+        let (min_x, max_x) = within(world_width);
+        let (min_y, max_y) = within(world_height);
+
+        (0..MAX_SPAWN_ATTEMPTS)
+            .map(|_| Vector2::new(rng.gen_range(min_x..max_x), rng.gen_range(min_y..max_y)))
+            .find(|pos| self.is_free(*pos) && far_enough(pos))
+    }
+
     /// Clear all agents
     pub fn clear_all(&mut self) {
         self.predators.clear();
         self.prey.clear();
     }
 
+    /// Get the centroid of all agents of a type (None if there are none).
+    /// Uses the circular mean under wraparound so clusters spanning a seam
+    /// are centred on the seam rather than the middle of the world.
+    pub fn centroid(&self, agent_type: AgentType) -> Option<Vector2> {
+        let positions: Vec<Vector2> = match agent_type {
+            AgentType::Predator => self.predators.iter().map(|p| p.position()).collect(),
+            AgentType::Prey => self.prey.iter().map(|p| p.position()).collect(),
+        };
+
+        if positions.is_empty() {
+            return None;
+        }
+
+        match self.params.world.boundary_type {
+            BoundaryType::Wraparound => Some(torus_mean(
+                &positions,
+                self.params.world.width,
+                self.params.world.height,
+            )),
+            BoundaryType::Walls => {
+                let sum = positions.iter().fold(Vector2::zero(), |acc, p| acc.add(p));
+                Some(sum.scale(1.0 / positions.len() as f64))
+            }
+        }
+    }
+
+    /// Count agents per cell on a `cols` x `rows` grid over the world,
+    /// optionally restricted to one agent type. Indexed `grid[row][col]`.
+    pub fn density_grid(&self, cols: usize, rows: usize, agent_type: Option<AgentType>) -> Vec<Vec<usize>> {
+        let predators = self
+            .predators
+            .iter()
+            .filter(|_| agent_type != Some(AgentType::Prey))
+            .map(|p| p.position());
+        let prey = self
+            .prey
+            .iter()
+            .filter(|_| agent_type != Some(AgentType::Predator))
+            .map(|p| p.position());
+
+        crate::simulation::spatial::density_grid(
+            predators.chain(prey),
+            cols,
+            rows,
+            self.params.world.width,
+            self.params.world.height,
+        )
+    }
+
+    /// Moran's I spatial autocorrelation of one species' presence on a coarse
+    /// grid. Grid edges are adjacent under wraparound.
+    pub fn morans_i(&self, agent_type: AgentType) -> f64 {
+        let grid = self.density_grid(MORANS_I_GRID_CELLS, MORANS_I_GRID_CELLS, Some(agent_type));
+        let wrap = self.params.world.boundary_type == BoundaryType::Wraparound;
+        crate::simulation::spatial::morans_i(&grid, wrap)
+    }
+
+    /// One JSON object per living agent (id, type, position, velocity, energy,
+    /// age) for the current tick, one per line
+    pub fn dump_agents_jsonl(&self) -> String {
+        let agents = self
+            .predators
+            .iter()
+            .map(|p| p as &dyn Agent)
+            .chain(self.prey.iter().map(|p| p as &dyn Agent));
+
+        let mut jsonl = String::new();
+        for agent in agents {
+            let record = crate::utils::export::AgentRecord {
+                tick: self.tick,
+                id: agent.id(),
+                agent_type: match agent.agent_type() {
+                    AgentType::Predator => "predator".to_string(),
+                    AgentType::Prey => "prey".to_string(),
+                },
+                x: agent.position().x,
+                y: agent.position().y,
+                vx: agent.velocity().x,
+                vy: agent.velocity().y,
+                energy: agent.energy(),
+                age: agent.age(),
+            };
+            // Plain numbers and strings: serialization cannot fail
+            jsonl.push_str(&serde_json::to_string(&record).unwrap_or_default());
+            jsonl.push('\n');
+        }
+        jsonl
+    }
+
     /// Get average energy for predators
     pub fn average_predator_energy(&self) -> f64 {
         if self.predators.is_empty() {
@@ -331,4 +1511,49 @@ impl World {
         let sum: f64 = self.prey.iter().map(|p| p.energy()).sum();
         sum / self.prey.len() as f64
     }
+
+    /// Total energy held by predators, prey and uneaten food
+    pub fn energy_totals(&self) -> EnergyTotals {
+        EnergyTotals {
+            predators: self.predators.iter().map(|p| p.energy()).sum(),
+            prey: self.prey.iter().map(|p| p.energy()).sum(),
+            food: self.food.len() as f64 * self.params.world.food_energy,
+        }
+    }
+
+    /// Get mean speed (velocity magnitude) of predators
+    pub fn average_predator_speed(&self) -> f64 {
+        if self.predators.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = self.predators.iter().map(|p| p.velocity().magnitude()).sum();
+        sum / self.predators.len() as f64
+    }
+
+    /// Get mean speed (velocity magnitude) of prey
+    pub fn average_prey_speed(&self) -> f64 {
+        if self.prey.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = self.prey.iter().map(|p| p.velocity().magnitude()).sum();
+        sum / self.prey.len() as f64
+    }
+}
+
+/// Cell centers of the smallest near-square grid with at least `count` cells,
+/// in row-major order (only the first `count` are returned)
+fn lattice_positions(count: usize, width: f64, height: f64) -> Vec<Vector2> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let cols = (count as f64).sqrt().ceil() as usize;
+    let rows = count.div_ceil(cols);
+    let (cell_width, cell_height) = (width / cols as f64, height / rows as f64);
+
+    (0..count)
+        .map(|i| {
+            let (row, col) = (i / cols, i % cols);
+            Vector2::new((col as f64 + 0.5) * cell_width, (row as f64 + 0.5) * cell_height)
+        })
+        .collect()
 }
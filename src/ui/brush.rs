@@ -0,0 +1,54 @@
+// Energy painting brush for debugging energy dynamics
+
+use crate::simulation::agent::AgentId;
+use crate::simulation::world::World;
+use crate::utils::math::Vector2;
+
+/// What dragging over the world does to agent energy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BrushMode {
+    #[default]
+    Off,
+    /// Add energy to agents under the brush
+    HealBrush,
+    /// Take energy from agents under the brush (never below zero)
+    DrainBrush,
+}
+
+/// Brush that changes the energy of every agent within `radius` of the cursor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnergyBrush {
+    pub mode: BrushMode,
+    /// Brush radius in world units
+    pub radius: f64,
+    /// Energy added or removed per agent per frame
+    pub strength: f64,
+}
+
+impl Default for EnergyBrush {
+    fn default() -> Self {
+        Self {
+            mode: BrushMode::Off,
+            radius: 30.0,
+            strength: 2.0,
+        }
+    }
+}
+
+impl EnergyBrush {
+    /// Apply one frame of the brush at a world point; returns the agents touched
+    pub fn apply(&self, world: &mut World, center: Vector2) -> Vec<AgentId> {
+        let delta = match self.mode {
+            BrushMode::Off => return Vec::new(),
+            BrushMode::HealBrush => self.strength,
+            BrushMode::DrainBrush => -self.strength,
+        };
+        let touched = world.agents_within(center, self.radius);
+        for &id in &touched {
+            if let Some(energy) = world.agent(id).map(|agent| agent.energy()) {
+                world.set_agent_energy(id, (energy + delta).max(0.0));
+            }
+        }
+        touched
+    }
+}
@@ -1,7 +1,23 @@
 // Sliders, buttons, parameter UI
 
 use crate::config::parameters::*;
-use crate::ui::visualization::VisualizationSettings;
+use crate::config::ranges;
+use crate::ui::brush::{BrushMode, EnergyBrush};
+use crate::ui::visualization::{Camera, VisualizationSettings};
+use crate::utils::rng::RngAlgorithm;
+
+/// Lifespan (ticks) filled in when a max-age limit is switched on
+const DEFAULT_MAX_AGE: u32 = 3000;
+
+/// What a left click on the canvas drops into the world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpawnMode {
+    /// Clicks select agents instead
+    #[default]
+    Off,
+    Predator,
+    Prey,
+}
 
 /// UI state for controls
 pub struct ControlPanel {
@@ -9,10 +25,25 @@ pub struct ControlPanel {
     pub viz_settings: VisualizationSettings,
     pub paused: bool,
     pub speed_multiplier: f64,
-    pub spawn_predators: u32,
-    pub spawn_prey: u32,
+    /// Time constant (seconds) over which the effective speed eases toward
+    /// `speed_multiplier` (0 = snap instantly)
+    pub speed_smoothing: f64,
     pub spawn_predators_requested: bool,
     pub spawn_prey_requested: bool,
+    pub freeze_predators: bool,
+    pub freeze_prey: bool,
+    /// Pause the run when a parameter change first triggers a stability warning
+    pub pause_on_warning: bool,
+    /// Apply the suggested fix for stability warnings automatically
+    pub auto_clamp: bool,
+    /// Debug brush for painting agent energy by dragging over the world
+    pub energy_brush: EnergyBrush,
+    /// Species placed by clicking the canvas
+    pub spawn_mode: SpawnMode,
+    /// Outcome of the last preset save or load: a confirmation or an error
+    pub preset_status: Option<Result<String, String>>,
+    /// Whether warnings were showing last frame, so a pause fires only once
+    warnings_shown: bool,
 }
 
 impl Default for ControlPanel {
@@ -22,15 +53,88 @@ impl Default for ControlPanel {
             viz_settings: VisualizationSettings::default(),
             paused: false,
             speed_multiplier: 1.0,
-            spawn_predators: 10,
-            spawn_prey: 50,
+            speed_smoothing: 0.3,
             spawn_predators_requested: false,
             spawn_prey_requested: false,
+            freeze_predators: false,
+            freeze_prey: false,
+            pause_on_warning: false,
+            auto_clamp: false,
+            energy_brush: EnergyBrush::default(),
+            spawn_mode: SpawnMode::default(),
+            preset_status: None,
+            warnings_shown: false,
         }
     }
 }
 
 impl ControlPanel {
+    /// Parameters a reset should start from. The population sliders are bound
+    /// directly to `initial_count`, so this is the single source of truth.
+    pub fn reset_parameters(&self) -> Parameters {
+        self.params.clone()
+    }
+
+    /// Inline stability warnings with their suggested fixes
+    fn show_warnings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.pause_on_warning, "Pause on Warning");
+            ui.checkbox(&mut self.auto_clamp, "Auto-Clamp");
+        });
+
+        if self.auto_clamp {
+            self.params.clamp_unstable();
+        }
+
+        let warnings = self.params.warnings();
+        if !warnings.is_empty() && !self.warnings_shown && self.pause_on_warning {
+            self.paused = true;
+        }
+        self.warnings_shown = !warnings.is_empty();
+
+        for warning in &warnings {
+            ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", warning));
+            ui.horizontal(|ui| {
+                ui.label(format!("Suggested: {}", warning.suggestion()));
+                if ui.small_button("Apply").clicked() {
+                    warning.apply_fix(&mut self.params);
+                }
+            });
+        }
+    }
+
+    /// Write the current parameters to `path` as a TOML preset
+    pub fn save_preset(&mut self, path: &str) {
+        self.preset_status = Some(match self.params.save_to_file(path) {
+            Ok(()) => Ok(format!("Saved preset to {}", path)),
+            Err(e) => Err(format!("Could not save preset: {}", e)),
+        });
+    }
+
+    /// Replace the parameters (population sliders included) with the preset
+    /// at `path`. If it can't be read, parsed or validated, the current
+    /// parameters stay and the error is reported instead.
+    pub fn load_preset(&mut self, path: &str) {
+        self.preset_status = Some(match Parameters::load_from_file(path) {
+            Ok(params) => {
+                self.params = params;
+                Ok(format!("Loaded preset from {}", path))
+            }
+            Err(e) => Err(format!("Could not load preset: {}", e)),
+        });
+    }
+
+    /// Native dialog for a preset file, to save or to open
+    fn preset_dialog(save: bool) -> Option<String> {
+        let dialog = rfd::FileDialog::new().add_filter("Parameter preset", &["toml"]);
+        let path = if save {
+            dialog.set_file_name("preset.toml").save_file()
+        } else {
+            dialog.pick_file()
+        };
+        path.map(|path| path.to_string_lossy().into_owned())
+    }
+
     /// Show the control panel with all sliders and buttons
     pub fn show(&mut self, ui: &mut egui::Ui) -> (bool, bool, bool, bool) {
         let mut reset_requested = false;
@@ -52,13 +156,28 @@ impl ControlPanel {
             }
             
             if ui.button("💾 Save Preset").clicked() {
-                // TODO: Implement save
+                if let Some(path) = Self::preset_dialog(true) {
+                    self.save_preset(&path);
+                }
             }
             
             if ui.button("📂 Load Preset").clicked() {
-                // TODO: Implement load
+                if let Some(path) = Self::preset_dialog(false) {
+                    self.load_preset(&path);
+                }
             }
         });
+        match &self.preset_status {
+            Some(Ok(message)) => {
+                ui.label(message);
+            }
+            Some(Err(error)) => {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+            None => {}
+        }
+        
+        self.show_warnings(ui);
         
         ui.separator();
         
@@ -67,9 +186,7 @@ impl ControlPanel {
         ui.horizontal(|ui| {
             ui.vertical(|ui| {
                 ui.label("Initial Predators:");
-                let val = self.spawn_predators;
-                ui.add(egui::Slider::new(&mut self.spawn_predators, 0..=500)
-                    .text(format!("{}", val)));
+                ui.add(egui::Slider::new(&mut self.params.predator.initial_count, ranges::PREDATOR_INITIAL_COUNT));
                 if ui.button("🐺 Spawn Now").clicked() {
                     self.spawn_predators_requested = true;
                 }
@@ -79,19 +196,29 @@ impl ControlPanel {
             
             ui.vertical(|ui| {
                 ui.label("Initial Prey:");
-                let val = self.spawn_prey;
-                ui.add(egui::Slider::new(&mut self.spawn_prey, 0..=500)
-                    .text(format!("{}", val)));
+                ui.add(egui::Slider::new(&mut self.params.prey.initial_count, ranges::PREY_INITIAL_COUNT));
                 if ui.button("🐰 Spawn Now").clicked() {
                     self.spawn_prey_requested = true;
                 }
             });
         });
         
-        ui.label(format!("Current: {} predators, {} prey", 
+        ui.horizontal(|ui| {
+            ui.label("Click to Spawn:");
+            ui.selectable_value(&mut self.spawn_mode, SpawnMode::Off, "Off");
+            ui.selectable_value(&mut self.spawn_mode, SpawnMode::Predator, "🐺 Predator");
+            ui.selectable_value(&mut self.spawn_mode, SpawnMode::Prey, "🐰 Prey");
+        });
+        
+        ui.label(format!("On reset: {} predators, {} prey", 
             self.params.predator.initial_count, 
             self.params.prey.initial_count));
         
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.freeze_predators, "❄ Freeze Predators");
+            ui.checkbox(&mut self.freeze_prey, "❄ Freeze Prey");
+        });
+        
         ui.separator();
         
         // Speed control
@@ -99,6 +226,8 @@ impl ControlPanel {
         let speed_value = self.speed_multiplier;
         ui.add(egui::Slider::new(&mut self.speed_multiplier, 0.1..=5.0)
             .text(format!("{:.1}x", speed_value)));
+        ui.add(egui::Slider::new(&mut self.speed_smoothing, 0.0..=2.0)
+            .text("Speed Smoothing (s)"));
         
         ui.separator();
         
@@ -118,38 +247,119 @@ impl ControlPanel {
         (params_changed, reset_requested, self.spawn_predators_requested, self.spawn_prey_requested)
     }
     
+    /// Checkbox enabling a max age, with a slider for it while enabled
+    fn show_lifespan(ui: &mut egui::Ui, max_age: &mut Option<u32>, range: std::ops::RangeInclusive<u32>) {
+        ui.horizontal(|ui| {
+            let mut limited = max_age.is_some();
+            if ui.checkbox(&mut limited, "Max Lifespan").changed() {
+                *max_age = limited.then_some(DEFAULT_MAX_AGE);
+            }
+            if let Some(age) = max_age {
+                ui.add(egui::Slider::new(age, range).text("ticks"));
+            }
+        });
+    }
+    
+    /// Checkbox overriding newborn energy, with a slider for it while enabled
+    fn show_offspring_energy(ui: &mut egui::Ui, offspring_energy: &mut Option<f64>, initial_energy: f64, range: std::ops::RangeInclusive<f64>) {
+        ui.horizontal(|ui| {
+            let mut custom = offspring_energy.is_some();
+            if ui.checkbox(&mut custom, "Offspring Energy").changed() {
+                *offspring_energy = custom.then_some(initial_energy);
+            }
+            if let Some(energy) = offspring_energy {
+                ui.add(egui::Slider::new(energy, range));
+            }
+        });
+    }
+    
     fn show_predator_controls(&mut self, ui: &mut egui::Ui) -> bool {
         let _changed = false;
         
         egui::CollapsingHeader::new("🐺 Predator Parameters")
             .default_open(true)
             .show(ui, |ui| {
-                ui.add(egui::Slider::new(&mut self.params.predator.initial_energy, 10.0..=500.0)
+                ui.add(egui::Slider::new(&mut self.params.predator.initial_energy, ranges::PREDATOR_INITIAL_ENERGY)
                     .text("Initial Energy"));
                 
-                ui.add(egui::Slider::new(&mut self.params.predator.max_speed, 0.5..=10.0)
+                ui.add(egui::Slider::new(&mut self.params.predator.max_speed, ranges::PREDATOR_MAX_SPEED)
                     .text("Max Speed"));
                 
-                ui.add(egui::Slider::new(&mut self.params.predator.perception_radius, 10.0..=200.0)
+                ui.add(egui::Slider::new(&mut self.params.predator.perception_radius, ranges::PREDATOR_PERCEPTION_RADIUS)
                     .text("Perception Radius"));
                 
-                ui.add(egui::Slider::new(&mut self.params.predator.capture_distance, 1.0..=20.0)
+                ui.add(egui::Slider::new(&mut self.params.predator.capture_distance, ranges::PREDATOR_CAPTURE_DISTANCE)
                     .text("Capture Distance"));
                 
-                ui.add(egui::Slider::new(&mut self.params.predator.energy_per_tick, 0.1..=5.0)
+                ui.checkbox(&mut self.params.predator.rear_attack_only, "Capture Only From Behind");
+                ui.add(egui::Slider::new(&mut self.params.predator.dilution_factor, ranges::PREDATOR_DILUTION_FACTOR)
+                    .text("Dilution Factor (0 = off)"));
+                
+                ui.horizontal(|ui| {
+                    ui.label("Pursuit:");
+                    ui.selectable_value(
+                        &mut self.params.predator.pursuit,
+                        PursuitMode::DirectSeek,
+                        "Direct",
+                    );
+                    ui.selectable_value(
+                        &mut self.params.predator.pursuit,
+                        PursuitMode::Intercept,
+                        "Intercept",
+                    );
+                });
+                
+                ui.checkbox(&mut self.params.predator.prefer_high_energy_prey, "Prefer High-Energy Prey");
+                ui.checkbox(&mut self.params.predator.alarm_attracts_predators, "Attracted by Prey Alarms");
+                if self.params.predator.alarm_attracts_predators {
+                    ui.add(egui::Slider::new(&mut self.params.predator.alarm_radius, ranges::PREDATOR_ALARM_RADIUS)
+                        .text("Alarm Hearing Radius"));
+                }
+                
+                ui.add(egui::Slider::new(&mut self.params.predator.learning_rate, ranges::PREDATOR_LEARNING_RATE)
+                    .text("Perception Learning Rate"));
+                
+                if self.params.predator.learning_rate > 0.0 {
+                    ui.add(egui::Slider::new(&mut self.params.predator.min_perception, ranges::PREDATOR_MIN_PERCEPTION)
+                        .text("Min Learned Perception"));
+                    ui.add(egui::Slider::new(&mut self.params.predator.max_perception, ranges::PREDATOR_MAX_PERCEPTION)
+                        .text("Max Learned Perception"));
+                }
+                
+                ui.add(egui::Slider::new(&mut self.params.predator.energy_per_tick, ranges::PREDATOR_ENERGY_PER_TICK)
                     .text("Energy per Tick"));
                 
-                ui.add(egui::Slider::new(&mut self.params.predator.energy_gain_from_prey, 10.0..=200.0)
+                ui.add(egui::Slider::new(&mut self.params.predator.energy_gain_from_prey, ranges::PREDATOR_ENERGY_GAIN_FROM_PREY)
                     .text("Energy Gain from Prey"));
                 
-                ui.add(egui::Slider::new(&mut self.params.predator.reproduction_threshold, 50.0..=500.0)
+                ui.add(egui::Slider::new(&mut self.params.predator.reproduction_threshold, ranges::PREDATOR_REPRODUCTION_THRESHOLD)
                     .text("Reproduction Threshold"));
                 
-                ui.add(egui::Slider::new(&mut self.params.predator.reproduction_cost, 20.0..=200.0)
+                ui.add(egui::Slider::new(&mut self.params.predator.reproduction_cost, ranges::PREDATOR_REPRODUCTION_COST)
                     .text("Reproduction Cost"));
+                let initial_energy = self.params.predator.initial_energy;
+                Self::show_offspring_energy(ui, &mut self.params.predator.offspring_energy, initial_energy, ranges::PREDATOR_OFFSPRING_ENERGY);
                 
-                ui.add(egui::Slider::new(&mut self.params.predator.initial_count, 0..=100)
-                    .text("Initial Count"));
+                ui.horizontal(|ui| {
+                    ui.label("Reproduction:");
+                    ui.selectable_value(
+                        &mut self.params.predator.reproduction_strategy,
+                        ReproductionStrategy::Threshold,
+                        "Threshold",
+                    );
+                    ui.selectable_value(
+                        &mut self.params.predator.reproduction_strategy,
+                        ReproductionStrategy::Budding,
+                        "Budding",
+                    );
+                });
+                if self.params.predator.reproduction_strategy == ReproductionStrategy::Budding {
+                    ui.add(egui::Slider::new(&mut self.params.predator.budding_threshold, ranges::PREDATOR_BUDDING_THRESHOLD)
+                        .text("Budding Threshold"));
+                }
+                Self::show_lifespan(ui, &mut self.params.predator.max_age, ranges::PREDATOR_MAX_AGE);
+                ui.add(egui::Slider::new(&mut self.params.predator.wander_strength, ranges::PREDATOR_WANDER_STRENGTH)
+                    .text("Idle Wander (fraction of max speed)"));
             });
         
         true // Parameters may have changed
@@ -161,32 +371,118 @@ impl ControlPanel {
         egui::CollapsingHeader::new("🐰 Prey Parameters")
             .default_open(true)
             .show(ui, |ui| {
-                ui.add(egui::Slider::new(&mut self.params.prey.initial_energy, 10.0..=500.0)
+                ui.add(egui::Slider::new(&mut self.params.prey.initial_energy, ranges::PREY_INITIAL_ENERGY)
                     .text("Initial Energy"));
                 
-                ui.add(egui::Slider::new(&mut self.params.prey.max_speed, 0.5..=10.0)
+                ui.add(egui::Slider::new(&mut self.params.prey.max_speed, ranges::PREY_MAX_SPEED)
                     .text("Max Speed"));
                 
-                ui.add(egui::Slider::new(&mut self.params.prey.detection_radius, 10.0..=200.0)
+                ui.add(egui::Slider::new(&mut self.params.prey.detection_radius, ranges::PREY_DETECTION_RADIUS)
                     .text("Detection Radius"));
                 
-                ui.add(egui::Slider::new(&mut self.params.prey.flee_distance, 10.0..=100.0)
+                ui.add(egui::Slider::new(&mut self.params.prey.flee_distance, ranges::PREY_FLEE_DISTANCE)
                     .text("Flee Distance"));
                 
-                ui.add(egui::Slider::new(&mut self.params.prey.energy_regeneration, 0.0..=2.0)
+                ui.add(egui::Slider::new(&mut self.params.prey.memory_ticks, ranges::PREY_MEMORY_TICKS)
+                    .text("Threat Memory (ticks)"));
+                
+                ui.checkbox(&mut self.params.prey.dodge_along_walls, "Dodge Along Walls");
+                ui.add(egui::Slider::new(&mut self.params.prey.threat_speed_weight, ranges::PREY_THREAT_SPEED_WEIGHT)
+                    .text("Flee All Threats, Speed Weight (0 = nearest only)"));
+                
+                ui.checkbox(&mut self.params.prey.infection_enabled, "Disease (SIR)");
+                if self.params.prey.infection_enabled {
+                    ui.add(egui::Slider::new(&mut self.params.prey.infection_radius, ranges::PREY_INFECTION_RADIUS)
+                        .text("Infection Radius"));
+                    ui.add(egui::Slider::new(&mut self.params.prey.infection_rate, ranges::PREY_INFECTION_RATE)
+                        .text("Infection Rate (per tick)"));
+                    ui.add(egui::Slider::new(&mut self.params.prey.infection_duration, ranges::PREY_INFECTION_DURATION)
+                        .text("Infection Duration (ticks)"));
+                    ui.add(egui::Slider::new(&mut self.params.prey.infection_energy_loss, ranges::PREY_INFECTION_ENERGY_LOSS)
+                        .text("Infection Energy Loss"));
+                }
+                
+                ui.add(egui::Slider::new(&mut self.params.prey.energy_regeneration, ranges::PREY_ENERGY_REGENERATION)
                     .text("Energy Regeneration"));
                 
-                ui.add(egui::Slider::new(&mut self.params.prey.energy_loss_fleeing, 0.0..=1.0)
+                ui.add(egui::Slider::new(&mut self.params.prey.density_cap, ranges::PREY_DENSITY_CAP)
+                    .text("Density Cap (0 = off)"));
+                
+                ui.add(egui::Slider::new(&mut self.params.prey.energy_loss_fleeing, ranges::PREY_ENERGY_LOSS_FLEEING)
                     .text("Energy Loss When Fleeing"));
                 
-                ui.add(egui::Slider::new(&mut self.params.prey.reproduction_threshold, 50.0..=500.0)
+                ui.add(egui::Slider::new(&mut self.params.prey.reproduction_threshold, ranges::PREY_REPRODUCTION_THRESHOLD)
                     .text("Reproduction Threshold"));
                 
-                ui.add(egui::Slider::new(&mut self.params.prey.reproduction_cost, 20.0..=200.0)
+                ui.add(egui::Slider::new(&mut self.params.prey.reproduction_cost, ranges::PREY_REPRODUCTION_COST)
                     .text("Reproduction Cost"));
+                let initial_energy = self.params.prey.initial_energy;
+                Self::show_offspring_energy(ui, &mut self.params.prey.offspring_energy, initial_energy, ranges::PREY_OFFSPRING_ENERGY);
+                
+                ui.horizontal(|ui| {
+                    ui.label("Reproduction:");
+                    ui.selectable_value(
+                        &mut self.params.prey.reproduction_strategy,
+                        ReproductionStrategy::Threshold,
+                        "Threshold",
+                    );
+                    ui.selectable_value(
+                        &mut self.params.prey.reproduction_strategy,
+                        ReproductionStrategy::Budding,
+                        "Budding",
+                    );
+                });
+                if self.params.prey.reproduction_strategy == ReproductionStrategy::Budding {
+                    ui.add(egui::Slider::new(&mut self.params.prey.budding_threshold, ranges::PREY_BUDDING_THRESHOLD)
+                        .text("Budding Threshold"));
+                }
+                Self::show_lifespan(ui, &mut self.params.prey.max_age, ranges::PREY_MAX_AGE);
+                ui.add(egui::Slider::new(&mut self.params.prey.wander_strength, ranges::PREY_WANDER_STRENGTH)
+                    .text("Idle Wander (fraction of max speed)"));
+                ui.add(egui::Slider::new(&mut self.params.prey.crowding_suppression, ranges::PREY_CROWDING_SUPPRESSION)
+                    .text("Crowding Suppression (0 = off)"));
+                if self.params.prey.crowding_suppression > 0.0 {
+                    ui.add(egui::Slider::new(&mut self.params.prey.crowding_cap, ranges::PREY_CROWDING_CAP)
+                        .text("Crowding Cap (prey)"));
+                }
+                
+                ui.add(egui::Slider::new(&mut self.params.prey.well_fed_window, ranges::PREY_WELL_FED_WINDOW)
+                    .text("Well-fed Window (ticks, 0 = off)"));
+                ui.add(egui::Slider::new(&mut self.params.prey.maturity_age, ranges::PREY_MATURITY_AGE)
+                    .text("Maturity Age (ticks)"));
+                
+                ui.add(egui::Slider::new(&mut self.params.prey.dash_distance, ranges::PREY_DASH_DISTANCE)
+                    .text("Dash Distance (0 = off)"));
+                if self.params.prey.dash_distance > 0.0 {
+                    ui.add(egui::Slider::new(&mut self.params.prey.dash_trigger_range, ranges::PREY_DASH_TRIGGER_RANGE)
+                        .text("Dash Trigger Range"));
+                    ui.add(egui::Slider::new(&mut self.params.prey.dash_cooldown, ranges::PREY_DASH_COOLDOWN)
+                        .text("Dash Cooldown (ticks)"));
+                }
+                
+                ui.add(egui::Slider::new(&mut self.params.prey.cohesion_weight, ranges::PREY_COHESION_WEIGHT)
+                    .text("Flocking Cohesion (0 = off)"));
+                ui.add(egui::Slider::new(&mut self.params.prey.alignment_weight, ranges::PREY_ALIGNMENT_WEIGHT)
+                    .text("Flocking Alignment (0 = off)"));
+                ui.add(egui::Slider::new(&mut self.params.prey.separation_weight, ranges::PREY_SEPARATION_WEIGHT)
+                    .text("Flocking Separation (0 = off)"));
+                if self.params.prey.separation_weight > 0.0 {
+                    ui.add(egui::Slider::new(&mut self.params.prey.separation_distance, ranges::PREY_SEPARATION_DISTANCE)
+                        .text("Separation Distance"));
+                }
+                if self.params.prey.cohesion_weight > 0.0 {
+                    let curve = &mut self.params.prey.cohesion_energy_curve;
+                    ui.add(egui::Slider::new(&mut curve.hungry, ranges::PREY_COHESION_ENERGY_CURVE_HUNGRY).text("Cohesion When Hungry (x)"));
+                    ui.add(egui::Slider::new(&mut curve.fed, ranges::PREY_COHESION_ENERGY_CURVE_FED).text("Cohesion When Fed (x)"));
+                    ui.add(egui::Slider::new(&mut curve.exponent, ranges::PREY_COHESION_ENERGY_CURVE_EXPONENT).text("Cohesion Curve Exponent"));
+                }
                 
-                ui.add(egui::Slider::new(&mut self.params.prey.initial_count, 0..=200)
-                    .text("Initial Count"));
+                ui.add(egui::Slider::new(&mut self.params.prey.home_range_weight, ranges::PREY_HOME_RANGE_WEIGHT)
+                    .text("Home Range Weight (0 = off)"));
+                if self.params.prey.home_range_weight > 0.0 {
+                    ui.add(egui::Slider::new(&mut self.params.prey.comfort_energy, ranges::PREY_COMFORT_ENERGY)
+                        .text("Comfort Energy"));
+                }
             });
         
         true // Parameters may have changed
@@ -198,10 +494,10 @@ impl ControlPanel {
         egui::CollapsingHeader::new("🌍 World Parameters")
             .default_open(false)
             .show(ui, |ui| {
-                ui.add(egui::Slider::new(&mut self.params.world.width, 200.0..=2000.0)
+                ui.add(egui::Slider::new(&mut self.params.world.width, ranges::WORLD_WIDTH)
                     .text("Width"));
                 
-                ui.add(egui::Slider::new(&mut self.params.world.height, 200.0..=2000.0)
+                ui.add(egui::Slider::new(&mut self.params.world.height, ranges::WORLD_HEIGHT)
                     .text("Height"));
                 
                 ui.horizontal(|ui| {
@@ -217,6 +513,92 @@ impl ControlPanel {
                         "Walls",
                     );
                 });
+                
+                ui.add(egui::Slider::new(&mut self.params.world.min_spawn_separation, ranges::WORLD_MIN_SPAWN_SEPARATION)
+                    .text("Min Spawn Separation (0 = off)"));
+                ui.add(egui::Slider::new(&mut self.params.world.spawn_margin, ranges::WORLD_SPAWN_MARGIN)
+                    .text("Spawn Margin from Edges"));
+                
+                ui.horizontal(|ui| {
+                    ui.label("Initial Placement:");
+                    let lattice = matches!(self.params.world.spawn_pattern, SpawnPattern::Lattice { .. });
+                    if ui.selectable_label(!lattice, "Random").clicked() {
+                        self.params.world.spawn_pattern = SpawnPattern::Random;
+                    }
+                    if ui.selectable_label(lattice, "Lattice").clicked() && !lattice {
+                        self.params.world.spawn_pattern = SpawnPattern::Lattice { interleave: false };
+                    }
+                });
+                if let SpawnPattern::Lattice { interleave } = &mut self.params.world.spawn_pattern {
+                    ui.checkbox(interleave, "Interleave Predators and Prey");
+                }
+                
+                let mut gradient_enabled = self.params.world.temperature_gradient.is_some();
+                if ui.checkbox(&mut gradient_enabled, "Temperature Gradient (cold left, warm right)").changed() {
+                    self.params.world.temperature_gradient = gradient_enabled.then(TemperatureGradient::default);
+                }
+                if let Some(gradient) = &mut self.params.world.temperature_gradient {
+                    ui.add(egui::Slider::new(&mut gradient.left, ranges::WORLD_TEMPERATURE_GRADIENT_LEFT).text("Left Edge Temperature"));
+                    ui.add(egui::Slider::new(&mut gradient.right, ranges::WORLD_TEMPERATURE_GRADIENT_RIGHT).text("Right Edge Temperature"));
+                    ui.add(egui::Slider::new(&mut gradient.min_speed_factor, ranges::WORLD_TEMPERATURE_GRADIENT_MIN_SPEED_FACTOR)
+                        .text("Speed Factor When Cold"));
+                }
+                
+                let mut scent_enabled = self.params.world.scent_trails.is_some();
+                if ui.checkbox(&mut scent_enabled, "Predator Scent Trails").changed() {
+                    self.params.world.scent_trails = scent_enabled.then(ScentTrails::default);
+                }
+                if let Some(scent) = &mut self.params.world.scent_trails {
+                    ui.add(egui::Slider::new(&mut scent.cell_size, ranges::WORLD_SCENT_TRAILS_CELL_SIZE).text("Scent Cell Size"));
+                    ui.add(egui::Slider::new(&mut scent.deposit, ranges::WORLD_SCENT_TRAILS_DEPOSIT).text("Scent per Tick"));
+                    ui.add(egui::Slider::new(&mut scent.retention, ranges::WORLD_SCENT_TRAILS_RETENTION).text("Scent Retention"));
+                    ui.add(egui::Slider::new(&mut scent.detection_threshold, ranges::WORLD_SCENT_TRAILS_DETECTION_THRESHOLD)
+                        .text("Prey Smell Threshold"));
+                    ui.add(egui::Slider::new(&mut self.params.prey.scent_flee_weight, ranges::PREY_SCENT_FLEE_WEIGHT)
+                        .text("Prey Scent Flee Weight"));
+                }
+                
+                ui.horizontal(|ui| {
+                    ui.label("Generated Obstacles:");
+                    let generator = &mut self.params.world.obstacle_generator;
+                    if ui.selectable_label(generator.is_none(), "None").clicked() {
+                        *generator = None;
+                    }
+                    if ui.selectable_label(matches!(generator, Some(ObstacleGenerator::RandomBlocks { .. })), "Blocks").clicked() {
+                        *generator = Some(ObstacleGenerator::RandomBlocks { count: 10, size: 40.0 });
+                    }
+                    if ui.selectable_label(matches!(generator, Some(ObstacleGenerator::BorderRing { .. })), "Border").clicked() {
+                        *generator = Some(ObstacleGenerator::BorderRing { thickness: 20.0 });
+                    }
+                    if ui.selectable_label(matches!(generator, Some(ObstacleGenerator::MazeSimple { .. })), "Maze").clicked() {
+                        *generator = Some(ObstacleGenerator::MazeSimple { cell_size: 80.0, density: 0.3 });
+                    }
+                });
+                match &mut self.params.world.obstacle_generator {
+                    Some(ObstacleGenerator::RandomBlocks { count, size }) => {
+                        ui.add(egui::Slider::new(count, 0..=100).text("Block Count"));
+                        ui.add(egui::Slider::new(size, 5.0..=200.0).text("Block Size"));
+                    }
+                    Some(ObstacleGenerator::BorderRing { thickness }) => {
+                        ui.add(egui::Slider::new(thickness, 1.0..=100.0).text("Border Thickness"));
+                    }
+                    Some(ObstacleGenerator::MazeSimple { cell_size, density }) => {
+                        ui.add(egui::Slider::new(cell_size, 20.0..=200.0).text("Maze Cell Size"));
+                        ui.add(egui::Slider::new(density, 0.0..=1.0).text("Wall Density"));
+                    }
+                    None => {}
+                }
+                if self.params.world.obstacle_generator.is_some() {
+                    ui.label("Applied on reset");
+                }
+                
+                ui.checkbox(&mut self.params.world.enable_food, "Enable Food");
+                if self.params.world.enable_food {
+                    ui.add(egui::Slider::new(&mut self.params.world.food_spawn_rate, ranges::WORLD_FOOD_SPAWN_RATE)
+                        .text("Food Spawn Rate"));
+                    ui.add(egui::Slider::new(&mut self.params.world.food_energy, ranges::WORLD_FOOD_ENERGY)
+                        .text("Food Energy"));
+                }
             });
         
         true // Parameters may have changed
@@ -228,15 +610,80 @@ impl ControlPanel {
         egui::CollapsingHeader::new("⚙️ Simulation Parameters")
             .default_open(false)
             .show(ui, |ui| {
-                ui.add(egui::Slider::new(&mut self.params.simulation.tick_rate, 1.0..=120.0)
+                ui.add(egui::Slider::new(&mut self.params.simulation.tick_rate, ranges::SIMULATION_TICK_RATE)
                     .text("Tick Rate (Hz)"));
                 
                 self.params.simulation.update_dt();
                 
-                ui.add(egui::Slider::new(&mut self.params.simulation.max_agents, 10..=5000)
+                ui.add(egui::Slider::new(&mut self.params.simulation.max_agents, ranges::SIMULATION_MAX_AGENTS)
                     .text("Max Agents"));
+                ui.add(egui::Slider::new(&mut self.params.simulation.max_predators, ranges::SIMULATION_MAX_PREDATORS)
+                    .text("Max Predators (0 = unlimited)"));
+                ui.add(egui::Slider::new(&mut self.params.simulation.max_prey, ranges::SIMULATION_MAX_PREY)
+                    .text("Max Prey (0 = unlimited)"));
                 
                 ui.checkbox(&mut self.params.simulation.enable_reproduction, "Enable Reproduction");
+                ui.checkbox(&mut self.params.simulation.deterministic_offspring, "Deterministic Offspring Placement");
+                ui.checkbox(&mut self.params.simulation.spatial_index, "Spatial Grid Index");
+                if self.params.simulation.spatial_index {
+                    ui.horizontal(|ui| {
+                        ui.label("Index:");
+                        ui.selectable_value(&mut self.params.simulation.spatial_index_kind, SpatialIndexKind::Grid, "Grid");
+                        ui.selectable_value(&mut self.params.simulation.spatial_index_kind, SpatialIndexKind::QuadTree, "Quadtree");
+                    });
+                    if self.params.simulation.spatial_index_kind == SpatialIndexKind::QuadTree {
+                        ui.add(egui::Slider::new(&mut self.params.simulation.quadtree_capacity, ranges::SIMULATION_QUADTREE_CAPACITY)
+                            .text("Quadtree Leaf Capacity"));
+                    }
+                }
+                ui.add(egui::Slider::new(&mut self.params.simulation.sensor_noise, ranges::SIMULATION_SENSOR_NOISE)
+                    .text("Sensor Noise (stddev)"));
+                ui.add(egui::Slider::new(&mut self.params.simulation.max_offspring_energy, ranges::SIMULATION_MAX_OFFSPRING_ENERGY)
+                    .text("Max Offspring Energy (0 = no cap)"));
+                ui.add(egui::Slider::new(&mut self.params.simulation.max_births_per_tick, ranges::SIMULATION_MAX_BIRTHS_PER_TICK)
+                    .text("Max Births per Tick (0 = unlimited)"));
+                
+                ui.horizontal(|ui| {
+                    ui.label("Integration:");
+                    ui.selectable_value(
+                        &mut self.params.simulation.integration,
+                        IntegrationScheme::Euler,
+                        "Euler",
+                    );
+                    ui.selectable_value(
+                        &mut self.params.simulation.integration,
+                        IntegrationScheme::SemiImplicitEuler,
+                        "Semi-implicit",
+                    );
+                });
+                
+                ui.checkbox(&mut self.params.simulation.auto_balance.enabled, "Auto-balance Prey Regeneration");
+                if self.params.simulation.auto_balance.enabled {
+                    ui.add(egui::Slider::new(&mut self.params.simulation.auto_balance.target_prey, ranges::SIMULATION_AUTO_BALANCE_TARGET_PREY)
+                        .text("Target Prey"));
+                    ui.add(egui::Slider::new(&mut self.params.simulation.auto_balance.gain, ranges::SIMULATION_AUTO_BALANCE_GAIN)
+                        .text("Balance Gain"));
+                }
+                
+                // RNG changes take effect on reset
+                ui.horizontal(|ui| {
+                    ui.label("RNG:");
+                    ui.selectable_value(&mut self.params.simulation.rng_algorithm, RngAlgorithm::Std, "Std");
+                    ui.selectable_value(&mut self.params.simulation.rng_algorithm, RngAlgorithm::ChaCha8, "ChaCha8 (fast)");
+                });
+                ui.horizontal(|ui| {
+                    let mut seeded = self.params.simulation.seed.is_some();
+                    if ui.checkbox(&mut seeded, "Seed").changed() {
+                        self.params.simulation.seed = seeded.then_some(0);
+                    }
+                    if let Some(seed) = &mut self.params.simulation.seed {
+                        ui.add(egui::DragValue::new(seed));
+                        ui.checkbox(&mut self.params.simulation.advance_seed_on_reset, "+1 per Reset");
+                    }
+                });
+                // Affects ids allocated from now on; reset to renumber everyone
+                ui.checkbox(&mut self.params.simulation.structured_ids, "Lineage-Encoding IDs");
+                ui.checkbox(&mut self.params.simulation.shuffle_update_order, "Shuffle Update Order");
             });
         
         true // Parameters may have changed
@@ -254,6 +701,29 @@ impl ControlPanel {
                 ui.checkbox(&mut self.viz_settings.show_velocity_vectors, "Show Velocity Vectors");
                 ui.checkbox(&mut self.viz_settings.show_perception_radius, "Show Perception Radius");
                 ui.checkbox(&mut self.viz_settings.show_energy_colors, "Show Energy Colors");
+                ui.checkbox(&mut self.viz_settings.cull_offscreen, "Skip Off-screen Agents");
+                ui.checkbox(&mut self.viz_settings.debug_neighbors, "Debug: Draw Perceived Neighbors");
+                ui.horizontal(|ui| {
+                    ui.label("Debug: Energy Brush");
+                    let mode = &mut self.energy_brush.mode;
+                    ui.selectable_value(mode, BrushMode::Off, "Off");
+                    ui.selectable_value(mode, BrushMode::HealBrush, "Heal");
+                    ui.selectable_value(mode, BrushMode::DrainBrush, "Drain");
+                });
+                if self.energy_brush.mode != BrushMode::Off {
+                    ui.add(egui::Slider::new(&mut self.energy_brush.radius, 5.0..=200.0).text("Brush Radius"));
+                    ui.add(egui::Slider::new(&mut self.energy_brush.strength, 0.1..=20.0).text("Energy per Frame"));
+                }
+                ui.checkbox(&mut self.viz_settings.follow_selected, "Follow Selected Agent (click to select)");
+                ui.horizontal(|ui| {
+                    ui.label(format!("Zoom: {:.2}x (scroll to zoom, drag to pan)", self.viz_settings.camera.zoom));
+                    if ui.button("Reset View").clicked() {
+                        self.viz_settings.camera = Camera::default();
+                        self.viz_settings.follow_selected = false;
+                    }
+                });
+                ui.checkbox(&mut self.viz_settings.show_territories, "Predator Territories");
+                ui.checkbox(&mut self.viz_settings.show_border, "Show World Border");
                 ui.checkbox(&mut self.viz_settings.grid_enabled, "Show Grid");
                 
                 if self.viz_settings.grid_enabled {
@@ -0,0 +1,116 @@
+// Offscreen software rendering of frames to PNG (for making videos)
+
+use std::path::{Path, PathBuf};
+
+use crate::simulation::agent::{Agent, AgentType};
+use crate::simulation::world::World;
+use crate::ui::visualization::VisualizationSettings;
+use crate::utils::color::{Color, Colors};
+
+/// Render the world into an RGBA8 buffer of `width` x `height` pixels,
+/// scaling the world rectangle to fill the image
+pub fn render_frame(
+    world: &World,
+    settings: &VisualizationSettings,
+    width: u32,
+    height: u32,
+) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity((width * height * 4) as usize);
+    let background = Colors::background();
+    for _ in 0..width * height {
+        buffer.extend_from_slice(&[background.r, background.g, background.b, 255]);
+    }
+
+    let params = world.parameters();
+    let scale_x = width as f64 / params.world.width;
+    let scale_y = height as f64 / params.world.height;
+
+    let agents = world
+        .predators()
+        .iter()
+        .map(|p| p as &dyn Agent)
+        .chain(world.prey().iter().map(|p| p as &dyn Agent));
+
+    for agent in agents {
+        let color = if settings.show_energy_colors {
+            let max_energy = match agent.agent_type() {
+                AgentType::Predator => params.predator.initial_energy,
+                AgentType::Prey => params.prey.initial_energy,
+            };
+            let energy_factor = (agent.energy() / max_energy).clamp(0.0, 1.0);
+            Colors::energy_color(energy_factor, agent.agent_type() == AgentType::Predator)
+        } else {
+            match agent.agent_type() {
+                AgentType::Predator => Colors::predator(),
+                AgentType::Prey => Colors::prey(),
+            }
+        };
+
+        let pos = agent.position();
+        fill_circle(
+            &mut buffer,
+            width,
+            height,
+            pos.x * scale_x,
+            pos.y * scale_y,
+            settings.agent_size as f64,
+            color,
+        );
+    }
+
+    buffer
+}
+
+/// Fill a circle into an RGBA8 buffer, clipped to the image
+fn fill_circle(buffer: &mut [u8], width: u32, height: u32, cx: f64, cy: f64, radius: f64, color: Color) {
+    let min_x = (cx - radius).floor().max(0.0) as u32;
+    let max_x = ((cx + radius).ceil().max(0.0) as u32).min(width);
+    let min_y = (cy - radius).floor().max(0.0) as u32;
+    let max_y = ((cy + radius).ceil().max(0.0) as u32).min(height);
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let dx = x as f64 + 0.5 - cx;
+            let dy = y as f64 + 0.5 - cy;
+            if dx * dx + dy * dy <= radius * radius {
+                let i = ((y * width + x) * 4) as usize;
+                buffer[i..i + 4].copy_from_slice(&[color.r, color.g, color.b, 255]);
+            }
+        }
+    }
+}
+
+/// Write an RGBA8 buffer as a PNG file
+pub fn write_png(path: &Path, rgba: &[u8], width: u32, height: u32) -> std::io::Result<()> {
+    let file = std::fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(rgba)?;
+    Ok(())
+}
+
+/// Dumps one PNG per tick (`frame_000123.png`) into a directory
+pub struct FrameDumper {
+    dir: PathBuf,
+    width: u32,
+    height: u32,
+}
+
+impl FrameDumper {
+    /// Create a dumper writing `width` x `height` frames to `dir` (created if missing)
+    pub fn new(dir: impl Into<PathBuf>, width: u32, height: u32) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir, width, height })
+    }
+
+    /// Render the world's current state and write it as `frame_<tick>.png`
+    pub fn dump(&self, world: &World, settings: &VisualizationSettings) -> std::io::Result<PathBuf> {
+        let path = self.dir.join(format!("frame_{:06}.png", world.tick()));
+        let rgba = render_frame(world, settings, self.width, self.height);
+        write_png(&path, &rgba, self.width, self.height)?;
+        Ok(path)
+    }
+}
@@ -3,10 +3,23 @@
 // This module is currently simple - the layout is handled directly in the main app
 // Could be extended for custom layouts, themes, etc.
 
+pub use crate::utils::color::Theme;
+
 pub struct UILayout {
     pub show_controls: bool,
     pub show_statistics: bool,
     pub control_panel_width: f32,
+    pub theme: Theme,
+}
+
+impl UILayout {
+    /// egui visuals matching the canvas theme
+    pub fn visuals(&self) -> egui::Visuals {
+        match self.theme {
+            Theme::Light => egui::Visuals::light(),
+            Theme::Dark => egui::Visuals::dark(),
+        }
+    }
 }
 
 impl Default for UILayout {
@@ -15,6 +28,7 @@ impl Default for UILayout {
             show_controls: true,
             show_statistics: true,
             control_panel_width: 300.0,
+            theme: Theme::Light,
         }
     }
 }
@@ -1,12 +1,30 @@
 // UI module - Graphical interface components
+// Only statistics and the energy brush build without the `gui` feature
 
+#[cfg(feature = "gui")]
 pub mod controls;
+#[cfg(feature = "gui")]
 pub mod visualization;
+#[cfg(feature = "gui")]
 pub mod layout;
 pub mod statistics;
+#[cfg(feature = "gui")]
+pub mod frames;
+#[cfg(feature = "gui")]
+pub mod session;
+pub mod brush;
 
+#[cfg(feature = "gui")]
 pub use controls::ControlPanel;
+#[cfg(feature = "gui")]
 pub use visualization::{VisualizationSettings, render_world};
+#[cfg(feature = "gui")]
 pub use layout::UILayout;
-pub use statistics::{StatisticsCollector, render_population_graph, Statistics};
+pub use statistics::{StatisticsCollector, Statistics, GraphStyle};
+#[cfg(feature = "gui")]
+pub use statistics::render_population_graph;
+#[cfg(feature = "gui")]
+pub use frames::FrameDumper;
+#[cfg(feature = "gui")]
+pub use session::Session;
 
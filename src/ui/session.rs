@@ -0,0 +1,35 @@
+// Whole-session save/restore: parameters, world, statistics and view
+
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::config::parameters::Parameters;
+use crate::simulation::snapshot::WorldSnapshot;
+use crate::ui::statistics::StatisticsCollector;
+use crate::ui::visualization::VisualizationSettings;
+
+/// Everything needed to put the app back exactly where it was
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// Control panel parameters (including the seed), used by the next reset
+    pub params: Parameters,
+    /// The running world
+    pub world: WorldSnapshot,
+    /// Recorded time series
+    pub statistics: StatisticsCollector,
+    /// Display toggles and the camera
+    pub viz_settings: VisualizationSettings,
+}
+
+impl Session {
+    /// Write the session as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Read a session written by `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
@@ -1,18 +1,34 @@
 // Statistics collection and visualization
 
 use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use crate::simulation::world::EnergyTotals;
+#[cfg(feature = "gui")]
+use crate::ui::visualization::draw_dashed_line;
+use crate::utils::color::Color;
+
+/// Vertical spacing between graph legend lines
+#[cfg(feature = "gui")]
+const LEGEND_LINE_HEIGHT: f32 = 14.0;
 
 /// Data point for time series
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct DataPoint {
     pub tick: u64,
     pub predator_count: usize,
     pub prey_count: usize,
     pub predator_energy: f64,
     pub prey_energy: f64,
+    /// Mean velocity magnitude of each population (0 when it is empty)
+    pub predator_speed: f64,
+    pub prey_speed: f64,
+    /// Energy held per category, if it was recorded for this tick
+    #[serde(default)]
+    pub energy: EnergyTotals,
 }
 
 /// Statistics collector with circular buffer
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatisticsCollector {
     data: VecDeque<DataPoint>,
     max_history: usize,
@@ -30,13 +46,24 @@ impl StatisticsCollector {
     }
 
     /// Record a data point
-    pub fn record(&mut self, predator_count: usize, prey_count: usize, predator_energy: f64, prey_energy: f64) {
+    pub fn record(
+        &mut self,
+        predator_count: usize,
+        prey_count: usize,
+        predator_energy: f64,
+        prey_energy: f64,
+        predator_speed: f64,
+        prey_speed: f64,
+    ) {
         let point = DataPoint {
             tick: self.current_tick,
             predator_count,
             prey_count,
             predator_energy,
             prey_energy,
+            predator_speed,
+            prey_speed,
+            energy: EnergyTotals::default(),
         };
         
         if self.data.len() >= self.max_history {
@@ -46,6 +73,13 @@ impl StatisticsCollector {
         self.current_tick += 1;
     }
 
+    /// Attach per-category energy totals to the most recent data point
+    pub fn record_energy(&mut self, energy: EnergyTotals) {
+        if let Some(point) = self.data.back_mut() {
+            point.energy = energy;
+        }
+    }
+
     /// Get all data points
     pub fn data(&self) -> &VecDeque<DataPoint> {
         &self.data
@@ -67,6 +101,31 @@ impl StatisticsCollector {
         self.data.back()
     }
 
+    /// Export the recorded time series as CSV (one row per data point)
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("tick,predator_count,prey_count,predator_energy,prey_energy\n");
+        for point in &self.data {
+            csv.push_str(&format!(
+                "{},{},{},{:.3},{:.3}\n",
+                point.tick,
+                point.predator_count,
+                point.prey_count,
+                point.predator_energy,
+                point.prey_energy,
+            ));
+        }
+        csv
+    }
+
+    /// Export the (predator, prey) phase-space trajectory as CSV, oldest first
+    pub fn phase_csv(&self) -> String {
+        let mut csv = String::from("predator_count,prey_count\n");
+        for point in &self.data {
+            csv.push_str(&format!("{},{}\n", point.predator_count, point.prey_count));
+        }
+        csv
+    }
+
     /// Calculate statistics
     pub fn stats(&self) -> Statistics {
         if self.data.is_empty() {
@@ -81,6 +140,8 @@ impl StatisticsCollector {
         let mut min_prey = usize::MAX;
         let mut sum_predator_energy = 0.0;
         let mut sum_prey_energy = 0.0;
+        let mut sum_predator_speed = 0.0;
+        let mut sum_prey_speed = 0.0;
 
         for point in &self.data {
             total_predators += point.predator_count;
@@ -91,6 +152,8 @@ impl StatisticsCollector {
             min_prey = min_prey.min(point.prey_count);
             sum_predator_energy += point.predator_energy;
             sum_prey_energy += point.prey_energy;
+            sum_predator_speed += point.predator_speed;
+            sum_prey_speed += point.prey_speed;
         }
 
         let count = self.data.len() as f64;
@@ -104,6 +167,8 @@ impl StatisticsCollector {
             min_prey: if min_prey == usize::MAX { 0 } else { min_prey },
             average_predator_energy: sum_predator_energy / count,
             average_prey_energy: sum_prey_energy / count,
+            average_predator_speed: sum_predator_speed / count,
+            average_prey_speed: sum_prey_speed / count,
             data_points: self.data.len(),
         }
     }
@@ -120,13 +185,131 @@ pub struct Statistics {
     pub min_prey: usize,
     pub average_predator_energy: f64,
     pub average_prey_energy: f64,
+    pub average_predator_speed: f64,
+    pub average_prey_speed: f64,
     pub data_points: usize,
 }
 
+/// Achieved tick rates below this fraction of the target are flagged
+pub const TICK_RATE_WARNING_FRACTION: f64 = 0.8;
+
+/// Whether the achieved tick rate falls significantly short of the target
+pub fn is_tick_rate_low(achieved: f64, target: f64) -> bool {
+    target > 0.0 && achieved < target * TICK_RATE_WARNING_FRACTION
+}
+
+/// Counts simulation ticks over one-second windows of wall-clock time
+#[derive(Debug, Clone, Default)]
+pub struct TickRateMeter {
+    window_start: Option<f64>,
+    ticks_in_window: u64,
+    last_rate: Option<f64>,
+}
+
+impl TickRateMeter {
+    /// Record `ticks` executed at time `now` (seconds)
+    pub fn record(&mut self, now: f64, ticks: u64) {
+        let start = *self.window_start.get_or_insert(now);
+        self.ticks_in_window += ticks;
+        let elapsed = now - start;
+        if elapsed >= 1.0 {
+            self.last_rate = Some(self.ticks_in_window as f64 / elapsed);
+            self.window_start = Some(now);
+            self.ticks_in_window = 0;
+        }
+    }
+
+    /// Ticks per second over the last completed window
+    pub fn rate(&self) -> Option<f64> {
+        self.last_rate
+    }
+
+    /// Forget all measurements (e.g. while paused)
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Styling options for the population graph
+#[derive(Debug, Clone, Default)]
+pub struct GraphStyle {
+    /// Dashed horizontal reference lines (population value, color),
+    /// e.g. a prey carrying capacity
+    pub reference_lines: Vec<(f64, Color)>,
+}
+
+/// Largest population the graph's y axis has to show: the data maximum,
+/// raised to include any reference lines
+pub fn graph_max_count(collector: &StatisticsCollector, style: &GraphStyle) -> usize {
+    let data_max = collector.data().iter()
+        .map(|p| p.predator_count.max(p.prey_count))
+        .max()
+        .unwrap_or(1);
+    let reference_max = style.reference_lines.iter()
+        .map(|(value, _)| value.max(0.0).ceil() as usize)
+        .max()
+        .unwrap_or(0);
+    data_max.max(reference_max).max(1)
+}
+
+/// Screen y of a population value, shared by data lines and reference lines
+#[cfg(feature = "gui")]
+pub fn value_to_y(value: f64, max_count: usize, rect: egui::Rect) -> f32 {
+    let y_scale = rect.height() / (max_count as f32 + 10.0);
+    rect.bottom() - value as f32 * y_scale
+}
+
+/// Horizontal distance between consecutive data points on the graph
+#[cfg(feature = "gui")]
+fn x_scale(rect: egui::Rect, data_len: usize) -> f32 {
+    if data_len > 1 {
+        rect.width() / (data_len - 1) as f32
+    } else {
+        rect.width()
+    }
+}
+
+/// Screen x of the data point at `index`
+#[cfg(feature = "gui")]
+pub fn index_to_x(index: usize, rect: egui::Rect, data_len: usize) -> f32 {
+    rect.left() + index as f32 * x_scale(rect, data_len)
+}
+
+/// Index of the data point plotted nearest to `cursor_x` (clamped to the
+/// data), or None if there is no data
+#[cfg(feature = "gui")]
+pub fn x_to_index(cursor_x: f32, rect: egui::Rect, data_len: usize) -> Option<usize> {
+    if data_len == 0 {
+        return None;
+    }
+    let position = ((cursor_x - rect.left()) / x_scale(rect, data_len)).round();
+    Some((position.max(0.0) as usize).min(data_len - 1))
+}
+
+/// Draw a vertical guide at the data point nearest `cursor_x` and return that point
+#[cfg(feature = "gui")]
+pub fn render_hover_guide(
+    painter: &egui::Painter,
+    collector: &StatisticsCollector,
+    rect: egui::Rect,
+    cursor_x: f32,
+) -> Option<DataPoint> {
+    let data = collector.data();
+    let index = x_to_index(cursor_x, rect, data.len())?;
+    let x = index_to_x(index, rect, data.len());
+    painter.line_segment(
+        [egui::Pos2::new(x, rect.top()), egui::Pos2::new(x, rect.bottom())],
+        egui::Stroke::new(1.0, egui::Color32::from_rgb(120, 120, 120)),
+    );
+    Some(data[index])
+}
+
 /// Render a population graph
+#[cfg(feature = "gui")]
 pub fn render_population_graph(
     painter: &egui::Painter,
     collector: &StatisticsCollector,
+    style: &GraphStyle,
     rect: egui::Rect,
 ) {
     let data = collector.data();
@@ -135,23 +318,7 @@ pub fn render_population_graph(
     }
 
     // Find data range
-    let max_count = data.iter()
-        .map(|p| p.predator_count.max(p.prey_count))
-        .max()
-        .unwrap_or(1)
-        .max(1);
-    
-    let y_scale = if max_count > 0 {
-        rect.height() / (max_count as f32 + 10.0)
-    } else {
-        1.0
-    };
-
-    let x_scale = if data.len() > 1 {
-        rect.width() / (data.len() - 1) as f32
-    } else {
-        rect.width()
-    };
+    let max_count = graph_max_count(collector, style);
 
     // Draw background
     painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(250, 250, 250));
@@ -176,13 +343,34 @@ pub fn render_population_graph(
         );
     }
 
+    // Draw reference lines (dashed, labelled with their value)
+    for (value, color) in &style.reference_lines {
+        let y = value_to_y(*value, max_count, rect);
+        let color = color.to_egui_color32();
+        draw_dashed_line(
+            painter,
+            egui::Pos2::new(rect.left(), y),
+            egui::Pos2::new(rect.right(), y),
+            6.0,
+            4.0,
+            egui::Stroke::new(1.0, color),
+        );
+        painter.text(
+            egui::Pos2::new(rect.right() - 4.0, y - 2.0),
+            egui::Align2::RIGHT_BOTTOM,
+            format!("{:.0}", value),
+            egui::FontId::monospace(10.0),
+            color,
+        );
+    }
+
     // Draw predator line (red)
     if data.len() > 1 {
         for i in 0..data.len() - 1 {
-            let x1 = rect.left() + i as f32 * x_scale;
-            let y1 = rect.bottom() - data[i].predator_count as f32 * y_scale;
-            let x2 = rect.left() + (i + 1) as f32 * x_scale;
-            let y2 = rect.bottom() - data[i + 1].predator_count as f32 * y_scale;
+            let x1 = index_to_x(i, rect, data.len());
+            let y1 = value_to_y(data[i].predator_count as f64, max_count, rect);
+            let x2 = index_to_x(i + 1, rect, data.len());
+            let y2 = value_to_y(data[i + 1].predator_count as f64, max_count, rect);
             
             painter.line_segment(
                 [egui::Pos2::new(x1, y1), egui::Pos2::new(x2, y2)],
@@ -194,10 +382,10 @@ pub fn render_population_graph(
     // Draw prey line (green)
     if data.len() > 1 {
         for i in 0..data.len() - 1 {
-            let x1 = rect.left() + i as f32 * x_scale;
-            let y1 = rect.bottom() - data[i].prey_count as f32 * y_scale;
-            let x2 = rect.left() + (i + 1) as f32 * x_scale;
-            let y2 = rect.bottom() - data[i + 1].prey_count as f32 * y_scale;
+            let x1 = index_to_x(i, rect, data.len());
+            let y1 = value_to_y(data[i].prey_count as f64, max_count, rect);
+            let x2 = index_to_x(i + 1, rect, data.len());
+            let y2 = value_to_y(data[i + 1].prey_count as f64, max_count, rect);
             
             painter.line_segment(
                 [egui::Pos2::new(x1, y1), egui::Pos2::new(x2, y2)],
@@ -206,24 +394,102 @@ pub fn render_population_graph(
         }
     }
 
-    // Draw legend
-    let legend_y = rect.top() + 10.0;
-    painter.circle_filled(egui::Pos2::new(rect.left() + 10.0, legend_y), 4.0, egui::Color32::from_rgb(220, 20, 60));
-    painter.text(
-        egui::Pos2::new(rect.left() + 20.0, legend_y),
-        egui::Align2::LEFT_CENTER,
-        "Predators",
-        egui::FontId::monospace(10.0),
-        egui::Color32::BLACK,
-    );
-    
-    painter.circle_filled(egui::Pos2::new(rect.left() + 90.0, legend_y), 4.0, egui::Color32::from_rgb(34, 139, 34));
-    painter.text(
-        egui::Pos2::new(rect.left() + 100.0, legend_y),
-        egui::Align2::LEFT_CENTER,
-        "Prey",
-        egui::FontId::monospace(10.0),
-        egui::Color32::BLACK,
-    );
+    // Draw legend, one line per series with its latest value
+    let Some(latest) = collector.latest() else {
+        return;
+    };
+    let entries = [
+        ("Predators", latest.predator_count, egui::Color32::from_rgb(220, 20, 60)),
+        ("Prey", latest.prey_count, egui::Color32::from_rgb(34, 139, 34)),
+    ];
+    for (line, (name, value, color)) in entries.into_iter().enumerate() {
+        let legend_y = rect.top() + 10.0 + line as f32 * LEGEND_LINE_HEIGHT;
+        painter.circle_filled(egui::Pos2::new(rect.left() + 10.0, legend_y), 4.0, color);
+        painter.text(
+            egui::Pos2::new(rect.left() + 20.0, legend_y),
+            egui::Align2::LEFT_CENTER,
+            legend_label(name, value),
+            egui::FontId::monospace(10.0),
+            egui::Color32::BLACK,
+        );
+    }
+}
+
+/// Graph legend text for a series and its current value
+pub fn legend_label(name: &str, value: usize) -> String {
+    format!("{}: {}", name, value)
+}
+
+
+/// Band colors of the energy stack, bottom to top: predators, prey, food
+#[cfg(feature = "gui")]
+const ENERGY_STACK_COLORS: [egui::Color32; 3] = [
+    egui::Color32::from_rgb(220, 20, 60),
+    egui::Color32::from_rgb(34, 139, 34),
+    egui::Color32::from_rgb(210, 180, 60),
+];
+
+/// Screen y of each band boundary of the energy stack, bottom to top: the
+/// baseline, then the running sums of predator, prey and food energy, with
+/// `max_total` mapped to the top of `rect`
+#[cfg(feature = "gui")]
+pub fn energy_band_edges(energy: &EnergyTotals, max_total: f64, rect: egui::Rect) -> [f32; 4] {
+    let scale = if max_total > 0.0 { rect.height() as f64 / max_total } else { 0.0 };
+    let mut edges = [rect.bottom(); 4];
+    let mut sum = 0.0;
+    for (i, value) in [energy.predators, energy.prey, energy.food].into_iter().enumerate() {
+        sum += value.max(0.0);
+        edges[i + 1] = rect.bottom() - (sum * scale) as f32;
+    }
+    edges
 }
 
+/// Render total energy per category over time as stacked filled bands
+#[cfg(feature = "gui")]
+pub fn render_energy_stack(painter: &egui::Painter, collector: &StatisticsCollector, rect: egui::Rect) {
+    let data = collector.data();
+    painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(250, 250, 250));
+    if data.len() < 2 {
+        return;
+    }
+
+    let max_total = data.iter().map(|p| p.energy.total()).fold(0.0, f64::max);
+    for i in 0..data.len() - 1 {
+        let x1 = index_to_x(i, rect, data.len());
+        let x2 = index_to_x(i + 1, rect, data.len());
+        let left = energy_band_edges(&data[i].energy, max_total, rect);
+        let right = energy_band_edges(&data[i + 1].energy, max_total, rect);
+        for (band, color) in ENERGY_STACK_COLORS.into_iter().enumerate() {
+            painter.add(egui::Shape::convex_polygon(
+                vec![
+                    egui::Pos2::new(x1, left[band]),
+                    egui::Pos2::new(x2, right[band]),
+                    egui::Pos2::new(x2, right[band + 1]),
+                    egui::Pos2::new(x1, left[band + 1]),
+                ],
+                color,
+                egui::Stroke::NONE,
+            ));
+        }
+    }
+
+    let Some(latest) = collector.latest() else {
+        return;
+    };
+    let entries = [
+        ("Predator energy", latest.energy.predators),
+        ("Prey energy", latest.energy.prey),
+        ("Food energy", latest.energy.food),
+    ];
+    for (line, ((name, value), color)) in entries.into_iter().zip(ENERGY_STACK_COLORS).enumerate() {
+        let legend_y = rect.top() + 10.0 + line as f32 * LEGEND_LINE_HEIGHT;
+        painter.circle_filled(egui::Pos2::new(rect.left() + 10.0, legend_y), 4.0, color);
+        painter.text(
+            egui::Pos2::new(rect.left() + 20.0, legend_y),
+            egui::Align2::LEFT_CENTER,
+            format!("{}: {:.0}", name, value),
+            egui::FontId::monospace(10.0),
+            egui::Color32::BLACK,
+        );
+    }
+}
@@ -1,11 +1,104 @@
 // Canvas rendering of agents
 
 use crate::simulation::world::World;
-use crate::simulation::agent::{Agent, AgentType};
-use crate::utils::color::Colors;
-use crate::utils::math::Vector2;
+use crate::simulation::agent::{Agent, AgentId, AgentType};
+use crate::utils::color::{Color, Colors, Theme};
+use crate::utils::math::{torus_delta, Vector2, wrap_position};
+use crate::config::parameters::{BoundaryType, Parameters};
+use serde::{Deserialize, Serialize};
+
+/// Vertical bands used to draw a temperature gradient
+const TEMPERATURE_BANDS: usize = 32;
+
+/// Columns of the coarse grid predator territories are computed on
+/// (rows follow the world's aspect ratio)
+const TERRITORY_COLUMNS: usize = 64;
+
+/// Closest the camera zooms out
+pub const MIN_ZOOM: f32 = 0.25;
+/// Furthest the camera zooms in
+pub const MAX_ZOOM: f32 = 8.0;
+
+/// View transform: a world-space shift applied (with torus wrapping) before
+/// drawing, so a point of interest can sit at the canvas center, then a zoom
+/// about the canvas center
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Camera {
+    pub offset: Vector2,
+    /// Magnification, clamped to `MIN_ZOOM..=MAX_ZOOM`
+    pub zoom: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self { offset: Vector2::zero(), zoom: 1.0 }
+    }
+}
+
+impl Camera {
+    /// Camera that puts `target` at the center of the view
+    pub fn centered_on(target: Vector2, world_width: f64, world_height: f64) -> Self {
+        let mut camera = Self::default();
+        camera.center_on(target, world_width, world_height);
+        camera
+    }
+
+    /// Shift the view so `target` sits at the center, keeping the zoom
+    pub fn center_on(&mut self, target: Vector2, world_width: f64, world_height: f64) {
+        self.offset = Vector2::new(world_width / 2.0 - target.x, world_height / 2.0 - target.y);
+    }
+
+    /// Where a world position appears in the shifted view. Wrapping keeps
+    /// everything on screen, so crossing a seam never makes the view jump.
+    pub fn to_view(&self, pos: Vector2, world_width: f64, world_height: f64) -> Vector2 {
+        wrap_position(pos.add(&self.offset), world_width, world_height)
+    }
+
+    /// Inverse of `to_view`
+    pub fn to_world(&self, view_pos: Vector2, world_width: f64, world_height: f64) -> Vector2 {
+        wrap_position(view_pos.subtract(&self.offset), world_width, world_height)
+    }
+
+    /// Screen rectangle the whole (shifted) world is drawn into: the canvas
+    /// scaled by the zoom about its center
+    pub fn view_rect(&self, canvas_rect: egui::Rect) -> egui::Rect {
+        egui::Rect::from_center_size(canvas_rect.center(), canvas_rect.size() * self.zoom)
+    }
+
+    /// Screen position of a world position on the canvas
+    pub fn world_to_screen(&self, pos: Vector2, canvas_rect: egui::Rect, world_width: f64, world_height: f64) -> egui::Pos2 {
+        world_to_screen(self.to_view(pos, world_width, world_height), self.view_rect(canvas_rect), world_width, world_height)
+    }
+
+    /// World position under a screen position (inverse of `world_to_screen`)
+    pub fn screen_to_world(&self, screen_pos: egui::Pos2, canvas_rect: egui::Rect, world_width: f64, world_height: f64) -> Vector2 {
+        let view_pos = screen_to_world(screen_pos, self.view_rect(canvas_rect), world_width, world_height);
+        self.to_world(view_pos, world_width, world_height)
+    }
+
+    /// Multiply the zoom by `factor` (within the clamp), keeping the world
+    /// point under `screen_pos` in place
+    pub fn zoom_at(&mut self, screen_pos: egui::Pos2, factor: f32, canvas_rect: egui::Rect, world_width: f64, world_height: f64) {
+        let before = self.screen_to_world(screen_pos, canvas_rect, world_width, world_height);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        let after = self.screen_to_world(screen_pos, canvas_rect, world_width, world_height);
+        self.offset = self.offset.add(&torus_delta(&before, &after, world_width, world_height));
+    }
+
+    /// Move the view along with a drag of `delta` screen pixels
+    pub fn pan(&mut self, delta: egui::Vec2, canvas_rect: egui::Rect, world_width: f64, world_height: f64) {
+        let view_rect = self.view_rect(canvas_rect);
+        self.offset = Vector2::new(
+            self.offset.x + delta.x as f64 * world_width / view_rect.width() as f64,
+            self.offset.y + delta.y as f64 * world_height / view_rect.height() as f64,
+        );
+    }
+}
 
 /// Visualization settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct VisualizationSettings {
     pub show_velocity_vectors: bool,
     pub show_perception_radius: bool,
@@ -13,6 +106,20 @@ pub struct VisualizationSettings {
     pub agent_size: f32,
     pub grid_enabled: bool,
     pub grid_size: f32,
+    pub cull_offscreen: bool,
+    /// Draw the world border (dashed under wraparound, solid under walls)
+    pub show_border: bool,
+    /// Dash and gap lengths (pixels) of the wraparound border
+    pub border_dash: f32,
+    pub border_gap: f32,
+    /// Debug draw: lines from the inspected agent to every neighbor it perceives
+    pub debug_neighbors: bool,
+    /// Keep the selected agent centered on the canvas
+    pub follow_selected: bool,
+    /// Tint the canvas by nearest predator (coarse Voronoi territories)
+    pub show_territories: bool,
+    /// Current view transform (updated every frame while following)
+    pub camera: Camera,
 }
 
 impl Default for VisualizationSettings {
@@ -24,6 +131,14 @@ impl Default for VisualizationSettings {
             agent_size: 4.0,
             grid_enabled: false,
             grid_size: 20.0,
+            cull_offscreen: true,
+            show_border: true,
+            border_dash: 8.0,
+            border_gap: 6.0,
+            debug_neighbors: false,
+            follow_selected: false,
+            show_territories: false,
+            camera: Camera::default(),
         }
     }
 }
@@ -34,66 +149,342 @@ pub fn render_world(
     world: &World,
     settings: &VisualizationSettings,
     canvas_rect: egui::Rect,
+    theme: Theme,
 ) {
     let world_params = world.parameters();
     let world_width = world_params.world.width;
     let world_height = world_params.world.height;
     
-    let scale_x = canvas_rect.width() / world_width as f32;
-    let scale_y = canvas_rect.height() / world_height as f32;
+    // Wrapped copies of shifted rectangles must not spill past the canvas
+    let painter = &painter.with_clip_rect(canvas_rect);
+    let camera = settings.camera;
+    let view_rect = camera.view_rect(canvas_rect);
     
     // Helper to convert world coordinates to screen coordinates
     let to_screen = |pos: Vector2| -> egui::Pos2 {
-        egui::Pos2::new(
-            canvas_rect.left() + pos.x as f32 * scale_x,
-            canvas_rect.top() + pos.y as f32 * scale_y,
-        )
+        camera.world_to_screen(pos, canvas_rect, world_width, world_height)
+    };
+    // Screen rectangles of a world rectangle, split across the view seams
+    let to_screen_rects = |min: Vector2, width: f64, height: f64| -> Vec<egui::Rect> {
+        let view_min = camera.to_view(min, world_width, world_height);
+        let mut origins = vec![view_min];
+        if view_min.x + width > world_width {
+            origins.push(Vector2::new(view_min.x - world_width, view_min.y));
+        }
+        if view_min.y + height > world_height {
+            let below: Vec<Vector2> = origins.iter().map(|o| Vector2::new(o.x, o.y - world_height)).collect();
+            origins.extend(below);
+        }
+        origins
+            .into_iter()
+            .map(|o| {
+                egui::Rect::from_min_max(
+                    world_to_screen(o, view_rect, world_width, world_height),
+                    world_to_screen(Vector2::new(o.x + width, o.y + height), view_rect, world_width, world_height),
+                )
+            })
+            .collect()
     };
     
     // Draw grid if enabled
     if settings.grid_enabled {
-        draw_grid(painter, canvas_rect, settings.grid_size);
+        draw_grid(painter, canvas_rect, settings.grid_size, theme);
     }
     
+    // Draw the temperature gradient as vertical bands
+    if let Some(gradient) = world_params.world.temperature_gradient {
+        let band_width = world_width / TEMPERATURE_BANDS as f64;
+        for band in 0..TEMPERATURE_BANDS {
+            let x = band as f64 * band_width;
+            let temperature = gradient.temperature_at(Vector2::new(x + band_width * 0.5, 0.0), world_width);
+            let color = Colors::temperature(gradient.warmth(temperature)).to_egui_color32();
+            for rect in to_screen_rects(Vector2::new(x, 0.0), band_width, world_height) {
+                painter.rect_filled(rect, 0.0, color);
+            }
+        }
+    }
+    
+    // Draw predator scent, relative to the strongest cell
+    let scent = world.scent();
+    let strongest = scent.max();
+    if world_params.world.scent_trails.is_some() && strongest > 0.0 {
+        let size = scent.cell_size();
+        for row in 0..scent.rows() {
+            for col in 0..scent.cols() {
+                let value = scent.value(col, row);
+                if value <= 0.0 {
+                    continue;
+                }
+                let color = Colors::scent(value / strongest).to_egui_color32();
+                let min = Vector2::new(col as f64 * size, row as f64 * size);
+                let width = size.min(world_width - min.x);
+                let height = size.min(world_height - min.y);
+                for rect in to_screen_rects(min, width, height) {
+                    painter.rect_filled(rect, 0.0, color);
+                }
+            }
+        }
+    }
+    
+    // Draw predator territories
+    if settings.show_territories {
+        let cols = TERRITORY_COLUMNS;
+        let rows = ((cols as f64 * world_height / world_width).round() as usize).max(1);
+        let cell_width = world_width / cols as f64;
+        let cell_height = world_height / rows as f64;
+        for (i, owner) in world.predator_territories(cols, rows).into_iter().enumerate() {
+            let Some(id) = owner else { continue };
+            let min = Vector2::new((i % cols) as f64 * cell_width, (i / cols) as f64 * cell_height);
+            let color = Colors::territory(id.index()).to_egui_color32();
+            for rect in to_screen_rects(min, cell_width, cell_height) {
+                painter.rect_filled(rect, 0.0, color);
+            }
+        }
+    }
+    
+    // Draw time zones
+    for zone in &world_params.world.time_zones {
+        for rect in to_screen_rects(Vector2::new(zone.x, zone.y), zone.width, zone.height) {
+            painter.rect_filled(rect, 0.0, Colors::time_zone(zone.dt_scale).to_egui_color32());
+        }
+    }
+    
+    // Draw obstacles
+    for obstacle in world.obstacles() {
+        for rect in to_screen_rects(Vector2::new(obstacle.x, obstacle.y), obstacle.width, obstacle.height) {
+            painter.rect_filled(rect, 0.0, Colors::obstacle().to_egui_color32());
+        }
+    }
+    
+    // Draw food
+    let food_color = Colors::food().to_egui_color32();
+    for food in world.food() {
+        painter.circle_filled(to_screen(*food), (settings.agent_size * 0.5).max(1.0), food_color);
+    }
+    
+    // Draw world border, styled by topology
+    if settings.show_border {
+        draw_border(painter, view_rect, world_params.world.boundary_type, settings, theme);
+    }
+    
+    let context = AgentRenderContext {
+        params: world_params,
+        settings,
+        to_screen: &to_screen,
+        canvas_rect,
+        view_rect,
+        color_override: None,
+    };
+    
     // Draw predators
     for predator in world.predators() {
-        render_agent(
-            painter,
-            predator,
-            AgentType::Predator,
-            world_params,
-            &to_screen,
-            settings,
-        );
+        render_agent(painter, predator, AgentType::Predator, &context);
     }
     
     // Draw prey
     for prey in world.prey() {
-        render_agent(
-            painter,
-            prey,
-            AgentType::Prey,
-            world_params,
-            &to_screen,
-            settings,
-        );
+        let context = AgentRenderContext { color_override: prey.is_infected().then(Colors::infected), ..context };
+        render_agent(painter, prey, AgentType::Prey, &context);
     }
 }
 
+/// Convert a screen position on the canvas back to world coordinates
+/// (inverse of the mapping used by `render_world`)
+pub fn screen_to_world(
+    screen_pos: egui::Pos2,
+    canvas_rect: egui::Rect,
+    world_width: f64,
+    world_height: f64,
+) -> Vector2 {
+    let scale_x = canvas_rect.width() as f64 / world_width;
+    let scale_y = canvas_rect.height() as f64 / world_height;
+    Vector2::new(
+        (screen_pos.x - canvas_rect.left()) as f64 / scale_x,
+        (screen_pos.y - canvas_rect.top()) as f64 / scale_y,
+    )
+}
+
+/// Convert a world position to a screen position on the canvas
+/// (the mapping used by `render_world`)
+pub fn world_to_screen(
+    pos: Vector2,
+    canvas_rect: egui::Rect,
+    world_width: f64,
+    world_height: f64,
+) -> egui::Pos2 {
+    egui::Pos2::new(
+        canvas_rect.left() + (pos.x / world_width) as f32 * canvas_rect.width(),
+        canvas_rect.top() + (pos.y / world_height) as f32 * canvas_rect.height(),
+    )
+}
+
+/// The agent nearest to a screen position, within `radius_px` screen pixels
+pub fn agent_under_cursor(
+    world: &World,
+    screen_pos: egui::Pos2,
+    canvas_rect: egui::Rect,
+    camera: &Camera,
+    radius_px: f64,
+) -> Option<AgentId> {
+    let world_params = &world.parameters().world;
+    let world_pos = camera.screen_to_world(screen_pos, canvas_rect, world_params.width, world_params.height);
+    let radius = radius_px * world_params.width / camera.view_rect(canvas_rect).width() as f64;
+    world.agent_at(world_pos, radius)
+}
+
+/// Draw a faint ring around a hovered agent
+pub fn render_hover_highlight(
+    painter: &egui::Painter,
+    world: &World,
+    id: AgentId,
+    settings: &VisualizationSettings,
+    canvas_rect: egui::Rect,
+    theme: Theme,
+) {
+    let Some(agent) = world.agent(id) else {
+        return;
+    };
+    let world_params = &world.parameters().world;
+    let screen_pos = settings.camera.world_to_screen(agent.position(), canvas_rect, world_params.width, world_params.height);
+    let color = Colors::text_for(theme).to_egui_color32().linear_multiply(0.4);
+    painter.circle_stroke(screen_pos, settings.agent_size * 2.5, egui::Stroke::new(1.5, color));
+}
+
+/// Draw a bold ring around the selected agent
+pub fn render_selection_highlight(
+    painter: &egui::Painter,
+    world: &World,
+    id: AgentId,
+    settings: &VisualizationSettings,
+    canvas_rect: egui::Rect,
+    theme: Theme,
+) {
+    let Some(agent) = world.agent(id) else {
+        return;
+    };
+    let world_params = &world.parameters().world;
+    let screen_pos = settings.camera.world_to_screen(agent.position(), canvas_rect, world_params.width, world_params.height);
+    let color = Colors::text_for(theme).to_egui_color32();
+    painter.circle_stroke(screen_pos, settings.agent_size * 3.0, egui::Stroke::new(2.5, color));
+}
+
+/// Debug draw of an agent's actual perception: a line to every neighbor the
+/// world reports for it, plus its true perception circle for comparison
+pub fn render_neighbor_lines(
+    painter: &egui::Painter,
+    world: &World,
+    id: AgentId,
+    camera: &Camera,
+    canvas_rect: egui::Rect,
+    theme: Theme,
+) {
+    let Some(agent) = world.agent(id) else {
+        return;
+    };
+    let params = world.parameters();
+    let (width, height) = (params.world.width, params.world.height);
+    let origin = camera.world_to_screen(agent.position(), canvas_rect, width, height);
+    let color = Colors::text_for(theme).to_egui_color32().linear_multiply(0.6);
+    let stroke = egui::Stroke::new(1.0, color);
+
+    for (_, position) in world.neighbors_of(id) {
+        painter.line_segment([origin, camera.world_to_screen(position, canvas_rect, width, height)], stroke);
+    }
+
+    let radius = match agent.agent_type() {
+        AgentType::Predator => world.predators().iter().find(|p| p.id() == id).map_or(0.0, |p| p.perception_radius()),
+        AgentType::Prey => params.prey.detection_radius,
+    };
+    let screen_radius = radius as f32 * camera.view_rect(canvas_rect).width() / width as f32;
+    painter.circle_stroke(origin, screen_radius, stroke);
+}
+
+/// Check whether a circle at `screen_pos` with `radius` overlaps `rect`
+pub fn is_visible(screen_pos: egui::Pos2, radius: f32, rect: egui::Rect) -> bool {
+    rect.expand(radius).contains(screen_pos)
+}
+
+/// Screen shifts at which a circle in a wrapping world shows up: always zero,
+/// plus one world width (height) the other way for each edge of `view_rect`
+/// it pokes over, so an agent on the seam is drawn whole as a ghost copy
+pub fn wrap_ghost_offsets(screen_pos: egui::Pos2, radius: f32, view_rect: egui::Rect) -> Vec<egui::Vec2> {
+    let shifts = |pos: f32, min: f32, max: f32| {
+        let size = max - min;
+        let mut shifts = vec![0.0];
+        if pos - radius < min {
+            shifts.push(size);
+        }
+        if pos + radius > max {
+            shifts.push(-size);
+        }
+        shifts
+    };
+    let xs = shifts(screen_pos.x, view_rect.left(), view_rect.right());
+    let ys = shifts(screen_pos.y, view_rect.top(), view_rect.bottom());
+    xs.iter().flat_map(|&x| ys.iter().map(move |&y| egui::Vec2::new(x, y))).collect()
+}
+
+/// Everything `render_agent` draws against besides the agent itself
+#[derive(Clone, Copy)]
+struct AgentRenderContext<'a> {
+    params: &'a Parameters,
+    settings: &'a VisualizationSettings,
+    /// World-to-screen transform of the current camera
+    to_screen: &'a dyn Fn(Vector2) -> egui::Pos2,
+    canvas_rect: egui::Rect,
+    /// Screen rectangle of one whole world (wrapped copies sit one of these over)
+    view_rect: egui::Rect,
+    /// Color drawn instead of the species or energy color
+    color_override: Option<Color>,
+}
+
 /// Render a single agent
-fn render_agent(
+fn render_agent(painter: &egui::Painter, agent: &dyn Agent, agent_type: AgentType, context: &AgentRenderContext) {
+    let AgentRenderContext { params, settings, canvas_rect, view_rect, .. } = *context;
+    let screen_pos = (context.to_screen)(agent.position());
+    
+    // How far the drawing reaches (perception circles may extend past the body)
+    let reach = if settings.show_perception_radius {
+        let radius = match agent_type {
+            AgentType::Predator => params.predator.perception_radius,
+            AgentType::Prey => params.prey.detection_radius,
+        } as f32;
+        radius.max(settings.agent_size)
+    } else {
+        settings.agent_size
+    };
+    
+    // In a wrapping world, an agent over a seam is drawn again on the other side
+    let offsets = match params.world.boundary_type {
+        BoundaryType::Wraparound => wrap_ghost_offsets(screen_pos, reach, view_rect),
+        BoundaryType::Walls => vec![egui::Vec2::ZERO],
+    };
+    for offset in offsets {
+        // Skip copies that can't be seen
+        if settings.cull_offscreen && !is_visible(screen_pos + offset, reach, canvas_rect) {
+            continue;
+        }
+        render_agent_at(painter, agent, agent_type, context, offset);
+    }
+}
+
+/// Draw one copy of an agent, shifted on screen by `offset`
+fn render_agent_at(
     painter: &egui::Painter,
     agent: &dyn Agent,
     agent_type: AgentType,
-    params: &crate::config::parameters::Parameters,
-    to_screen: &dyn Fn(Vector2) -> egui::Pos2,
-    settings: &VisualizationSettings,
+    context: &AgentRenderContext,
+    offset: egui::Vec2,
 ) {
+    let AgentRenderContext { params, settings, color_override, .. } = *context;
+    let to_screen = |pos: Vector2| (context.to_screen)(pos) + offset;
     let pos = agent.position();
     let screen_pos = to_screen(pos);
     
     // Determine color based on energy if enabled
-    let color = if settings.show_energy_colors {
+    let color = if let Some(color) = color_override {
+        color
+    } else if settings.show_energy_colors {
         let max_energy = match agent_type {
             AgentType::Predator => params.predator.initial_energy,
             AgentType::Prey => params.prey.initial_energy,
@@ -158,8 +549,8 @@ fn render_agent(
 }
 
 /// Draw grid overlay
-fn draw_grid(painter: &egui::Painter, rect: egui::Rect, grid_size: f32) {
-    let color = Colors::grid().to_egui_color32();
+fn draw_grid(painter: &egui::Painter, rect: egui::Rect, grid_size: f32, theme: Theme) {
+    let color = Colors::grid_for(theme).to_egui_color32();
     let stroke = egui::Stroke::new(0.5, color);
     
     // Vertical lines
@@ -183,25 +574,114 @@ fn draw_grid(painter: &egui::Painter, rect: egui::Rect, grid_size: f32) {
     }
 }
 
+/// Draw the world border: dashed if the world wraps, solid if it has walls
+fn draw_border(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    boundary_type: BoundaryType,
+    settings: &VisualizationSettings,
+    theme: Theme,
+) {
+    let stroke = egui::Stroke::new(1.5, Colors::border_for(theme).to_egui_color32());
+    
+    match boundary_type {
+        BoundaryType::Walls => {
+            painter.rect_stroke(rect, 0.0, stroke);
+        }
+        BoundaryType::Wraparound => {
+            let corners = [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()];
+            for i in 0..corners.len() {
+                let a = corners[i];
+                let b = corners[(i + 1) % corners.len()];
+                draw_dashed_line(painter, a, b, settings.border_dash, settings.border_gap, stroke);
+            }
+        }
+    }
+}
+
+/// Split the line from `a` to `b` into dashes of length `dash` separated by `gap`.
+/// The last dash is cut short at `b`. A non-positive dash yields the whole line.
+pub fn dashed_segments(a: egui::Pos2, b: egui::Pos2, dash: f32, gap: f32) -> Vec<[egui::Pos2; 2]> {
+    let length = a.distance(b);
+    if dash <= 0.0 || length <= 0.0 {
+        return vec![[a, b]];
+    }
+    
+    let direction = (b - a) / length;
+    let mut segments = Vec::new();
+    let mut start = 0.0;
+    while start < length {
+        let end = (start + dash).min(length);
+        segments.push([a + direction * start, a + direction * end]);
+        start = end + gap.max(0.0);
+    }
+    segments
+}
+
+/// Draw a dashed line (egui has no native dashed stroke)
+pub fn draw_dashed_line(
+    painter: &egui::Painter,
+    a: egui::Pos2,
+    b: egui::Pos2,
+    dash: f32,
+    gap: f32,
+    stroke: egui::Stroke,
+) {
+    for segment in dashed_segments(a, b, dash, gap) {
+        painter.line_segment(segment, stroke);
+    }
+}
+
+/// Lines of text shown in the statistics overlay, in separately drawn groups
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatisticsLines {
+    /// Population counts
+    pub counts: Vec<String>,
+    /// Average energy per species, under a heading
+    pub energy: Vec<String>,
+}
+
+impl StatisticsLines {
+    /// The groups in display order
+    pub fn groups(&self) -> [&[String]; 2] {
+        [&self.counts, &self.energy]
+    }
+}
+
+/// Lines of text shown in the statistics overlay
+pub fn statistics_lines(world: &World) -> StatisticsLines {
+    StatisticsLines {
+        counts: vec![
+            format!("Predators: {}", world.predator_count()),
+            format!("Prey: {}", world.prey_count()),
+            format!("Total Agents: {}", world.total_agents()),
+        ],
+        energy: vec![
+            "Energy Levels:".to_string(),
+            format!("  Predators: {:.1}", world.average_predator_energy()),
+            format!("  Prey: {:.1}", world.average_prey_energy()),
+        ],
+    }
+}
+
 /// Render statistics overlay
 pub fn render_statistics(
     ui: &mut egui::Ui,
     world: &World,
 ) {
+    let lines = statistics_lines(world);
     egui::Window::new("Statistics")
         .collapsible(true)
         .resizable(true)
         .default_size([200.0, 150.0])
         .show(ui.ctx(), |ui| {
-            ui.label(format!("Predators: {}", world.predator_count()));
-            ui.label(format!("Prey: {}", world.prey_count()));
-            ui.label(format!("Total Agents: {}", world.total_agents()));
-            
-            ui.separator();
-            
-            // Average energy (would need to calculate from agents)
-            ui.label("Energy Levels:");
-            ui.label(format!("  Predators: {:.1}", 100.0)); // Placeholder
-            ui.label(format!("  Prey: {:.1}", 80.0)); // Placeholder
+            for (i, group) in lines.groups().iter().enumerate() {
+                if i > 0 {
+                    ui.separator();
+                }
+                for line in group.iter() {
+                    ui.label(line);
+                }
+            }
         });
 }
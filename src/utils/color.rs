@@ -21,10 +21,54 @@ impl Color {
     }
 
     /// Convert to egui color32 format
+    #[cfg(feature = "gui")]
     pub fn to_egui_color32(&self) -> egui::Color32 {
         egui::Color32::from_rgba_unmultiplied(self.r, self.g, self.b, self.a)
     }
 
+    /// Perceived brightness from 0.0 (black) to 1.0 (white), Rec. 709 weights
+    pub fn luminance(&self) -> f64 {
+        (0.2126 * self.r as f64 + 0.7152 * self.g as f64 + 0.0722 * self.b as f64) / 255.0
+    }
+
+    /// Create a color from hue (degrees), saturation and value (0.0 to 1.0)
+    pub fn from_hsv(hue: f64, saturation: f64, value: f64) -> Self {
+        let hue = hue.rem_euclid(360.0);
+        let chroma = value * saturation;
+        let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+        let m = value - chroma;
+        let (r, g, b) = match (hue / 60.0) as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let to_u8 = |c: f64| ((c + m) * 255.0).round() as u8;
+        Self::rgb(to_u8(r), to_u8(g), to_u8(b))
+    }
+
+    /// Hue in degrees (0.0 to 360.0); 0.0 for grays
+    pub fn hue(&self) -> f64 {
+        let (r, g, b) = (self.r as f64 / 255.0, self.g as f64 / 255.0, self.b as f64 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        if delta == 0.0 {
+            return 0.0;
+        }
+
+        let hue = if max == r {
+            60.0 * ((g - b) / delta)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        hue.rem_euclid(360.0)
+    }
+
     /// Interpolate between two colors based on a factor (0.0 to 1.0)
     pub fn lerp(&self, other: &Self, factor: f64) -> Self {
         let factor = factor.max(0.0).min(1.0);
@@ -37,6 +81,15 @@ impl Color {
     }
 }
 
+/// Canvas color theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Light,
+    /// Dark canvas for projectors in dark rooms
+    Dark,
+}
+
 /// Predefined colors for the simulation
 pub struct Colors;
 
@@ -51,6 +104,34 @@ impl Colors {
         Color::rgb(34, 139, 34) // Forest green
     }
 
+    /// Color for species `index` of `total`: hues spread evenly around the
+    /// color wheel, starting from the prey green, so any number of species
+    /// stay distinguishable
+    pub fn species_color(index: usize, total: usize) -> Color {
+        const BASE_HUE: f64 = 120.0;
+        let step = 360.0 / total.max(1) as f64;
+        Color::from_hsv(BASE_HUE + step * index as f64, 0.75, 0.7)
+    }
+
+    /// Translucent territory tint for a predator: hues a golden angle apart
+    /// per id, so neighboring ids get clearly different colors
+    pub fn territory(id: u64) -> Color {
+        const GOLDEN_ANGLE: f64 = 137.508;
+        let mut color = Color::from_hsv(id as f64 * GOLDEN_ANGLE, 0.6, 0.9);
+        color.a = 50;
+        color
+    }
+
+    /// Food item color (amber)
+    pub fn food() -> Color {
+        Color::rgb(218, 165, 32) // Goldenrod
+    }
+
+    /// Infected prey color (purple)
+    pub fn infected() -> Color {
+        Color::rgb(148, 0, 211) // Dark violet
+    }
+
     /// Background color
     pub fn background() -> Color {
         Color::rgb(240, 240, 240) // Light gray
@@ -78,8 +159,72 @@ impl Colors {
         Color::rgb(200, 200, 200)
     }
 
+    /// Obstacle fill color
+    pub fn obstacle() -> Color {
+        Color::rgb(90, 90, 90)
+    }
+
+    /// Translucent tint for a time zone: blue for slow motion, orange for fast forward
+    pub fn time_zone(dt_scale: f64) -> Color {
+        if dt_scale < 1.0 {
+            Color::rgba(80, 140, 255, 40)
+        } else {
+            Color::rgba(255, 160, 60, 40)
+        }
+    }
+
+    /// Translucent tint for a temperature: blue in the cold, red in the warm
+    /// (`warmth` from 0 to 1)
+    pub fn temperature(warmth: f64) -> Color {
+        let cold = Color::rgba(80, 140, 255, 45);
+        let warm = Color::rgba(255, 90, 60, 45);
+        cold.lerp(&warm, warmth)
+    }
+
+    /// Faint red predator scent, from transparent (none) to `strength` 1 (strongest)
+    pub fn scent(strength: f64) -> Color {
+        Color::rgba(200, 30, 30, (strength.clamp(0.0, 1.0) * 90.0) as u8)
+    }
+
+    /// World border color
+    pub fn border() -> Color {
+        Color::rgb(120, 120, 120)
+    }
+
     /// Text color
     pub fn text() -> Color {
         Color::rgb(30, 30, 30)
     }
+
+    /// Canvas background color for a theme
+    pub fn background_for(theme: Theme) -> Color {
+        match theme {
+            Theme::Light => Self::background(),
+            Theme::Dark => Color::rgb(28, 28, 32),
+        }
+    }
+
+    /// Grid line color for a theme
+    pub fn grid_for(theme: Theme) -> Color {
+        match theme {
+            Theme::Light => Self::grid(),
+            Theme::Dark => Color::rgb(70, 70, 78),
+        }
+    }
+
+    /// World border color for a theme
+    pub fn border_for(theme: Theme) -> Color {
+        match theme {
+            Theme::Light => Self::border(),
+            Theme::Dark => Color::rgb(150, 150, 160),
+        }
+    }
+
+    /// Text color for a theme
+    pub fn text_for(theme: Theme) -> Color {
+        match theme {
+            Theme::Light => Self::text(),
+            Theme::Dark => Color::rgb(225, 225, 230),
+        }
+    }
 }
@@ -0,0 +1,164 @@
+// Run metadata and file export helpers
+
+use std::fmt::Write;
+use serde::{Deserialize, Serialize};
+use crate::config::parameters::Parameters;
+use crate::simulation::agent::AgentId;
+use crate::simulation::runner::{classify_outcome_with, OutcomeThresholds};
+use crate::ui::statistics::Statistics;
+
+/// One agent's state at one tick, as written to JSON Lines exports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentRecord {
+    pub tick: u64,
+    pub id: AgentId,
+    /// "predator" or "prey"
+    #[serde(rename = "type")]
+    pub agent_type: String,
+    pub x: f64,
+    pub y: f64,
+    pub vx: f64,
+    pub vy: f64,
+    pub energy: f64,
+    pub age: u32,
+}
+
+/// Everything needed to describe (and reproduce) an exported run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunMetadata {
+    /// Number of simulation ticks run when the export was made
+    pub total_ticks: u64,
+    /// RNG seed, if the run was seeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Full parameter set of the run
+    pub parameters: Parameters,
+}
+
+impl RunMetadata {
+    /// Create metadata for a run
+    pub fn new(parameters: Parameters, seed: Option<u64>, total_ticks: u64) -> Self {
+        Self {
+            total_ticks,
+            seed,
+            parameters,
+        }
+    }
+
+    /// Render the metadata as TOML with every line prefixed by `# `,
+    /// suitable for the top of a CSV file
+    pub fn to_comment_header(&self) -> String {
+        let toml_string = toml::to_string_pretty(self).unwrap_or_default();
+        let mut header = String::new();
+        for line in toml_string.lines() {
+            if line.is_empty() {
+                header.push_str("#\n");
+            } else {
+                header.push_str("# ");
+                header.push_str(line);
+                header.push('\n');
+            }
+        }
+        header
+    }
+
+    /// Parse metadata back from the leading `#` comment lines of a CSV file
+    pub fn from_comment_header(csv: &str) -> Result<Self, String> {
+        let mut toml_string = String::new();
+        for line in csv.lines().take_while(|line| line.starts_with('#')) {
+            let content = line.trim_start_matches('#');
+            toml_string.push_str(content.strip_prefix(' ').unwrap_or(content));
+            toml_string.push('\n');
+        }
+
+        if toml_string.is_empty() {
+            return Err("No metadata header found".to_string());
+        }
+
+        toml::from_str(&toml_string).map_err(|e| e.to_string())
+    }
+}
+
+/// Write a CSV body to a file, preceded by the run metadata as comment lines
+pub fn write_csv_with_metadata(
+    path: &str,
+    metadata: &RunMetadata,
+    csv: &str,
+) -> std::io::Result<()> {
+    let mut content = metadata.to_comment_header();
+    content.push_str(csv);
+    std::fs::write(path, content)
+}
+
+/// Format a 2D grid as a CSV matrix (one line per row, no header)
+pub fn grid_to_csv(grid: &[Vec<usize>]) -> String {
+    let mut csv = String::new();
+    for row in grid {
+        let cells: Vec<String> = row.iter().map(|count| count.to_string()).collect();
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Format directed edges as a plain edge list: one "source target" pair per
+/// line, after a `#` header line
+pub fn edge_list(edges: &[(AgentId, AgentId)]) -> String {
+    let mut text = String::from("# source target\n");
+    for (source, target) in edges {
+        let _ = writeln!(text, "{} {}", source, target);
+    }
+    text
+}
+
+/// End-of-run state summarized in a report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunSummary {
+    pub ticks: u64,
+    pub final_predators: usize,
+    pub final_prey: usize,
+}
+
+/// Human-readable Markdown report of a run: populations, extinction status,
+/// configuration hash and the full parameter table
+pub fn run_report_markdown(params: &Parameters, stats: &Statistics, summary: &RunSummary) -> String {
+    // Writing to a String cannot fail
+    let mut report = String::new();
+    let _ = writeln!(report, "# Simulation Report\n");
+    let _ = writeln!(report, "- Configuration hash: `{}`", params.config_hash());
+    let _ = writeln!(report, "- Ticks run: {}", summary.ticks);
+    let extinct: Vec<&str> = [("predators", summary.final_predators), ("prey", summary.final_prey)]
+        .iter()
+        .filter(|(_, count)| *count == 0)
+        .map(|(name, _)| *name)
+        .collect();
+    let status = if extinct.is_empty() {
+        "none".to_string()
+    } else {
+        extinct.join(", ")
+    };
+    let _ = writeln!(report, "- Extinct: {}", status);
+    let outcome = classify_outcome_with(stats, summary, &OutcomeThresholds::for_parameters(params));
+    let _ = writeln!(report, "- Outcome: {}\n", outcome);
+
+    let _ = writeln!(report, "## Populations\n");
+    let _ = writeln!(report, "| | Predators | Prey |");
+    let _ = writeln!(report, "|---|---|---|");
+    let _ = writeln!(report, "| Final | {} | {} |", summary.final_predators, summary.final_prey);
+    let _ = writeln!(report, "| Peak | {} | {} |", stats.max_predators, stats.max_prey);
+    let _ = writeln!(report, "| Average | {:.1} | {:.1} |", stats.average_predators, stats.average_prey);
+    let _ = writeln!(report, "| Average energy | {:.1} | {:.1} |\n", stats.average_predator_energy, stats.average_prey_energy);
+
+    let _ = writeln!(report, "## Parameters\n");
+    let _ = writeln!(report, "| Section | Parameter | Value |");
+    let _ = writeln!(report, "|---|---|---|");
+    if let Ok(toml::Value::Table(sections)) = toml::Value::try_from(params) {
+        for (section, values) in &sections {
+            let Some(values) = values.as_table() else { continue };
+            for (name, value) in values {
+                let _ = writeln!(report, "| {} | {} | `{}` |", section, name, value);
+            }
+        }
+    }
+    report
+}
@@ -1,7 +1,9 @@
 // Vector math, distance calculations
 
+use serde::{Deserialize, Serialize};
+
 /// 2D vector for positions and velocities
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Vector2 {
     pub x: f64,
     pub y: f64,
@@ -49,6 +51,11 @@ impl Vector2 {
         }
     }
 
+    /// Whether both components are within `eps` of the other vector's
+    pub fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        (self.x - other.x).abs() <= eps && (self.y - other.y).abs() <= eps
+    }
+
     /// Limit the magnitude to a maximum value
     pub fn limit(&self, max: f64) -> Self {
         let mag = self.magnitude();
@@ -74,6 +81,11 @@ impl Vector2 {
             y: self.y - other.y,
         }
     }
+
+    /// Dot product
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
 }
 
 impl std::ops::Add for Vector2 {
@@ -141,6 +153,56 @@ pub fn distance_torus_squared(pos1: &Vector2, pos2: &Vector2, width: f64, height
     dx_wrapped * dx_wrapped + dy_wrapped * dy_wrapped
 }
 
+/// Shortest displacement from `from` to `to` on a torus
+pub fn torus_delta(from: &Vector2, to: &Vector2, width: f64, height: f64) -> Vector2 {
+    let wrap = |d: f64, size: f64| {
+        if d > size / 2.0 {
+            d - size
+        } else if d < -size / 2.0 {
+            d + size
+        } else {
+            d
+        }
+    };
+    Vector2::new(wrap(to.x - from.x, width), wrap(to.y - from.y, height))
+}
+
+/// Average positions on a torus using the circular mean per axis.
+/// A naive arithmetic mean is wrong across the seam: x=1 and x=width-1
+/// average to width/2, whereas the true midpoint is at the seam (x≈0).
+/// Falls back to the arithmetic mean on an axis where the points are spread
+/// evenly enough that no circular mean exists. Returns zero for no positions.
+pub fn torus_mean(positions: &[Vector2], width: f64, height: f64) -> Vector2 {
+    if positions.is_empty() {
+        return Vector2::zero();
+    }
+
+    let n = positions.len() as f64;
+    Vector2 {
+        x: circular_mean(positions.iter().map(|p| p.x), width, n),
+        y: circular_mean(positions.iter().map(|p| p.y), height, n),
+    }
+}
+
+/// Circular mean of values on the periodic interval [0, period)
+fn circular_mean(values: impl Iterator<Item = f64> + Clone, period: f64, n: f64) -> f64 {
+    let to_angle = std::f64::consts::PI * 2.0 / period;
+    let (sin_sum, cos_sum) = values
+        .clone()
+        .fold((0.0, 0.0), |(s, c), v| (s + (v * to_angle).sin(), c + (v * to_angle).cos()));
+
+    if sin_sum.abs() < 1e-9 && cos_sum.abs() < 1e-9 {
+        return values.sum::<f64>() / n;
+    }
+
+    let mean = sin_sum.atan2(cos_sum) / to_angle;
+    if mean < 0.0 {
+        mean + period
+    } else {
+        mean
+    }
+}
+
 /// Calculate the angle of a vector in radians
 pub fn angle(vector: &Vector2) -> f64 {
     vector.y.atan2(vector.x)
@@ -154,6 +216,49 @@ pub fn from_angle(angle: f64, magnitude: f64) -> Vector2 {
     }
 }
 
+/// 64-bit FNV-1a hash: tiny, stable across runs, platforms and Rust versions
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Offset of up to `max_dist` in a direction derived from a hash of
+/// `(id, age)`: the same inputs always give the same offset
+pub fn deterministic_offset(id: u64, age: u32, max_dist: f64) -> Vector2 {
+    let mut bytes = [0u8; 12];
+    bytes[..8].copy_from_slice(&id.to_le_bytes());
+    bytes[8..].copy_from_slice(&age.to_le_bytes());
+    let hash = fnv1a_64(&bytes);
+
+    // High half picks the angle, low half the distance
+    let angle_fraction = (hash >> 32) as f64 / u32::MAX as f64;
+    let distance_fraction = (hash & 0xffff_ffff) as f64 / u32::MAX as f64;
+    from_angle(angle_fraction * std::f64::consts::PI * 2.0, distance_fraction * max_dist)
+}
+
+/// Sample from a normal distribution with mean 0 and the given standard deviation
+/// (Box-Muller transform)
+pub fn gaussian(rng: &mut impl rand::Rng, stddev: f64) -> f64 {
+    // 1 - u keeps the logarithm's argument in (0, 1]
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    stddev * (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+/// Exponentially ease `current` toward `target` over `elapsed` seconds with the
+/// given time constant (0 = jump straight to the target)
+pub fn ease_toward(current: f64, target: f64, time_constant: f64, elapsed: f64) -> f64 {
+    if time_constant <= 0.0 {
+        return target;
+    }
+    let blend = 1.0 - (-elapsed.max(0.0) / time_constant).exp();
+    current + (target - current) * blend
+}
+
 /// Wrap a position within world bounds (toroidal)
 pub fn wrap_position(pos: Vector2, width: f64, height: f64) -> Vector2 {
     let mut x = pos.x;
@@ -2,4 +2,7 @@
 
 pub mod math;
 pub mod color;
-
+pub mod export;
+pub mod rng;
+#[cfg(feature = "net")]
+pub mod net;
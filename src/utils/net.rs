@@ -0,0 +1,107 @@
+// Live state streaming over TCP (behind the `net` feature)
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use serde::{Deserialize, Serialize};
+use crate::simulation::agent::Agent;
+use crate::simulation::world::World;
+
+/// Largest frame `read_frame` accepts, guarding against garbage length prefixes
+pub const MAX_FRAME_BYTES: u32 = 64 * 1024 * 1024;
+
+/// Compact state of one tick: population counts plus agent positions as [x, y]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateFrame {
+    pub tick: u64,
+    pub predator_count: usize,
+    pub prey_count: usize,
+    pub predators: Vec<[f64; 2]>,
+    pub prey: Vec<[f64; 2]>,
+}
+
+impl StateFrame {
+    /// Capture the current state of `world`
+    pub fn from_world(world: &World) -> Self {
+        let position = |agent: &dyn Agent| {
+            let pos = agent.position();
+            [pos.x, pos.y]
+        };
+        Self {
+            tick: world.tick(),
+            predator_count: world.predator_count(),
+            prey_count: world.prey_count(),
+            predators: world.predators().iter().map(|p| position(p)).collect(),
+            prey: world.prey().iter().map(|p| position(p)).collect(),
+        }
+    }
+}
+
+/// Write `frame` as a 4-byte big-endian length followed by that many bytes of JSON
+pub fn write_frame(writer: &mut impl Write, frame: &StateFrame) -> io::Result<()> {
+    let json = serde_json::to_vec(frame)?;
+    let len = u32::try_from(json.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "frame too large"))?;
+    writer.write_all(&len.to_be_bytes())?;
+    writer.write_all(&json)
+}
+
+/// Read one frame written by `write_frame`
+pub fn read_frame(reader: &mut impl Read) -> io::Result<StateFrame> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len);
+    if len > MAX_FRAME_BYTES {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "frame length exceeds limit"));
+    }
+    let mut json = vec![0u8; len as usize];
+    reader.read_exact(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// Broadcasts a `StateFrame` per tick to every connected TCP client
+pub struct StateServer {
+    listener: TcpListener,
+    clients: Vec<TcpStream>,
+}
+
+impl StateServer {
+    /// Listen on `addr` (port 0 picks a free port; see `local_addr`)
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self { listener, clients: Vec::new() })
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Number of clients currently connected
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Admit clients waiting to connect, then send each the current frame of
+    /// `world`. Clients that fail to receive it are dropped.
+    pub fn broadcast(&mut self, world: &World) -> io::Result<()> {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(false)?;
+                    stream.set_nodelay(true)?;
+                    self.clients.push(stream);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        if self.clients.is_empty() {
+            return Ok(());
+        }
+        let mut bytes = Vec::new();
+        write_frame(&mut bytes, &StateFrame::from_world(world))?;
+        self.clients.retain_mut(|client| client.write_all(&bytes).is_ok());
+        Ok(())
+    }
+}
@@ -2,19 +2,19 @@
 
 use predator_prey_sim::simulation::agent::*;
 use predator_prey_sim::utils::math::Vector2;
-use predator_prey_sim::config::parameters::BoundaryType;
+use predator_prey_sim::config::parameters::{BoundaryType, IntegrationScheme};
 
 #[test]
 fn test_base_agent_creation() {
     let agent = BaseAgent::new(
-        1,
+        AgentId(1),
         AgentType::Predator,
         Vector2::new(10.0, 20.0),
         100.0,
         2.0,
     );
 
-    assert_eq!(agent.id, 1);
+    assert_eq!(agent.id, AgentId(1));
     assert_eq!(agent.agent_type, AgentType::Predator);
     assert_eq!(agent.position.x, 10.0);
     assert_eq!(agent.position.y, 20.0);
@@ -26,7 +26,7 @@ fn test_base_agent_creation() {
 #[test]
 fn test_base_agent_velocity_limit() {
     let mut agent = BaseAgent::new(
-        1,
+        AgentId(1),
         AgentType::Predator,
         Vector2::new(0.0, 0.0),
         100.0,
@@ -44,7 +44,7 @@ fn test_base_agent_velocity_limit() {
 #[test]
 fn test_base_agent_energy_consumption() {
     let mut agent = BaseAgent::new(
-        1,
+        AgentId(1),
         AgentType::Predator,
         Vector2::new(0.0, 0.0),
         100.0,
@@ -61,7 +61,7 @@ fn test_base_agent_energy_consumption() {
 #[test]
 fn test_base_agent_energy_addition() {
     let mut agent = BaseAgent::new(
-        1,
+        AgentId(1),
         AgentType::Predator,
         Vector2::new(0.0, 0.0),
         100.0,
@@ -75,7 +75,7 @@ fn test_base_agent_energy_addition() {
 #[test]
 fn test_base_agent_age_increment() {
     let mut agent = BaseAgent::new(
-        1,
+        AgentId(1),
         AgentType::Predator,
         Vector2::new(0.0, 0.0),
         100.0,
@@ -92,7 +92,7 @@ fn test_base_agent_age_increment() {
 #[test]
 fn test_base_agent_alive_check() {
     let mut agent = BaseAgent::new(
-        1,
+        AgentId(1),
         AgentType::Predator,
         Vector2::new(0.0, 0.0),
         100.0,
@@ -106,10 +106,29 @@ fn test_base_agent_alive_check() {
     assert!(!agent.check_alive()); // Energy is 0
 }
 
+#[test]
+fn test_base_agent_outlived() {
+    let mut agent = BaseAgent::new(
+        AgentId(1),
+        AgentType::Prey,
+        Vector2::new(0.0, 0.0),
+        100.0,
+        2.0,
+    );
+
+    for _ in 0..9 {
+        agent.increment_age();
+    }
+    assert!(!agent.outlived(Some(10)));
+    agent.increment_age();
+    assert!(agent.outlived(Some(10)));
+    assert!(!agent.outlived(None));
+}
+
 #[test]
 fn test_base_agent_position_update_wraparound() {
     let mut agent = BaseAgent::new(
-        1,
+        AgentId(1),
         AgentType::Predator,
         Vector2::new(50.0, 50.0),
         100.0,
@@ -124,7 +143,12 @@ fn test_base_agent_position_update_wraparound() {
         boundary_type: BoundaryType::Wraparound,
         nearby_predators: Vec::new(),
         nearby_prey: Vec::new(),
+        alarm_positions: Vec::new(),
+        scent_gradient: Vector2::zero(),
         dt: 60.0, // Large dt to test wrapping
+        speed_scale: 1.0,
+        integration: IntegrationScheme::default(),
+        deterministic_offspring: false,
     };
 
     agent.update_position(&world_state);
@@ -137,7 +161,7 @@ fn test_base_agent_position_update_wraparound() {
 #[test]
 fn test_base_agent_position_update_walls() {
     let mut agent = BaseAgent::new(
-        1,
+        AgentId(1),
         AgentType::Predator,
         Vector2::new(50.0, 50.0),
         100.0,
@@ -152,7 +176,12 @@ fn test_base_agent_position_update_walls() {
         boundary_type: BoundaryType::Walls,
         nearby_predators: Vec::new(),
         nearby_prey: Vec::new(),
+        alarm_positions: Vec::new(),
+        scent_gradient: Vector2::zero(),
         dt: 10.0, // Large dt
+        speed_scale: 1.0,
+        integration: IntegrationScheme::default(),
+        deterministic_offspring: false,
     };
 
     agent.update_position(&world_state);
@@ -161,3 +190,90 @@ fn test_base_agent_position_update_walls() {
     assert!(agent.position.x >= 0.0 && agent.position.x <= 100.0);
     assert!(agent.position.y >= 0.0 && agent.position.y <= 100.0);
 }
+
+/// Accelerate an agent from rest at a constant rate for `steps` ticks
+/// and return the distance travelled
+fn travel_under_constant_acceleration(integration: IntegrationScheme, steps: u32) -> f64 {
+    let mut agent = BaseAgent::new(
+        AgentId(1),
+        AgentType::Prey,
+        Vector2::new(0.0, 50.0),
+        100.0,
+        1000.0,
+    );
+
+    let world_state = WorldState {
+        width: 1_000_000.0,
+        height: 100.0,
+        boundary_type: BoundaryType::Walls,
+        nearby_predators: Vec::new(),
+        nearby_prey: Vec::new(),
+        alarm_positions: Vec::new(),
+        scent_gradient: Vector2::zero(),
+        dt: 0.1,
+        speed_scale: 1.0,
+        integration,
+        deterministic_offspring: false,
+    };
+
+    let acceleration = 2.0;
+    for _ in 0..steps {
+        let velocity = agent.velocity.add(&Vector2::new(acceleration * world_state.dt, 0.0));
+        agent.set_velocity(velocity);
+        agent.update_position(&world_state);
+    }
+
+    agent.position.x
+}
+
+#[test]
+fn test_integration_schemes_under_constant_acceleration() {
+    let steps = 10;
+    let dt: f64 = 0.1;
+    let acceleration = 2.0;
+    let n = steps as f64;
+
+    let euler = travel_under_constant_acceleration(IntegrationScheme::Euler, steps);
+    let semi_implicit = travel_under_constant_acceleration(IntegrationScheme::SemiImplicitEuler, steps);
+    let exact = 0.5 * acceleration * (n * dt).powi(2);
+
+    // Explicit Euler lags the exact solution, semi-implicit leads it
+    assert!((euler - acceleration * dt * dt * n * (n - 1.0) / 2.0).abs() < 1e-9);
+    assert!((semi_implicit - acceleration * dt * dt * n * (n + 1.0) / 2.0).abs() < 1e-9);
+    assert!(euler < exact);
+    assert!(semi_implicit > exact);
+}
+
+fn neighbor(id: AgentId, position: Vector2, distance: f64) -> NeighborInfo {
+    NeighborInfo { id, position, velocity: Vector2::zero(), energy: 10.0, distance }
+}
+
+#[test]
+fn test_compare_neighbors_tie_breaks_by_id() {
+    let mut neighbors = vec![
+        neighbor(AgentId(9), Vector2::new(1.0, 0.0), 5.0),
+        neighbor(AgentId(4), Vector2::new(0.0, 1.0), 5.0),
+        neighbor(AgentId(6), Vector2::new(2.0, 0.0), 2.0),
+    ];
+    neighbors.sort_by(compare_neighbors);
+
+    let ids: Vec<AgentId> = neighbors.iter().map(|n| n.id).collect();
+    assert_eq!(ids, vec![AgentId(6), AgentId(4), AgentId(9)]);
+}
+
+#[test]
+fn test_agent_id_encoding() {
+    let id = AgentId::structured(AgentType::Prey, 7, 1234);
+    assert_eq!(id.agent_type(), Some(AgentType::Prey));
+    assert_eq!(id.generation(), 7);
+    assert_eq!(id.index(), 1234);
+
+    let flat = AgentId::flat(1234);
+    assert_eq!(flat.agent_type(), None);
+    assert_eq!(flat.generation(), 0);
+    assert_eq!(flat.index(), 1234);
+
+    let deep = AgentId::structured(AgentType::Predator, u32::MAX, 1);
+    assert_eq!(deep.generation(), MAX_ID_GENERATION);
+    assert_eq!(deep.agent_type(), Some(AgentType::Predator));
+}
@@ -0,0 +1,80 @@
+// Allocation tests
+//
+// Counts heap allocations made on the test thread while the world ticks, to
+// check that the per-tick scratch buffers are reused.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::world::World;
+
+struct CountingAllocator;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(|c| c.get()) {
+            ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if COUNTING.with(|c| c.get()) {
+            ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|a| a.set(0));
+    COUNTING.with(|c| c.set(true));
+    f();
+    COUNTING.with(|c| c.set(false));
+    ALLOCATIONS.with(|a| a.get())
+}
+
+#[test]
+fn test_world_update_reuses_buffers() {
+    let mut params = Parameters::default();
+    params.world.width = 200.0;
+    params.world.height = 200.0;
+    params.simulation.enable_reproduction = false;
+    let mut world = World::new(params);
+
+    // Warm up: size the scratch buffers and initialise the thread RNG
+    let _ = rand::thread_rng();
+    for _ in 0..20 {
+        world.update();
+    }
+
+    let ticks = 10;
+    let allocations = count_allocations(|| {
+        for _ in 0..ticks {
+            world.update();
+        }
+    });
+
+    // Allocating fresh views would cost at least one allocation per agent with
+    // neighbors per tick; reused buffers only grow occasionally
+    assert!(
+        allocations < world.total_agents(),
+        "{} allocations over {} ticks with {} agents",
+        allocations,
+        ticks,
+        world.total_agents(),
+    );
+}
@@ -0,0 +1,89 @@
+// Energy brush tests
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::agent::{Agent, AgentId};
+use predator_prey_sim::simulation::predator::Predator;
+use predator_prey_sim::simulation::prey::Prey;
+use predator_prey_sim::simulation::scent::ScentField;
+use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::ui::brush::{BrushMode, EnergyBrush};
+use predator_prey_sim::utils::math::Vector2;
+
+/// Predators at x = 100, 120, 140, 200 and prey at x = 110, 400, all on y = 100
+fn row_of_agents() -> World {
+    let params = Parameters::default();
+    let predators = [100.0, 120.0, 140.0, 200.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| Predator::new(AgentId(i as u64 + 1), Vector2::new(x, 100.0), params.predator.clone()))
+        .collect();
+    let prey = [110.0, 400.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| Prey::new(AgentId(i as u64 + 10), Vector2::new(x, 100.0), params.prey.clone()))
+        .collect();
+    World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params,
+        predators,
+        prey,
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: false,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    })
+}
+
+fn energies(world: &World) -> Vec<(AgentId, f64)> {
+    let mut energies: Vec<(AgentId, f64)> = world
+        .predators()
+        .iter()
+        .map(|p| (p.id(), p.energy()))
+        .chain(world.prey().iter().map(|p| (p.id(), p.energy())))
+        .collect();
+    energies.sort_by_key(|(id, _)| *id);
+    energies
+}
+
+#[test]
+fn test_agents_within_radius_nearest_first() {
+    let world = row_of_agents();
+    let ids = world.agents_within(Vector2::new(118.0, 100.0), 25.0);
+    assert_eq!(ids, vec![AgentId(2), AgentId(10), AgentId(1), AgentId(3)]);
+    assert!(world.agents_within(Vector2::new(300.0, 300.0), 25.0).is_empty());
+}
+
+#[test]
+fn test_heal_brush_raises_energy_only_within_radius() {
+    let mut world = row_of_agents();
+    let before = energies(&world);
+    let brush = EnergyBrush { mode: BrushMode::HealBrush, radius: 25.0, strength: 5.0 };
+
+    let touched = brush.apply(&mut world, Vector2::new(118.0, 100.0));
+    brush.apply(&mut world, Vector2::new(118.0, 100.0));
+
+    assert_eq!(touched.len(), 4);
+    for ((id, old), (_, new)) in before.iter().zip(energies(&world)) {
+        let expected = if touched.contains(id) { old + 10.0 } else { *old };
+        assert_eq!(new, expected, "agent {}", id);
+    }
+}
+
+#[test]
+fn test_drain_brush_stops_at_zero_and_off_does_nothing() {
+    let mut world = row_of_agents();
+    let drain = EnergyBrush { mode: BrushMode::DrainBrush, radius: 5.0, strength: 1000.0 };
+    assert_eq!(drain.apply(&mut world, Vector2::new(400.0, 100.0)), vec![AgentId(11)]);
+    assert_eq!(world.agent(AgentId(11)).unwrap().energy(), 0.0);
+
+    let before = energies(&world);
+    let off = EnergyBrush { mode: BrushMode::Off, ..drain };
+    assert!(off.apply(&mut world, Vector2::new(100.0, 100.0)).is_empty());
+    assert_eq!(energies(&world), before);
+}
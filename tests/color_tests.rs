@@ -61,3 +61,39 @@ fn test_energy_color() {
     assert!(mid.r <= low.r.max(high.r));
 }
 
+
+#[test]
+fn test_dark_theme_background_and_grid_contrast() {
+    let light = Colors::background_for(Theme::Light);
+    let dark = Colors::background_for(Theme::Dark);
+    assert!(dark.luminance() < light.luminance());
+
+    // Grid lines and text stay distinguishable from the background in both themes
+    for theme in [Theme::Light, Theme::Dark] {
+        let background = Colors::background_for(theme).luminance();
+        assert!((Colors::grid_for(theme).luminance() - background).abs() > 0.1);
+        assert!((Colors::text_for(theme).luminance() - background).abs() > 0.5);
+        assert!((Colors::predator().luminance() - background).abs() > 0.1);
+        assert!((Colors::prey().luminance() - background).abs() > 0.1);
+    }
+}
+
+#[test]
+fn test_species_colors_spread_evenly() {
+    let colors: Vec<Color> = (0..3).map(|i| Colors::species_color(i, 3)).collect();
+
+    for i in 0..3 {
+        let next = (i + 1) % 3;
+        let gap = (colors[next].hue() - colors[i].hue()).rem_euclid(360.0);
+        assert!((gap - 120.0).abs() < 3.0, "hue gap {}", gap);
+        assert_ne!(colors[i], colors[next]);
+    }
+}
+
+#[test]
+fn test_hsv_roundtrip_hue() {
+    for hue in [0.0, 60.0, 135.0, 200.0, 300.0] {
+        let color = Color::from_hsv(hue, 0.8, 0.9);
+        assert!((color.hue() - hue).abs() < 2.0);
+    }
+}
@@ -0,0 +1,32 @@
+// Shared test helpers
+
+// Each test binary compiles this module and uses only some of the helpers
+#![allow(dead_code)]
+
+use predator_prey_sim::utils::math::Vector2;
+
+/// Assert two floats differ by at most `eps`
+#[track_caller]
+pub fn assert_f64_approx(actual: f64, expected: f64, eps: f64) {
+    assert!(
+        (actual - expected).abs() <= eps,
+        "expected {} within {} of {}",
+        actual,
+        eps,
+        expected,
+    );
+}
+
+/// Assert both components of two vectors differ by at most `eps`
+#[track_caller]
+pub fn assert_vec2_approx(actual: Vector2, expected: Vector2, eps: f64) {
+    assert!(
+        actual.approx_eq(&expected, eps),
+        "expected ({}, {}) within {} of ({}, {})",
+        actual.x,
+        actual.y,
+        eps,
+        expected.x,
+        expected.y,
+    );
+}
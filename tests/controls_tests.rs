@@ -0,0 +1,65 @@
+// Control panel tests (need the `gui` feature)
+#![cfg(feature = "gui")]
+
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::ui::controls::ControlPanel;
+
+#[test]
+fn test_reset_uses_initial_count_sliders() {
+    let mut panel = ControlPanel::default();
+    panel.params.predator.initial_count = 3;
+    panel.params.prey.initial_count = 7;
+
+    let world = World::new(panel.reset_parameters());
+
+    assert_eq!(world.predator_count(), 3);
+    assert_eq!(world.prey_count(), 7);
+}
+
+#[test]
+fn test_reset_parameters_match_panel_parameters() {
+    let mut panel = ControlPanel::default();
+    panel.params.prey.initial_count = 123;
+
+    let params = panel.reset_parameters();
+    assert_eq!(params, panel.params);
+}
+
+#[test]
+fn test_preset_round_trip_updates_population_sliders() {
+    let path = std::env::temp_dir().join(format!("pps_preset_{}.toml", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let mut saver = ControlPanel::default();
+    saver.params.predator.initial_count = 4;
+    saver.params.prey.initial_count = 44;
+    saver.save_preset(path);
+    assert!(matches!(saver.preset_status, Some(Ok(_))));
+
+    let mut loader = ControlPanel::default();
+    loader.load_preset(path);
+    std::fs::remove_file(path).ok();
+    assert!(matches!(loader.preset_status, Some(Ok(_))));
+    assert_eq!(loader.params, saver.params);
+    assert_eq!(loader.params.prey.initial_count, 44);
+}
+
+#[test]
+fn test_invalid_preset_keeps_parameters_and_reports_error() {
+    let path = std::env::temp_dir().join(format!("pps_invalid_preset_{}.toml", std::process::id()));
+    let mut invalid = predator_prey_sim::config::parameters::Parameters::default();
+    invalid.world.width = -5.0;
+    std::fs::write(&path, toml::to_string(&invalid).unwrap()).unwrap();
+
+    let mut panel = ControlPanel::default();
+    panel.params.prey.initial_count = 12;
+    let before = panel.params.clone();
+    panel.load_preset(path.to_str().unwrap());
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(panel.params, before);
+    match &panel.preset_status {
+        Some(Err(error)) => assert!(error.contains("World dimensions must be positive"), "{}", error),
+        other => panic!("expected a load error, got {:?}", other),
+    }
+}
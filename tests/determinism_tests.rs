@@ -0,0 +1,264 @@
+// Determinism tests
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::agent::{Agent, AgentId};
+use predator_prey_sim::simulation::scent::ScentField;
+use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+use predator_prey_sim::simulation::world::World;
+
+/// Run a seeded world straight through for `total` ticks, and again with a
+/// snapshot taken at `checkpoint`, serialized, restored into a fresh world and
+/// continued to `total`. Both must end in exactly the same state.
+fn verify_snapshot_determinism(params: Parameters, seed: u64, total: u64, checkpoint: u64) {
+    let mut params = params;
+    params.simulation.seed = Some(seed);
+
+    let mut straight = World::new(params.clone());
+    for _ in 0..total {
+        straight.update();
+    }
+
+    let mut first_leg = World::new(params);
+    for _ in 0..checkpoint {
+        first_leg.update();
+    }
+    let json = serde_json::to_string(&first_leg.snapshot()).unwrap();
+    let snapshot: WorldSnapshot = serde_json::from_str(&json).unwrap();
+    let mut resumed = World::from_snapshot(snapshot);
+    for _ in checkpoint..total {
+        resumed.update();
+    }
+
+    assert_eq!(resumed.tick(), straight.tick());
+    assert_eq!(
+        resumed.state_hash(),
+        straight.state_hash(),
+        "restoring the snapshot at tick {} changed the trajectory",
+        checkpoint,
+    );
+}
+
+#[test]
+fn test_snapshot_replay_matches_straight_run() {
+    let mut params = Parameters::default();
+    // Also cover hashed offspring offsets
+    params.simulation.deterministic_offspring = true;
+    params.world.enable_food = true;
+    params.world.food_spawn_rate = 0.5;
+    params.prey.infection_enabled = true;
+    params.simulation.sensor_noise = 2.0;
+
+    verify_snapshot_determinism(params, 11, 300, 120);
+}
+
+/// State hash after each of the first `ticks` ticks
+fn early_trajectory(world: &mut World, ticks: u64) -> Vec<u64> {
+    (0..ticks)
+        .map(|_| {
+            world.update();
+            world.state_hash()
+        })
+        .collect()
+}
+
+#[test]
+fn test_new_seeded_worlds_run_identically() {
+    let positions = |world: &World| {
+        let predators = world.predators().iter().map(|p| p.position());
+        predators.chain(world.prey().iter().map(|p| p.position())).collect::<Vec<_>>()
+    };
+
+    let mut a = World::new_seeded(Parameters::default(), 99);
+    let mut b = World::new_seeded(Parameters::default(), 99);
+    assert_eq!(a.parameters().simulation.seed, Some(99));
+    for _ in 0..500 {
+        a.update();
+        b.update();
+    }
+    assert_eq!(a.predator_count(), b.predator_count());
+    assert_eq!(a.prey_count(), b.prey_count());
+    assert_eq!(positions(&a), positions(&b));
+}
+
+#[test]
+fn test_reset_of_seeded_world_replays_original_run() {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(21);
+    params.simulation.deterministic_offspring = true;
+
+    let mut world = World::new(params);
+    let initial_predators = world.predators().iter().map(|p| p.position()).collect::<Vec<_>>();
+    let original = early_trajectory(&mut world, 60);
+
+    world.reset();
+    let replayed_predators = world.predators().iter().map(|p| p.position()).collect::<Vec<_>>();
+    assert_eq!(replayed_predators, initial_predators);
+    assert_eq!(early_trajectory(&mut world, 60), original);
+}
+
+#[test]
+fn test_advancing_seed_on_reset_gives_new_reproducible_run() {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(21);
+    params.simulation.deterministic_offspring = true;
+    params.simulation.advance_seed_on_reset = true;
+
+    let mut world = World::new(params.clone());
+    let original = early_trajectory(&mut world, 30);
+    world.reset();
+    assert_eq!(world.run_number(), 1);
+    assert_ne!(early_trajectory(&mut world, 30), original);
+
+    // Run 1 of seed 21 is exactly a fresh run of seed 22 (the hashes differ
+    // only because they cover the parameters, seed included)
+    params.simulation.seed = Some(22);
+    let mut fresh = World::new(params);
+    for _ in 0..30 {
+        fresh.update();
+    }
+    assert_eq!(positions(&fresh), positions(&world));
+}
+
+/// Positions of all agents, predators first
+fn positions(world: &World) -> Vec<(f64, f64)> {
+    let predators = world.predators().iter().map(|p| p.position());
+    let prey = world.prey().iter().map(|p| p.position());
+    predators.chain(prey).map(|p| (p.x, p.y)).collect()
+}
+
+/// A predator 3 units from its target prey, with a second prey close enough
+/// to the target that dilution halves the capture chance
+fn contested_capture(seed: u64) -> World {
+    use predator_prey_sim::simulation::predator::Predator;
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(seed);
+    params.simulation.enable_reproduction = false;
+    params.predator.dilution_factor = 1.0;
+    World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params: params.clone(),
+        predators: vec![Predator::new(AgentId(1), Vector2::new(100.0, 100.0), params.predator.clone())],
+        prey: vec![
+            Prey::new(AgentId(2), Vector2::new(103.0, 100.0), params.prey.clone()),
+            Prey::new(AgentId(3), Vector2::new(103.0, 140.0), params.prey.clone()),
+        ],
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: true,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    })
+}
+
+#[test]
+fn test_seeded_rng_makes_probabilistic_capture_repeatable() {
+    let captured = |seed: u64| {
+        let mut world = contested_capture(seed);
+        world.update();
+        world.prey_count() == 1
+    };
+
+    let outcomes: Vec<bool> = (0..32).map(captured).collect();
+    assert_eq!((0..32).map(captured).collect::<Vec<_>>(), outcomes);
+    // The capture really is a coin flip: some seeds catch, some don't
+    assert!(outcomes.contains(&true) && outcomes.contains(&false));
+}
+
+#[test]
+fn test_agent_update_with_seeded_rng_is_repeatable() {
+    use predator_prey_sim::config::parameters::PreyParameters;
+    use predator_prey_sim::simulation::agent::{NeighborInfo, WorldState};
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::utils::math::Vector2;
+    use rand::SeedableRng;
+
+    // A predator exactly on top of the prey leaves the flee direction to chance
+    let world = World::new(Parameters::default());
+    let mut state: WorldState = world.view_of(world.prey()[0].id()).unwrap();
+    let position = Vector2::new(50.0, 50.0);
+    state.nearby_predators = vec![NeighborInfo { id: AgentId(1), position, velocity: Vector2::zero(), energy: 100.0, distance: 0.0 }];
+
+    let flee_with_seed = |seed: u64| {
+        let mut prey = Prey::new(AgentId(2), position, PreyParameters::default());
+        prey.update_with_rng(&state, &mut rand::rngs::StdRng::seed_from_u64(seed));
+        prey.velocity()
+    };
+
+    assert_eq!(flee_with_seed(7), flee_with_seed(7));
+    assert_ne!(flee_with_seed(7), flee_with_seed(8));
+}
+
+#[test]
+fn test_shuffled_update_order_is_seeded_permutation() {
+    let seeded_world = |shuffle: bool| {
+        let mut params = Parameters::default();
+        params.simulation.seed = Some(11);
+        params.simulation.enable_reproduction = false;
+        params.simulation.shuffle_update_order = shuffle;
+        World::new(params)
+    };
+    let inserted = |world: &World| -> Vec<AgentId> {
+        world.predators().iter().map(|p| p.id()).chain(world.prey().iter().map(|p| p.id())).collect()
+    };
+
+    let mut plain = seeded_world(false);
+    let insertion_order = inserted(&plain);
+    plain.update();
+    assert_eq!(plain.update_order(), insertion_order.as_slice());
+
+    let mut first = seeded_world(true);
+    let mut second = seeded_world(true);
+    assert_eq!(inserted(&first), insertion_order);
+    first.update();
+    second.update();
+    let order = first.update_order().to_vec();
+    assert_eq!(order, second.update_order());
+    assert_ne!(order, insertion_order);
+
+    // Same agents, predators still ahead of prey
+    let predator_count = first.predator_count();
+    let mut predators = order[..predator_count].to_vec();
+    let mut prey = order[predator_count..].to_vec();
+    predators.sort();
+    prey.sort();
+    assert_eq!(predators, insertion_order[..predator_count]);
+    assert_eq!(prey, insertion_order[predator_count..]);
+}
+
+#[test]
+fn test_fork_predicts_without_disturbing_original() {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(21);
+    let mut world = World::new(params);
+    for _ in 0..20 {
+        world.update();
+    }
+    let original_hash = world.state_hash();
+
+    let mut fork = world.fork();
+    let mut twin = world.fork();
+    assert_eq!(fork.state_hash(), original_hash);
+    for _ in 0..100 {
+        fork.update();
+        twin.update();
+    }
+
+    // The original is untouched, and identical forks predict identically
+    assert_eq!(world.state_hash(), original_hash);
+    assert_eq!(world.tick(), 20);
+    assert_eq!(fork.state_hash(), twin.state_hash());
+    assert_ne!(fork.state_hash(), original_hash);
+
+    // The prediction is what the live world then actually does
+    for _ in 0..100 {
+        world.update();
+    }
+    assert_eq!(world.state_hash(), fork.state_hash());
+}
@@ -0,0 +1,132 @@
+// Export tests
+
+mod common;
+
+use common::assert_f64_approx;
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::ui::statistics::StatisticsCollector;
+use predator_prey_sim::simulation::agent::{Agent, AgentId};
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::utils::export::{edge_list, run_report_markdown, AgentRecord, RunMetadata, RunSummary};
+
+#[test]
+fn test_csv_header_roundtrips_parameters() {
+    let mut params = Parameters::default();
+    params.predator.max_speed = 3.25;
+    params.prey.initial_count = 42;
+    params.simulation.tick_rate = 30.0;
+    params.simulation.update_dt();
+
+    let mut collector = StatisticsCollector::new(100);
+    collector.record(10, 50, 100.0, 80.0, 1.0, 1.5);
+    collector.record(11, 48, 98.5, 81.0, 1.0, 1.5);
+
+    let metadata = RunMetadata::new(params.clone(), Some(7), 120);
+    let csv = format!("{}{}", metadata.to_comment_header(), collector.to_csv());
+
+    let parsed = RunMetadata::from_comment_header(&csv).unwrap();
+    assert_eq!(parsed.parameters, params);
+    assert_eq!(parsed.seed, Some(7));
+    assert_eq!(parsed.total_ticks, 120);
+}
+
+#[test]
+fn test_csv_body_follows_header() {
+    let mut collector = StatisticsCollector::new(100);
+    collector.record(10, 50, 100.0, 80.0, 1.0, 1.5);
+
+    let metadata = RunMetadata::new(Parameters::default(), None, 1);
+    let csv = format!("{}{}", metadata.to_comment_header(), collector.to_csv());
+
+    let body: Vec<&str> = csv.lines().filter(|line| !line.starts_with('#')).collect();
+    assert_eq!(body.len(), 2);
+    assert_eq!(body[0], "tick,predator_count,prey_count,predator_energy,prey_energy");
+    assert!(body[1].starts_with("0,10,50,"));
+}
+
+#[test]
+fn test_missing_header_is_error() {
+    assert!(RunMetadata::from_comment_header("tick,predator_count\n0,1\n").is_err());
+}
+
+#[test]
+fn test_dump_agents_jsonl_one_line_per_agent() {
+    let mut world = World::new(Parameters::default());
+    world.update();
+
+    let jsonl = world.dump_agents_jsonl();
+    let records: Vec<AgentRecord> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is a JSON object"))
+        .collect();
+    assert_eq!(records.len(), world.total_agents());
+
+    let predator = &world.predators()[0];
+    let record = records.iter().find(|r| r.id == predator.id()).unwrap();
+    assert_eq!(record.agent_type, "predator");
+    assert_eq!(record.tick, 1);
+    // JSON float parsing may differ from the original in the last bit
+    assert_f64_approx(record.x, predator.position().x, 1e-9);
+    assert_f64_approx(record.vy, predator.velocity().y, 1e-9);
+    assert_f64_approx(record.energy, predator.energy(), 1e-9);
+    assert_eq!(record.age, predator.age());
+    assert_eq!(records.iter().filter(|r| r.agent_type == "prey").count(), world.prey_count());
+}
+
+#[test]
+fn test_run_report_contains_final_counts() {
+    let params = Parameters::default();
+    let mut collector = StatisticsCollector::new(100);
+    collector.record(12, 80, 100.0, 80.0, 1.0, 1.5);
+    collector.record(7, 0, 90.0, 0.0, 1.0, 1.5);
+    let summary = RunSummary { ticks: 2, final_predators: 7, final_prey: 0 };
+
+    let report = run_report_markdown(&params, &collector.stats(), &summary);
+    assert!(!report.is_empty());
+    assert!(report.contains("| Final | 7 | 0 |"));
+    assert!(report.contains("| Peak | 12 | 80 |"));
+    assert!(report.contains("Extinct: prey"));
+    assert!(report.contains("Outcome: prey extinction"));
+    assert!(report.contains(&params.config_hash()));
+    assert!(report.contains("| predator | max_speed |"));
+}
+
+#[test]
+fn test_edge_list_one_pair_per_line() {
+    let text = edge_list(&[(AgentId(1), AgentId(2)), (AgentId(2), AgentId(1)), (AgentId(3), AgentId(1))]);
+    let lines: Vec<&str> = text.lines().filter(|line| !line.starts_with('#')).collect();
+    assert_eq!(lines, vec!["1 2", "2 1", "3 1"]);
+}
+
+#[test]
+fn test_metadata_seed_follows_advancing_reset() {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(40);
+    params.simulation.advance_seed_on_reset = true;
+    let mut world = World::new(params);
+    assert_eq!(world.current_seed(), Some(40));
+
+    world.reset();
+    let metadata = RunMetadata::new(world.parameters().clone(), world.current_seed(), world.tick());
+    let parsed = RunMetadata::from_comment_header(&metadata.to_comment_header()).unwrap();
+    assert_eq!(parsed.seed, Some(41));
+
+    // The recorded seed reproduces the run after the reset
+    let mut replay_params = parsed.parameters.clone();
+    replay_params.simulation.seed = parsed.seed;
+    let mut replay = World::new(replay_params);
+    for _ in 0..20 {
+        world.update();
+        replay.update();
+    }
+    // (state_hash covers the parameters, whose seed fields differ, so compare agents)
+    let agents = |world: &World| -> Vec<(AgentId, f64, f64, f64)> {
+        world
+            .predators()
+            .iter()
+            .map(|p| (p.id(), p.position().x, p.position().y, p.energy()))
+            .chain(world.prey().iter().map(|p| (p.id(), p.position().x, p.position().y, p.energy())))
+            .collect()
+    };
+    assert_eq!(agents(&replay), agents(&world));
+}
@@ -0,0 +1,53 @@
+// Frame dump tests (need the `gui` feature)
+#![cfg(feature = "gui")]
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::runner::run_headless_with;
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::ui::frames::{render_frame, FrameDumper};
+use predator_prey_sim::ui::visualization::VisualizationSettings;
+
+/// Read the width and height from a PNG file's IHDR chunk
+fn png_dimensions(bytes: &[u8]) -> (u32, u32) {
+    let width = u32::from_be_bytes(bytes[16..20].try_into().unwrap());
+    let height = u32::from_be_bytes(bytes[20..24].try_into().unwrap());
+    (width, height)
+}
+
+#[test]
+fn test_render_frame_buffer_size() {
+    let world = World::new(Parameters::default());
+    let settings = VisualizationSettings::default();
+
+    let rgba = render_frame(&world, &settings, 64, 48);
+    assert_eq!(rgba.len(), 64 * 48 * 4);
+}
+
+#[test]
+fn test_dump_five_frames() {
+    let dir = std::env::temp_dir().join(format!("pps_frames_test_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut params = Parameters::default();
+    params.predator.initial_count = 2;
+    params.prey.initial_count = 5;
+    let mut world = World::new(params);
+    let settings = VisualizationSettings::default();
+    let dumper = FrameDumper::new(&dir, 80, 60).unwrap();
+
+    let mut written = Vec::new();
+    run_headless_with(&mut world, 5, |world| {
+        written.push(dumper.dump(world, &settings).unwrap());
+    });
+
+    let files: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+    assert_eq!(files.len(), 5);
+    assert!(dir.join("frame_000001.png").exists());
+    assert!(dir.join("frame_000005.png").exists());
+    for path in &written {
+        let bytes = std::fs::read(path).unwrap();
+        assert_eq!(png_dimensions(&bytes), (80, 60));
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
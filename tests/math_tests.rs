@@ -1,5 +1,8 @@
 // Math utility tests
 
+mod common;
+
+use common::{assert_f64_approx, assert_vec2_approx};
 use predator_prey_sim::utils::math::*;
 
 #[test]
@@ -115,13 +118,8 @@ fn test_angle() {
 
 #[test]
 fn test_from_angle() {
-    let v = from_angle(0.0, 5.0);
-    assert!((v.x - 5.0).abs() < 1e-10);
-    assert!((v.y - 0.0).abs() < 1e-10);
-    
-    let v = from_angle(std::f64::consts::PI / 2.0, 5.0);
-    assert!((v.x - 0.0).abs() < 1e-10);
-    assert!((v.y - 5.0).abs() < 1e-10);
+    assert_vec2_approx(from_angle(0.0, 5.0), Vector2::new(5.0, 0.0), 1e-10);
+    assert_vec2_approx(from_angle(std::f64::consts::PI / 2.0, 5.0), Vector2::new(0.0, 5.0), 1e-10);
 }
 
 #[test]
@@ -150,3 +148,55 @@ fn test_clamp_position() {
     assert_eq!(clamped.y, 100.0);
 }
 
+#[test]
+fn test_deterministic_offset() {
+    let offset = deterministic_offset(7, 120, 20.0);
+    assert_eq!(offset, deterministic_offset(7, 120, 20.0));
+    assert!(offset.magnitude() <= 20.0);
+
+    // Different parents (or the same parent at another age) spawn elsewhere
+    assert_ne!(offset, deterministic_offset(8, 120, 20.0));
+    assert_ne!(offset, deterministic_offset(7, 121, 20.0));
+}
+
+#[test]
+fn test_ease_toward_approaches_target_monotonically() {
+    let target = 3.0;
+    let mut value = 1.0;
+    // Five seconds of 60 fps frames is many time constants
+    for _ in 0..300 {
+        let next = ease_toward(value, target, 0.3, 1.0 / 60.0);
+        assert!(next >= value && next <= target);
+        value = next;
+    }
+    assert!((value - target).abs() < 1e-3);
+
+    // No smoothing jumps straight to the target
+    assert_eq!(ease_toward(1.0, 3.0, 0.0, 1.0 / 60.0), 3.0);
+}
+
+#[test]
+fn test_vector_approx_eq() {
+    let v = Vector2::new(1.0, 2.0);
+    assert!(v.approx_eq(&Vector2::new(1.0 + 1e-12, 2.0 - 1e-12), 1e-9));
+    assert!(!v.approx_eq(&Vector2::new(1.0, 2.1), 1e-9));
+    assert!(!v.approx_eq(&Vector2::new(1.1, 2.0), 1e-9));
+}
+
+#[test]
+fn test_approx_helpers_accept_close_values() {
+    assert_f64_approx(0.1 + 0.2, 0.3, 1e-12);
+    assert_vec2_approx(Vector2::new(0.1 + 0.2, 1.0), Vector2::new(0.3, 1.0), 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "within")]
+fn test_assert_f64_approx_rejects_far_values() {
+    assert_f64_approx(1.0, 1.1, 1e-9);
+}
+
+#[test]
+#[should_panic(expected = "within")]
+fn test_assert_vec2_approx_rejects_far_values() {
+    assert_vec2_approx(Vector2::new(1.0, 1.0), Vector2::new(1.0, 1.5), 1e-9);
+}
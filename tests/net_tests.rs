@@ -0,0 +1,44 @@
+// State streaming tests (need the `net` feature)
+#![cfg(feature = "net")]
+
+use std::net::TcpStream;
+use std::time::Duration;
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::runner::run_headless_serving;
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::utils::net::{read_frame, write_frame, StateFrame, StateServer};
+
+#[test]
+fn test_frame_round_trips_through_length_prefix() {
+    let world = World::new(Parameters::default());
+    let frame = StateFrame::from_world(&world);
+    let mut bytes = Vec::new();
+    write_frame(&mut bytes, &frame).unwrap();
+
+    let len = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+    assert_eq!(len, bytes.len() - 4);
+    assert_eq!(read_frame(&mut bytes.as_slice()).unwrap(), frame);
+}
+
+#[test]
+fn test_client_receives_well_formed_frames() {
+    let mut server = StateServer::bind("127.0.0.1:0").unwrap();
+    let mut client = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+    client.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(3);
+    let mut world = World::new(params);
+    run_headless_serving(&mut world, 3, &mut server, Duration::ZERO).unwrap();
+    assert_eq!(server.client_count(), 1);
+
+    let frame = read_frame(&mut client).unwrap();
+    assert!(frame.tick >= 1 && frame.tick <= 3);
+    assert_eq!(frame.predators.len(), frame.predator_count);
+    assert_eq!(frame.prey.len(), frame.prey_count);
+    assert!(frame.predator_count + frame.prey_count > 0);
+
+    // Frames keep coming, one per tick
+    let next = read_frame(&mut client).unwrap();
+    assert_eq!(next.tick, frame.tick + 1);
+}
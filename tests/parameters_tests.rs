@@ -74,3 +74,94 @@ fn test_parameters_validate_failure_zero_energy() {
     assert!(params.validate().is_err());
 }
 
+#[test]
+fn test_parameters_validate_failure_zero_offspring_energy() {
+    let mut params = Parameters::default();
+    params.predator.offspring_energy = Some(0.0);
+    assert!(params.validate().is_err());
+}
+
+#[test]
+fn test_config_hash_is_stable_and_sensitive() {
+    let params = Parameters::default();
+    let same = Parameters::default();
+    assert_eq!(params.config_hash(), same.config_hash());
+    assert_eq!(params.config_hash().len(), 8);
+    assert!(params.config_hash().chars().all(|c| c.is_ascii_hexdigit()));
+
+    let mut changed = Parameters::default();
+    changed.prey.max_speed += 0.5;
+    assert_ne!(params.config_hash(), changed.config_hash());
+}
+
+#[test]
+fn test_load_missing_file_is_io_error() {
+    let path = std::env::temp_dir().join(format!("pps_missing_params_{}.toml", std::process::id()));
+    let result = Parameters::load_from_file(path.to_str().unwrap());
+    assert!(matches!(result, Err(ParametersError::Io(_))));
+}
+
+#[test]
+fn test_load_malformed_toml_is_parse_error() {
+    let path = std::env::temp_dir().join(format!("pps_malformed_params_{}.toml", std::process::id()));
+    std::fs::write(&path, "[world\nwidth = = 3").unwrap();
+    let result = Parameters::load_from_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(result, Err(ParametersError::Parse(_))));
+}
+
+#[test]
+fn test_load_out_of_range_config_is_validation_error() {
+    let path = std::env::temp_dir().join(format!("pps_invalid_params_{}.toml", std::process::id()));
+    let mut params = Parameters::default();
+    params.world.width = -100.0;
+    params.save_to_file(path.to_str().unwrap()).unwrap();
+    let result = Parameters::load_from_file(path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(result, Err(ParametersError::Validation(_))));
+}
+
+#[test]
+fn test_schema_includes_slider_range_of_predator_max_speed() {
+    use predator_prey_sim::config::ranges;
+
+    let schema: serde_json::Value = serde_json::from_str(&Parameters::schema_json()).unwrap();
+    let fields = schema.as_array().unwrap();
+    let field = |name: &str| fields.iter().find(|f| f["name"] == name).unwrap_or_else(|| panic!("{} missing", name));
+
+    let max_speed = field("predator.max_speed");
+    assert_eq!(max_speed["type"], "number");
+    assert_eq!(max_speed["default"], PredatorParameters::default().max_speed);
+    assert_eq!(max_speed["min"], *ranges::PREDATOR_MAX_SPEED.start());
+    assert_eq!(max_speed["max"], *ranges::PREDATOR_MAX_SPEED.end());
+
+    // Fields without a slider still appear, just without bounds
+    let boundary = field("world.boundary_type");
+    assert_eq!(boundary["type"], "string");
+    assert!(boundary.get("min").is_none());
+    assert_eq!(field("simulation.auto_balance.target_prey")["type"], "integer");
+}
+
+#[test]
+fn test_default_parameters_have_no_warnings() {
+    assert!(Parameters::default().warnings().is_empty());
+}
+
+#[test]
+fn test_speed_far_beyond_capture_distance_warns_of_tunneling() {
+    let mut params = Parameters::default();
+    params.simulation.tick_rate = 1.0;
+    params.simulation.update_dt();
+    params.predator.max_speed = 20.0;
+
+    let warnings = params.warnings();
+    assert_eq!(
+        warnings,
+        vec![ParameterWarning::PredatorTunneling { step: 20.0, capture_distance: params.predator.capture_distance }]
+    );
+    assert!(warnings[0].suggestion().contains("predator max speed"));
+
+    assert!(params.clamp_unstable());
+    assert!(params.warnings().is_empty());
+    assert_eq!(params.predator.max_speed, params.predator.capture_distance);
+}
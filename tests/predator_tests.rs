@@ -3,7 +3,7 @@
 use predator_prey_sim::simulation::predator::Predator;
 use predator_prey_sim::simulation::agent::*;
 use predator_prey_sim::utils::math::Vector2;
-use predator_prey_sim::config::parameters::{PredatorParameters, BoundaryType};
+use predator_prey_sim::config::parameters::{PredatorParameters, BoundaryType, IntegrationScheme, PursuitMode};
 
 fn create_test_world_state() -> WorldState {
     WorldState {
@@ -12,16 +12,25 @@ fn create_test_world_state() -> WorldState {
         boundary_type: BoundaryType::Wraparound,
         nearby_predators: Vec::new(),
         nearby_prey: Vec::new(),
+        alarm_positions: Vec::new(),
+        scent_gradient: Vector2::zero(),
         dt: 1.0 / 60.0,
+        speed_scale: 1.0,
+        integration: IntegrationScheme::default(),
+        deterministic_offspring: false,
     }
 }
 
+fn neighbor(id: AgentId, position: Vector2, distance: f64, energy: f64) -> NeighborInfo {
+    NeighborInfo { id, position, velocity: Vector2::zero(), energy, distance }
+}
+
 #[test]
 fn test_predator_creation() {
     let params = PredatorParameters::default();
-    let predator = Predator::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     
-    assert_eq!(predator.id(), 1);
+    assert_eq!(predator.id(), AgentId(1));
     assert_eq!(predator.agent_type(), AgentType::Predator);
     assert_eq!(predator.energy(), params.initial_energy);
 }
@@ -29,7 +38,7 @@ fn test_predator_creation() {
 #[test]
 fn test_predator_energy_consumption() {
     let params = PredatorParameters::default();
-    let mut predator = Predator::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     
     let initial_energy = predator.energy();
     let mut world_state = create_test_world_state();
@@ -46,11 +55,11 @@ fn test_predator_energy_consumption() {
 #[test]
 fn test_predator_chases_prey() {
     let params = PredatorParameters::default();
-    let mut predator = Predator::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     
     let mut world_state = create_test_world_state();
     // Add nearby prey
-    world_state.nearby_prey.push((2, Vector2::new(60.0, 50.0), 10.0));
+    world_state.nearby_prey.push(neighbor(AgentId(2), Vector2::new(60.0, 50.0), 10.0, 80.0));
     
     let initial_pos = predator.position();
     predator.update(&world_state);
@@ -63,11 +72,11 @@ fn test_predator_chases_prey() {
 #[test]
 fn test_predator_consumes_prey() {
     let params = PredatorParameters::default();
-    let mut predator = Predator::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     
     let mut world_state = create_test_world_state();
     // Add prey within capture distance
-    world_state.nearby_prey.push((2, Vector2::new(52.0, 50.0), 2.0)); // Very close
+    world_state.nearby_prey.push(neighbor(AgentId(2), Vector2::new(52.0, 50.0), 2.0, 80.0)); // Very close
     
     let initial_energy = predator.energy();
     let action = predator.update(&world_state);
@@ -75,7 +84,7 @@ fn test_predator_consumes_prey() {
     // Should consume prey and gain energy
     match action {
         AgentAction::Consumed { target_id } => {
-            assert_eq!(target_id, 2);
+            assert_eq!(target_id, AgentId(2));
             assert!(predator.energy() > initial_energy);
         }
         _ => panic!("Expected Consumed action"),
@@ -86,7 +95,7 @@ fn test_predator_consumes_prey() {
 fn test_predator_dies_when_no_energy() {
     let mut params = PredatorParameters::default();
     params.energy_per_tick = 1000.0; // High consumption
-    let mut predator = Predator::new(1, Vector2::new(50.0, 50.0), params);
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params);
     
     let mut world_state = create_test_world_state();
     
@@ -98,3 +107,242 @@ fn test_predator_dies_when_no_energy() {
     assert!(!predator.is_alive());
 }
 
+
+#[test]
+fn test_predator_learning_widens_perception_after_captures() {
+    let mut params = PredatorParameters::default();
+    params.learning_rate = 0.05;
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey.push(neighbor(AgentId(2), Vector2::new(52.0, 50.0), 2.0, 80.0));
+
+    for _ in 0..5 {
+        let action = predator.update(&world_state);
+        assert!(matches!(action, AgentAction::Consumed { .. }));
+    }
+
+    assert!(predator.perception_radius() > params.perception_radius);
+    assert!(predator.perception_radius() <= params.max_perception);
+}
+
+#[test]
+fn test_predator_learning_narrows_perception_without_captures() {
+    let mut params = PredatorParameters::default();
+    params.learning_rate = 0.05;
+    params.energy_per_tick = 0.0;
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+
+    let world_state = create_test_world_state();
+    for _ in 0..1000 {
+        predator.update(&world_state);
+    }
+
+    assert!(predator.perception_radius() < params.perception_radius);
+    assert!(predator.perception_radius() >= params.min_perception);
+}
+
+#[test]
+fn test_forfeited_capture_undoes_learning() {
+    let mut params = PredatorParameters::default();
+    params.learning_rate = 0.05;
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey.push(neighbor(AgentId(2), Vector2::new(52.0, 50.0), 2.0, 80.0));
+    let energy_before = predator.energy();
+    assert!(matches!(predator.update(&world_state), AgentAction::Consumed { .. }));
+    assert!(predator.perception_radius() > params.perception_radius);
+
+    predator.forfeit_capture();
+    assert_eq!(predator.perception_radius(), params.perception_radius);
+    assert!(predator.energy() <= energy_before);
+}
+
+#[test]
+fn test_predator_without_learning_keeps_perception() {
+    let params = PredatorParameters::default();
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey.push(neighbor(AgentId(2), Vector2::new(52.0, 50.0), 2.0, 80.0));
+    predator.update(&world_state);
+
+    assert_eq!(predator.perception_radius(), params.perception_radius);
+}
+
+#[test]
+fn test_predator_tie_break_prefers_lower_id() {
+    let params = PredatorParameters::default();
+
+    // Same two equidistant prey, inserted in both orders
+    for order in [[7, 3], [3, 7]] {
+        let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+        let mut world_state = create_test_world_state();
+        for id in order {
+            let x = if id == 3 { 52.0 } else { 48.0 };
+            world_state.nearby_prey.push(neighbor(AgentId(id), Vector2::new(x, 50.0), 2.0, 80.0));
+        }
+
+        match predator.update(&world_state) {
+            AgentAction::Consumed { target_id } => assert_eq!(target_id, AgentId(3)),
+            _ => panic!("Expected Consumed action"),
+        }
+    }
+}
+
+#[test]
+fn test_predator_steers_toward_alarm_when_enabled() {
+    let mut params = PredatorParameters::default();
+    params.alarm_attracts_predators = true;
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+
+    let mut world_state = create_test_world_state();
+    world_state.alarm_positions.push(Vector2::new(80.0, 50.0));
+    predator.update(&world_state);
+
+    assert!(predator.velocity().x > 0.0);
+    assert!(predator.velocity().y.abs() < 1e-9);
+}
+
+#[test]
+fn test_predator_ignores_alarm_when_disabled() {
+    let params = PredatorParameters::default();
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+
+    let mut world_state = create_test_world_state();
+    world_state.alarm_positions.push(Vector2::new(80.0, 50.0));
+    predator.update(&world_state);
+
+    assert_eq!(predator.velocity().magnitude(), 0.0);
+}
+
+#[test]
+fn test_predator_prefers_high_energy_prey_when_enabled() {
+    let mut params = PredatorParameters::default();
+    params.prefer_high_energy_prey = true;
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+
+    // Two equidistant prey: a lean one to the east, a rich one to the west
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey.push(neighbor(AgentId(2), Vector2::new(70.0, 50.0), 20.0, 20.0));
+    world_state.nearby_prey.push(neighbor(AgentId(3), Vector2::new(30.0, 50.0), 20.0, 150.0));
+    predator.update(&world_state);
+
+    assert!(predator.velocity().x < 0.0);
+}
+
+#[test]
+fn test_predator_without_preference_ignores_prey_energy() {
+    let params = PredatorParameters::default();
+    let mut predator = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+
+    // Equidistant prey: the lower id wins regardless of energy
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey.push(neighbor(AgentId(2), Vector2::new(70.0, 50.0), 20.0, 20.0));
+    world_state.nearby_prey.push(neighbor(AgentId(3), Vector2::new(30.0, 50.0), 20.0, 150.0));
+    predator.update(&world_state);
+
+    assert!(predator.velocity().x > 0.0);
+}
+
+#[test]
+fn test_intercept_leads_laterally_moving_prey() {
+    // Prey due east, running north (+y)
+    let prey = NeighborInfo {
+        id: AgentId(2),
+        position: Vector2::new(70.0, 50.0),
+        velocity: Vector2::new(0.0, 2.0),
+        energy: 80.0,
+        distance: 20.0,
+    };
+
+    let mut direct = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), PredatorParameters::default());
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey.push(prey);
+    direct.update(&world_state);
+    assert!(direct.velocity().y.abs() < 1e-9);
+
+    let mut params = PredatorParameters::default();
+    params.pursuit = PursuitMode::Intercept;
+    let mut intercepting = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+    intercepting.update(&world_state);
+
+    // Aims ahead of the prey rather than straight at it
+    assert!(intercepting.velocity().x > 0.0);
+    assert!(intercepting.velocity().y > 0.0);
+}
+
+#[test]
+fn test_rear_attack_only_needs_approach_from_behind() {
+    let mut params = PredatorParameters::default();
+    params.rear_attack_only = true;
+
+    // Prey at (52, 50) running east (+x)
+    let prey = NeighborInfo {
+        id: AgentId(2),
+        position: Vector2::new(52.0, 50.0),
+        velocity: Vector2::new(2.0, 0.0),
+        energy: 80.0,
+        distance: 2.0,
+    };
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey.push(prey);
+
+    // Predator just ahead of the prey: it is seen coming
+    let mut in_front = Predator::new(AgentId(1), Vector2::new(54.0, 50.0), params.clone());
+    assert!(!matches!(in_front.update(&world_state), AgentAction::Consumed { .. }));
+
+    // Predator behind the prey: capture succeeds
+    let mut behind = Predator::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+    assert!(matches!(behind.update(&world_state), AgentAction::Consumed { target_id: AgentId(2) }));
+}
+
+#[test]
+fn test_idle_predator_wanders() {
+    use rand::SeedableRng;
+    use predator_prey_sim::utils::math::distance;
+
+    let mut world_state = create_test_world_state();
+    world_state.width = 10_000.0;
+    world_state.height = 10_000.0;
+    world_state.dt = 1.0;
+    let start = Vector2::new(5_000.0, 5_000.0);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+
+    // Net distance from the start after 200 ticks with no prey in sight
+    let mut displacement = |wander_strength: f64| {
+        let mut params = PredatorParameters::default();
+        params.wander_strength = wander_strength;
+        let mut predator = Predator::new(AgentId(1), start, params);
+        predator.set_energy(1_000.0);
+        for _ in 0..200 {
+            predator.update_with_rng(&world_state, &mut rng);
+        }
+        distance(&start, &predator.position())
+    };
+
+    assert_eq!(displacement(0.0), 0.0);
+    // Half of max speed (2.0) is up to 200 units of travel; a heading that
+    // jumped at random every tick would cancel out far more of it
+    let wandered = displacement(0.5);
+    assert!(wandered > 60.0, "predator only got {} units from its start", wandered);
+}
+
+#[test]
+fn test_predator_seeks_across_wraparound_edge() {
+    let mut predator = Predator::new(AgentId(1), Vector2::new(95.0, 50.0), PredatorParameters::default());
+
+    // The prey at x=5 is 10 units ahead across the seam, not 90 behind
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey.push(neighbor(AgentId(2), Vector2::new(5.0, 50.0), 10.0, 100.0));
+    predator.update(&world_state);
+    assert!(predator.velocity().x > 0.0, "predator went the long way: {:?}", predator.velocity());
+
+    // Under walls there is no seam: the prey really is to the left
+    let mut predator = Predator::new(AgentId(1), Vector2::new(95.0, 50.0), PredatorParameters::default());
+    world_state.boundary_type = BoundaryType::Walls;
+    world_state.nearby_prey[0].distance = 90.0;
+    predator.update(&world_state);
+    assert!(predator.velocity().x < 0.0);
+}
@@ -1,9 +1,12 @@
 // Prey tests
 
+mod common;
+
+use common::assert_f64_approx;
 use predator_prey_sim::simulation::prey::Prey;
 use predator_prey_sim::simulation::agent::*;
 use predator_prey_sim::utils::math::Vector2;
-use predator_prey_sim::config::parameters::{PreyParameters, BoundaryType};
+use predator_prey_sim::config::parameters::{PreyParameters, BoundaryType, IntegrationScheme, ReproductionStrategy};
 
 fn create_test_world_state() -> WorldState {
     WorldState {
@@ -12,16 +15,25 @@ fn create_test_world_state() -> WorldState {
         boundary_type: BoundaryType::Wraparound,
         nearby_predators: Vec::new(),
         nearby_prey: Vec::new(),
+        alarm_positions: Vec::new(),
+        scent_gradient: Vector2::zero(),
         dt: 1.0 / 60.0,
+        speed_scale: 1.0,
+        integration: IntegrationScheme::default(),
+        deterministic_offspring: false,
     }
 }
 
+fn neighbor(id: AgentId, position: Vector2, distance: f64, energy: f64) -> NeighborInfo {
+    NeighborInfo { id, position, velocity: Vector2::zero(), energy, distance }
+}
+
 #[test]
 fn test_prey_creation() {
     let params = PreyParameters::default();
-    let prey = Prey::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     
-    assert_eq!(prey.id(), 1);
+    assert_eq!(prey.id(), AgentId(1));
     assert_eq!(prey.agent_type(), AgentType::Prey);
     assert_eq!(prey.energy(), params.initial_energy);
 }
@@ -29,7 +41,7 @@ fn test_prey_creation() {
 #[test]
 fn test_prey_energy_regeneration() {
     let params = PreyParameters::default();
-    let mut prey = Prey::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     
     // Reduce energy first
     let mut world_state = create_test_world_state();
@@ -51,11 +63,11 @@ fn test_prey_energy_regeneration() {
 #[test]
 fn test_prey_flees_from_predator() {
     let params = PreyParameters::default();
-    let mut prey = Prey::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     
     let mut world_state = create_test_world_state();
     // Add nearby predator within flee distance
-    world_state.nearby_predators.push((2, Vector2::new(60.0, 50.0), 30.0));
+    world_state.nearby_predators.push(neighbor(AgentId(2), Vector2::new(60.0, 50.0), 30.0, 100.0));
     
     let initial_pos = prey.position();
     prey.update(&world_state);
@@ -68,10 +80,10 @@ fn test_prey_flees_from_predator() {
 #[test]
 fn test_prey_extra_energy_loss_when_fleeing() {
     let params = PreyParameters::default();
-    let mut prey = Prey::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     
     let mut world_state = create_test_world_state();
-    world_state.nearby_predators.push((2, Vector2::new(55.0, 50.0), 25.0)); // Close predator
+    world_state.nearby_predators.push(neighbor(AgentId(2), Vector2::new(55.0, 50.0), 25.0, 100.0)); // Close predator
     
     let initial_energy = prey.energy();
     
@@ -92,7 +104,7 @@ fn test_prey_reproduction() {
     params.reproduction_threshold = 50.0; // Lower threshold for testing
     params.initial_energy = 100.0; // High initial energy
     
-    let mut prey = Prey::new(1, Vector2::new(50.0, 50.0), params.clone());
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
     // Set energy high enough to reproduce
     // We can't directly set energy, so we'll need to test through the update mechanism
     // For a proper test, we'd need to expose a way to set energy, or test after many updates
@@ -107,3 +119,394 @@ fn test_prey_reproduction() {
     assert!(prey.is_alive());
 }
 
+
+#[test]
+fn test_prey_density_reduces_regeneration() {
+    let mut params = PreyParameters::default();
+    params.density_cap = 10.0;
+
+    let mut isolated = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+    let mut crowded = Prey::new(AgentId(2), Vector2::new(50.0, 50.0), params.clone());
+
+    let isolated_state = create_test_world_state();
+    let mut crowded_state = create_test_world_state();
+    for id in 10..18 {
+        crowded_state.nearby_prey.push(neighbor(AgentId(id), Vector2::new(55.0, 50.0), 5.0, 100.0));
+    }
+
+    for _ in 0..100 {
+        isolated.update(&isolated_state);
+        crowded.update(&crowded_state);
+    }
+
+    let isolated_gain = isolated.energy() - params.initial_energy;
+    let crowded_gain = crowded.energy() - params.initial_energy;
+    assert!(isolated_gain > 0.0);
+    assert!(crowded_gain < isolated_gain);
+}
+
+#[test]
+fn test_prey_remembers_threat_after_losing_sight() {
+    let mut params = PreyParameters::default();
+    params.memory_ticks = 5;
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+
+    let mut world_state = create_test_world_state();
+    world_state.nearby_predators.push(neighbor(AgentId(2), Vector2::new(60.0, 50.0), 10.0, 100.0));
+    prey.update(&world_state);
+    assert!(prey.velocity().x < 0.0);
+
+    // Predator disappears from view - prey keeps fleeing for a few ticks
+    world_state.nearby_predators.clear();
+    for _ in 0..3 {
+        prey.update(&world_state);
+        assert!(prey.velocity().magnitude() > 0.0);
+        assert!(prey.velocity().x < 0.0);
+    }
+}
+
+#[test]
+fn test_prey_without_memory_relaxes_immediately() {
+    let params = PreyParameters::default();
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+
+    let mut world_state = create_test_world_state();
+    world_state.nearby_predators.push(neighbor(AgentId(2), Vector2::new(60.0, 50.0), 10.0, 100.0));
+    prey.update(&world_state);
+    let fleeing_speed = prey.velocity().magnitude();
+
+    world_state.nearby_predators.clear();
+    prey.update(&world_state);
+    assert!(prey.velocity().magnitude() < fleeing_speed);
+}
+
+#[test]
+fn test_prey_dodges_along_wall_when_cornered() {
+    let mut params = PreyParameters::default();
+    params.dodge_along_walls = true;
+    let mut prey = Prey::new(AgentId(1), Vector2::new(98.0, 50.0), params.clone());
+
+    // Predator to the west pushes the prey into the east wall
+    let mut world_state = create_test_world_state();
+    world_state.boundary_type = BoundaryType::Walls;
+    world_state.nearby_predators.push(neighbor(AgentId(2), Vector2::new(80.0, 50.0), 18.0, 100.0));
+    prey.update(&world_state);
+
+    let velocity = prey.velocity();
+    assert!(velocity.y.abs() > velocity.x.abs());
+    assert!(velocity.x <= 0.0);
+
+    // Under wraparound there is no wall to dodge
+    let mut wrapping = Prey::new(AgentId(1), Vector2::new(98.0, 50.0), params);
+    world_state.boundary_type = BoundaryType::Wraparound;
+    wrapping.update(&world_state);
+    assert!(wrapping.velocity().x > 0.0);
+}
+
+#[test]
+fn test_prey_reproduces_only_when_recently_fed() {
+    let mut params = PreyParameters::default();
+    params.well_fed_window = 10;
+    let world_state = create_test_world_state();
+
+    let mut hungry = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+    hungry.set_energy(params.reproduction_threshold + 50.0);
+    assert!(!matches!(hungry.update(&world_state), AgentAction::Reproduce { .. }));
+
+    let mut fed = Prey::new(AgentId(2), Vector2::new(50.0, 50.0), params.clone());
+    fed.set_energy(params.reproduction_threshold + 50.0);
+    fed.feed(0.0);
+    assert!(matches!(fed.update(&world_state), AgentAction::Reproduce { .. }));
+}
+
+#[test]
+fn test_prey_below_maturity_age_never_reproduces() {
+    let mut params = PreyParameters::default();
+    params.maturity_age = 50;
+    let world_state = create_test_world_state();
+
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+    for _ in 1..50 {
+        prey.set_energy(params.reproduction_threshold + 50.0);
+        assert!(!matches!(prey.update(&world_state), AgentAction::Reproduce { .. }));
+    }
+
+    // Age 50 after this update: mature
+    prey.set_energy(params.reproduction_threshold + 50.0);
+    assert!(matches!(prey.update(&world_state), AgentAction::Reproduce { .. }));
+}
+
+#[test]
+fn test_prey_dash_then_cooldown() {
+    let mut params = PreyParameters::default();
+    params.dash_distance = 20.0;
+    params.dash_trigger_range = 10.0;
+    params.dash_cooldown = 5;
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+    let world_state = create_test_world_state();
+
+    // Predator always 5 units to the west of wherever the prey is
+    let step = |prey: &mut Prey| {
+        let start = prey.position();
+        let mut state = world_state.clone();
+        state.nearby_predators.push(neighbor(AgentId(2), start.subtract(&Vector2::new(5.0, 0.0)), 5.0, 100.0));
+        prey.update(&state);
+        prey.position().x - start.x
+    };
+
+    let dashed = step(&mut prey);
+    assert!((dashed - 20.0).abs() < 1.0, "dashed {}", dashed);
+    assert_eq!(prey.dash_cooldown_remaining(), 5);
+
+    for _ in 1..5 {
+        assert!(step(&mut prey) < 1.0);
+    }
+    assert!(step(&mut prey) > 19.0);
+}
+
+#[test]
+fn test_calm_prey_returns_to_safe_spot() {
+    let mut params = PreyParameters::default();
+    params.home_range_weight = 0.5;
+    params.comfort_energy = 1000.0; // Never comfortable: always heads home once it has a spot
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+    let calm = create_test_world_state();
+
+    // First calm tick records the safe spot
+    prey.update(&calm);
+    let spot = prey.safe_spot().unwrap();
+
+    // A predator chases it away
+    let mut threatened = create_test_world_state();
+    threatened.nearby_predators.push(neighbor(AgentId(2), Vector2::new(40.0, 50.0), 10.0, 100.0));
+    for _ in 0..30 {
+        prey.update(&threatened);
+    }
+    let displaced = prey.position().subtract(&spot).magnitude();
+    assert!(displaced > 0.5);
+
+    // Once calm again it turns around and heads back
+    for _ in 0..20 {
+        prey.update(&calm);
+    }
+    assert!(prey.velocity().x < 0.0);
+    assert!(prey.position().subtract(&spot).magnitude() < displaced);
+    assert_eq!(prey.safe_spot(), Some(spot));
+}
+
+#[test]
+fn test_budding_prey_spawns_one_offspring_when_progress_completes() {
+    let mut params = PreyParameters::default();
+    params.reproduction_strategy = ReproductionStrategy::Budding;
+    params.budding_threshold = 50.0;
+    params.reproduction_cost = 60.0;
+    params.energy_regeneration = 0.0;
+    params.initial_energy = 95.0; // 45 surplus = 0.75 of an offspring
+
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+    let world_state = create_test_world_state();
+
+    // The surplus is banked as progress, but not enough for a child yet
+    assert!(!matches!(prey.update(&world_state), AgentAction::Reproduce { .. }));
+    assert_f64_approx(prey.energy(), 50.0, 1e-9);
+    assert_f64_approx(prey.reproduction_progress(), 0.75, 1e-9);
+
+    // Another 15 energy above the threshold (a quarter offspring) completes it
+    prey.set_energy(65.0);
+    let mut births = 0;
+    for _ in 0..10 {
+        if let AgentAction::Reproduce { .. } = prey.update(&world_state) {
+            births += 1;
+        }
+    }
+    assert_eq!(births, 1);
+    assert_f64_approx(prey.energy(), 50.0, 1e-9);
+    assert_f64_approx(prey.reproduction_progress(), 0.0, 1e-9);
+}
+
+#[test]
+fn test_hungry_prey_has_weaker_cohesion_than_fed_prey() {
+    use predator_prey_sim::config::parameters::EnergyCurve;
+
+    let mut params = PreyParameters::default();
+    params.cohesion_weight = 0.5;
+    params.cohesion_energy_curve = EnergyCurve { hungry: 0.2, fed: 1.5, exponent: 1.0 };
+
+    let mut world_state = create_test_world_state();
+    world_state.nearby_prey = vec![
+        neighbor(AgentId(2), Vector2::new(70.0, 50.0), 20.0, 80.0),
+        neighbor(AgentId(3), Vector2::new(60.0, 60.0), 14.1, 80.0),
+    ];
+
+    let mut hungry = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+    hungry.set_energy(10.0);
+    let mut fed = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+    fed.set_energy(120.0);
+
+    let hungry_cohesion = hungry.cohesion_steering(&world_state);
+    let fed_cohesion = fed.cohesion_steering(&world_state);
+    assert!(hungry_cohesion.magnitude() > 0.0);
+    assert!(hungry_cohesion.magnitude() < fed_cohesion.magnitude());
+    // Same direction, toward the neighbors' center
+    assert!(hungry_cohesion.x > 0.0 && hungry_cohesion.y > 0.0);
+    assert!((hungry_cohesion.normalize().x - fed_cohesion.normalize().x).abs() < 1e-9);
+}
+
+#[test]
+fn test_prey_flees_mainly_from_fast_approaching_predator() {
+    let mut params = PreyParameters::default();
+    params.threat_speed_weight = 1.0;
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params);
+
+    let mut world_state = create_test_world_state();
+    // Fast predator 20 below, closing in; slow one 15 to the left, moving away
+    let mut approaching = neighbor(AgentId(2), Vector2::new(50.0, 30.0), 20.0, 100.0);
+    approaching.velocity = Vector2::new(0.0, 2.0);
+    let mut receding = neighbor(AgentId(3), Vector2::new(35.0, 50.0), 15.0, 100.0);
+    receding.velocity = Vector2::new(-0.5, 0.0);
+    world_state.nearby_predators = vec![approaching, receding];
+
+    prey.update(&world_state);
+    let flee = prey.velocity();
+    // Away from the approaching predator (+y) dominates away from the receding one (+x)
+    assert!(flee.y > 0.0 && flee.x >= 0.0);
+    assert!(flee.y > 3.0 * flee.x, "flee velocity {:?}", flee);
+}
+
+#[test]
+fn test_prey_steers_down_scent_gradient() {
+    let params = PreyParameters::default();
+    let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+    let mut world_state = create_test_world_state();
+    world_state.scent_gradient = Vector2::new(0.3, 0.0);
+
+    prey.update(&world_state);
+
+    assert!(prey.velocity().x < 0.0);
+    assert_f64_approx(prey.velocity().y, 0.0, 1e-12);
+}
+
+#[test]
+fn test_crowded_prey_reproduce_less_often() {
+    use rand::SeedableRng;
+
+    let mut params = PreyParameters::default();
+    params.crowding_suppression = 0.8;
+    params.crowding_cap = 8;
+
+    let isolated = create_test_world_state();
+    let mut crowded = create_test_world_state();
+    for i in 0..6 {
+        let position = Vector2::new(50.0 + i as f64, 52.0);
+        crowded.nearby_prey.push(neighbor(AgentId(10 + i), position, 2.0, 50.0));
+    }
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+    let mut births = |world_state: &WorldState| {
+        (0..500)
+            .filter(|_| {
+                let mut prey = Prey::new(AgentId(1), Vector2::new(50.0, 50.0), params.clone());
+                prey.set_energy(params.reproduction_threshold + 50.0);
+                matches!(prey.update_with_rng(world_state, &mut rng), AgentAction::Reproduce { .. })
+            })
+            .count()
+    };
+    let alone = births(&isolated);
+    let packed = births(&crowded);
+
+    assert_eq!(alone, 500);
+    // 6 of 8 neighbors: 1 - 0.8 * 0.75 = 40% of attempts succeed
+    assert!((150..250).contains(&packed), "crowded prey bred {} times", packed);
+}
+
+#[test]
+fn test_idle_prey_wanders_with_persistent_heading() {
+    use rand::SeedableRng;
+
+    let mut world_state = create_test_world_state();
+    world_state.width = 10_000.0;
+    world_state.height = 10_000.0;
+    world_state.dt = 1.0;
+    let mut params = PreyParameters::default();
+    params.wander_strength = 0.4;
+    let mut prey = Prey::new(AgentId(1), Vector2::new(5_000.0, 5_000.0), params.clone());
+    let mut rng = rand::rngs::StdRng::seed_from_u64(4);
+
+    let mut headings = Vec::new();
+    for _ in 0..100 {
+        prey.update_with_rng(&world_state, &mut rng);
+        headings.push(prey.velocity().y.atan2(prey.velocity().x));
+    }
+    // Up to speed after the ramp, steering no more than a little per tick
+    let speed = prey.velocity().magnitude();
+    assert!(speed > 0.5 * params.wander_strength * params.max_speed, "speed {}", speed);
+    for pair in headings[50..].windows(2) {
+        let turn = (pair[1] - pair[0] + std::f64::consts::PI).rem_euclid(2.0 * std::f64::consts::PI) - std::f64::consts::PI;
+        assert!(turn.abs() < 0.3, "heading jumped by {}", turn);
+    }
+}
+
+#[test]
+fn test_three_prey_converge_by_cohesion() {
+    use predator_prey_sim::utils::math::distance;
+    use rand::SeedableRng;
+
+    let mut params = PreyParameters::default();
+    params.cohesion_weight = 0.5;
+    params.alignment_weight = 0.2;
+    params.separation_weight = 0.5;
+    params.separation_distance = 3.0;
+    let mut herd: Vec<Prey> = [(30.0, 30.0), (60.0, 35.0), (45.0, 65.0)]
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, y))| Prey::new(AgentId(i as u64 + 1), Vector2::new(x, y), params.clone()))
+        .collect();
+    let spread = |herd: &[Prey]| {
+        let mut widest: f64 = 0.0;
+        for a in herd {
+            for b in herd {
+                widest = widest.max(distance(&a.position(), &b.position()));
+            }
+        }
+        widest
+    };
+
+    let mut world_state = create_test_world_state();
+    world_state.dt = 1.0;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(2);
+    let initial_spread = spread(&herd);
+    for _ in 0..150 {
+        // Everyone perceives everyone else's position from the start of the tick
+        let snapshot: Vec<NeighborInfo> = herd
+            .iter()
+            .map(|p| NeighborInfo { id: p.id(), position: p.position(), velocity: p.velocity(), energy: p.energy(), distance: 0.0 })
+            .collect();
+        for prey in herd.iter_mut() {
+            world_state.nearby_prey = snapshot
+                .iter()
+                .filter(|n| n.id != prey.id())
+                .map(|n| NeighborInfo { distance: distance(&prey.position(), &n.position), ..*n })
+                .collect();
+            prey.update_with_rng(&world_state, &mut rng);
+        }
+    }
+
+    let final_spread = spread(&herd);
+    assert!(final_spread < initial_spread / 3.0, "spread went from {} to {}", initial_spread, final_spread);
+    // Separation keeps them from collapsing onto one point
+    assert!(final_spread > 1.0, "herd collapsed to {}", final_spread);
+}
+
+#[test]
+fn test_prey_flees_across_wraparound_edge() {
+    let params = PreyParameters::default();
+    let mut prey = Prey::new(AgentId(1), Vector2::new(5.0, 50.0), params);
+
+    // The predator at x=95 is 10 units away across the x=0 seam, so away is +x
+    let mut world_state = create_test_world_state();
+    world_state.nearby_predators.push(neighbor(AgentId(2), Vector2::new(95.0, 50.0), 10.0, 100.0));
+    prey.update(&world_state);
+
+    assert!(prey.velocity().x > 0.0, "prey fled toward the predator: {:?}", prey.velocity());
+    assert!(prey.velocity().y.abs() < 1e-9);
+}
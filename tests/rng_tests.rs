@@ -0,0 +1,57 @@
+// RNG tests
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::agent::Agent;
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::utils::math::Vector2;
+use predator_prey_sim::utils::rng::{RngAlgorithm, WorldRng};
+use rand::RngCore;
+
+fn seeded_params(algorithm: RngAlgorithm, seed: u64) -> Parameters {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(seed);
+    params.simulation.rng_algorithm = algorithm;
+    params
+}
+
+fn positions(world: &World) -> Vec<Vector2> {
+    world
+        .predators()
+        .iter()
+        .map(|p| p.position())
+        .chain(world.prey().iter().map(|p| p.position()))
+        .collect()
+}
+
+#[test]
+fn test_same_algorithm_and_seed_match() {
+    for algorithm in [RngAlgorithm::Std, RngAlgorithm::ChaCha8] {
+        let a = World::new(seeded_params(algorithm, 7));
+        let b = World::new(seeded_params(algorithm, 7));
+        assert_eq!(positions(&a), positions(&b));
+    }
+}
+
+#[test]
+fn test_algorithms_give_different_runs_for_one_seed() {
+    // ChaCha12 and ChaCha8 share the seeding but differ in rounds, so one seed
+    // yields unrelated streams: a seed only reproduces a run on its own algorithm
+    let std_world = World::new(seeded_params(RngAlgorithm::Std, 7));
+    let chacha8_world = World::new(seeded_params(RngAlgorithm::ChaCha8, 7));
+    assert_ne!(positions(&std_world), positions(&chacha8_world));
+}
+
+#[test]
+fn test_world_rng_reports_algorithm() {
+    let mut rng = WorldRng::new(RngAlgorithm::ChaCha8, Some(1));
+    assert_eq!(rng.algorithm(), RngAlgorithm::ChaCha8);
+    let first = rng.next_u64();
+    assert_eq!(WorldRng::new(RngAlgorithm::ChaCha8, Some(1)).next_u64(), first);
+}
+
+#[test]
+fn test_old_small_name_still_loads() {
+    let algorithm: RngAlgorithm = serde_json::from_str("\"Small\"").unwrap();
+    assert_eq!(algorithm, RngAlgorithm::ChaCha8);
+    assert_eq!(serde_json::to_string(&algorithm).unwrap(), "\"ChaCha8\"");
+}
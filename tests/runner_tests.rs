@@ -0,0 +1,187 @@
+// Headless runner tests
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::runner::{classify_outcome, classify_outcome_with, compare, run_headless, stress_test, run_headless_to_jsonl, run_headless_with_checkpoints, Outcome, OutcomeThresholds, Simulation, StopReason};
+use predator_prey_sim::simulation::snapshot::{CheckpointConfig, WorldSnapshot};
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::ui::statistics::Statistics;
+use predator_prey_sim::utils::export::RunSummary;
+
+#[test]
+fn test_run_headless_stops_at_tick() {
+    let params = Parameters::default();
+    let mut world = World::new(params);
+
+    let outcome = run_headless(&mut world, 50);
+
+    assert_eq!(world.tick(), outcome.ticks);
+    match outcome.reason {
+        StopReason::ReachedStopTick => assert_eq!(outcome.ticks, 50),
+        StopReason::Extinction => assert!(outcome.ticks <= 50),
+    }
+}
+
+#[test]
+fn test_run_headless_stops_early_on_extinction() {
+    let mut params = Parameters::default();
+    params.predator.initial_count = 3;
+    params.prey.initial_count = 0;
+    params.predator.energy_per_tick = 10_000.0; // Predators starve on the first tick
+    let mut world = World::new(params);
+
+    let outcome = run_headless(&mut world, 50);
+
+    assert_eq!(outcome.reason, StopReason::Extinction);
+    assert_eq!(outcome.ticks, 1);
+    assert_eq!(world.tick(), 1);
+}
+
+#[test]
+fn test_run_headless_to_jsonl_writes_every_tick() {
+    let mut params = Parameters::default();
+    params.simulation.enable_reproduction = false;
+    let mut world = World::new(params);
+    let path = std::env::temp_dir().join(format!("pps_jsonl_test_{}.jsonl", std::process::id()));
+    // Leftovers from an earlier run are replaced, not appended to
+    std::fs::write(&path, "stale\n").unwrap();
+
+    let mut expected_lines = 0;
+    let outcome = run_headless_to_jsonl(&mut world, 3, path.to_str().unwrap()).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    for tick in 1..=outcome.ticks {
+        let lines = content.lines().filter(|l| l.contains(&format!("\"tick\":{},", tick))).count();
+        assert!(lines > 0);
+        expected_lines += lines;
+    }
+    assert_eq!(content.lines().count(), expected_lines);
+    assert!(!content.contains("stale"));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_checkpoints_keep_last_three() {
+    let mut params = Parameters::default();
+    params.simulation.enable_reproduction = false;
+    let mut world = World::new(params);
+    let dir = std::env::temp_dir().join(format!("pps_checkpoint_test_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let config = CheckpointConfig {
+        dir: dir.clone(),
+        interval: 25,
+        keep_last: 3,
+    };
+    let outcome = run_headless_with_checkpoints(&mut world, 100, config).unwrap();
+    assert_eq!(outcome.ticks, 100);
+
+    let mut files: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec!["checkpoint_00000050.json", "checkpoint_00000075.json", "checkpoint_00000100.json"]
+    );
+
+    // The newest checkpoint restores the world at that tick
+    let snapshot = WorldSnapshot::load(dir.join("checkpoint_00000100.json")).unwrap();
+    let restored = World::from_snapshot(snapshot);
+    assert_eq!(restored.tick(), 100);
+    assert_eq!(restored.total_agents(), world.total_agents());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_compare_same_params_and_seed_match() {
+    let mut params = Parameters::default();
+    params.simulation.deterministic_offspring = true;
+
+    let (a, b) = compare(params.clone(), params, 7, 100);
+    assert!(a.ticks > 0);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_compare_summarizes_each_parameter_set() {
+    let params_a = Parameters::default();
+    let mut params_b = Parameters::default();
+    params_b.predator.initial_count = 0;
+    params_b.simulation.enable_reproduction = false;
+
+    let (a, b) = compare(params_a, params_b, 7, 20);
+    assert!(a.final_predators > 0);
+    assert_eq!(b.final_predators, 0);
+    assert_eq!(b.ticks, 20);
+}
+
+#[test]
+fn test_stress_test_fills_to_max_agents() {
+    let mut params = Parameters::default();
+    params.simulation.max_agents = 300;
+    let mut world = World::new(params);
+
+    let report = stress_test(&mut world, 20);
+
+    assert_eq!(report.agents, 300);
+    assert_eq!(report.ticks, 20);
+    assert!(world.total_agents() <= 300);
+    assert!(report.ticks_per_second > 0.0 && report.ticks_per_second.is_finite());
+    assert!(report.mean_tick_ms > 0.0);
+}
+
+/// Statistics whose populations peaked at the given counts
+fn peaks(max_predators: usize, max_prey: usize) -> Statistics {
+    Statistics { max_predators, max_prey, ..Statistics::default() }
+}
+
+fn ended_with(final_predators: usize, final_prey: usize) -> RunSummary {
+    RunSummary { ticks: 5000, final_predators, final_prey }
+}
+
+#[test]
+fn test_classify_outcome_labels_each_category() {
+    assert_eq!(classify_outcome(&peaks(30, 200), &ended_with(12, 140)), Outcome::StableCoexistence);
+    assert_eq!(classify_outcome(&peaks(60, 200), &ended_with(25, 0)), Outcome::PreyExtinction);
+    assert_eq!(classify_outcome(&peaks(30, 400), &ended_with(0, 390)), Outcome::PredatorExtinction);
+    assert_eq!(classify_outcome(&peaks(80, 300), &ended_with(0, 0)), Outcome::BothExtinct);
+    // Default cap is 1000 agents, cap-bound from 950
+    assert_eq!(classify_outcome(&peaks(40, 900), &ended_with(40, 910)), Outcome::Explosion);
+}
+
+#[test]
+fn test_classify_outcome_explosion_from_trajectory_peak() {
+    // Recovered from a cap-bound prey boom: the peak still marks it
+    assert_eq!(classify_outcome(&peaks(50, 960), &ended_with(30, 200)), Outcome::Explosion);
+
+    // Extinction wins over a boom earlier in the run
+    assert_eq!(classify_outcome(&peaks(50, 990), &ended_with(30, 0)), Outcome::PreyExtinction);
+
+    // A higher cap makes the same run stable
+    let thresholds = OutcomeThresholds { agent_cap: 5000, ..OutcomeThresholds::default() };
+    assert_eq!(classify_outcome_with(&peaks(50, 960), &ended_with(30, 200), &thresholds), Outcome::StableCoexistence);
+}
+
+#[test]
+fn test_simulation_respects_max_agents_for_1000_ticks() {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(8);
+    params.simulation.max_agents = 150;
+    let max_agents = params.simulation.max_agents as usize;
+    let mut simulation = Simulation::new(params);
+    assert!(simulation.world().total_agents() <= max_agents);
+
+    let mut peak = 0;
+    let ticks = simulation.run_until(|world| {
+        peak = peak.max(world.total_agents());
+        world.tick() >= 1000
+    });
+    assert_eq!(ticks, 1000);
+    assert_eq!(simulation.world().tick(), 1000);
+    assert!(peak <= max_agents, "population peaked at {} over a cap of {}", peak, max_agents);
+
+    simulation.run_for(5);
+    assert_eq!(simulation.into_world().tick(), 1005);
+}
@@ -0,0 +1,44 @@
+// Session tests (need the `gui` feature)
+#![cfg(feature = "gui")]
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::ui::session::Session;
+use predator_prey_sim::ui::statistics::StatisticsCollector;
+use predator_prey_sim::ui::visualization::VisualizationSettings;
+
+#[test]
+fn test_session_round_trip() {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(1234);
+    let mut world = World::new(params.clone());
+    let mut statistics = StatisticsCollector::new(100);
+    for _ in 0..5 {
+        world.update();
+        statistics.record(
+            world.predator_count(),
+            world.prey_count(),
+            world.average_predator_energy(),
+            world.average_prey_energy(),
+            world.average_predator_speed(),
+            world.average_prey_speed(),
+        );
+    }
+    let mut viz_settings = VisualizationSettings::default();
+    viz_settings.show_velocity_vectors = true;
+
+    let session = Session { params, world: world.snapshot(), statistics, viz_settings };
+    let path = std::env::temp_dir().join(format!("pps_session_test_{}.json", std::process::id()));
+    session.save(&path).unwrap();
+    let loaded = Session::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let restored = World::from_snapshot(loaded.world);
+    assert_eq!(restored.predator_count(), world.predator_count());
+    assert_eq!(restored.prey_count(), world.prey_count());
+    assert_eq!(restored.tick(), 5);
+    assert_eq!(restored.state_hash(), world.state_hash());
+    assert_eq!(loaded.statistics.data().len(), 5);
+    assert_eq!(loaded.params.simulation.seed, Some(1234));
+    assert!(loaded.viz_settings.show_velocity_vectors);
+}
@@ -0,0 +1,246 @@
+// Spatial helper tests
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::agent::AgentType;
+use predator_prey_sim::simulation::spatial::{cell_index, density_grid, morans_i};
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::utils::export::grid_to_csv;
+use predator_prey_sim::utils::math::Vector2;
+
+#[test]
+fn test_cell_index() {
+    assert_eq!(cell_index(Vector2::new(0.0, 0.0), 4, 3, 100.0, 60.0), (0, 0));
+    assert_eq!(cell_index(Vector2::new(30.0, 25.0), 4, 3, 100.0, 60.0), (1, 1));
+    // Far edges clamp into the last cell
+    assert_eq!(cell_index(Vector2::new(100.0, 60.0), 4, 3, 100.0, 60.0), (3, 2));
+}
+
+#[test]
+fn test_density_grid_concentrated_in_corner() {
+    let positions = vec![
+        Vector2::new(1.0, 1.0),
+        Vector2::new(5.0, 3.0),
+        Vector2::new(9.0, 9.0),
+    ];
+    let grid = density_grid(positions, 10, 10, 100.0, 100.0);
+
+    assert_eq!(grid.len(), 10);
+    assert_eq!(grid[0][0], 3);
+    let total: usize = grid.iter().flatten().sum();
+    assert_eq!(total, 3);
+}
+
+#[test]
+fn test_world_density_grid_counts_by_type() {
+    let params = Parameters::default();
+    let world = World::new(params);
+
+    let all: usize = world.density_grid(8, 6, None).iter().flatten().sum();
+    let predators: usize = world.density_grid(8, 6, Some(AgentType::Predator)).iter().flatten().sum();
+    let prey: usize = world.density_grid(8, 6, Some(AgentType::Prey)).iter().flatten().sum();
+
+    assert_eq!(all, world.total_agents());
+    assert_eq!(predators, world.predator_count());
+    assert_eq!(prey, world.prey_count());
+}
+
+#[test]
+fn test_grid_to_csv() {
+    let grid = vec![vec![1, 0], vec![0, 2]];
+    assert_eq!(grid_to_csv(&grid), "1,0\n0,2\n");
+}
+
+#[test]
+fn test_morans_i_cluster_beats_scatter() {
+    // 50 agents packed into a 30x30 patch of a 100x100 world
+    let clustered: Vec<Vector2> = (0..50)
+        .map(|i| Vector2::new(35.0 + (i % 7) as f64 * 4.0, 35.0 + (i / 7) as f64 * 4.0))
+        .collect();
+    // 50 agents spread in a checkerboard over the whole world
+    let scattered: Vec<Vector2> = (0..100)
+        .filter(|i| (i % 10 + i / 10) % 2 == 0)
+        .map(|i| Vector2::new((i % 10) as f64 * 10.0 + 5.0, (i / 10) as f64 * 10.0 + 5.0))
+        .collect();
+
+    let clustered_i = morans_i(&density_grid(clustered, 10, 10, 100.0, 100.0), true);
+    let scattered_i = morans_i(&density_grid(scattered, 10, 10, 100.0, 100.0), true);
+
+    assert!(clustered_i > 0.3);
+    assert!(clustered_i > scattered_i);
+}
+
+#[test]
+fn test_morans_i_uniform_presence_is_zero() {
+    let grid = vec![vec![1; 5]; 5];
+    assert_eq!(morans_i(&grid, true), 0.0);
+
+    let world = World::new(Parameters::default());
+    assert!(world.morans_i(AgentType::Prey).is_finite());
+}
+
+#[test]
+fn test_spatial_grid_matches_brute_force() {
+    use predator_prey_sim::simulation::spatial::SpatialGrid;
+    use predator_prey_sim::utils::math::distance_torus;
+    use rand::{Rng, SeedableRng};
+
+    let (width, height) = (300.0, 200.0);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+    let positions: Vec<Vector2> = (0..500)
+        .map(|_| Vector2::new(rng.gen_range(0.0..width), rng.gen_range(0.0..height)))
+        .collect();
+
+    for (cell_size, radius) in [(25.0, 25.0), (25.0, 60.0), (80.0, 10.0), (1000.0, 40.0)] {
+        let grid = SpatialGrid::new(positions.iter().copied(), width, height, cell_size);
+        // Query points include the world edges, where neighbors wrap around
+        for query in [Vector2::new(0.0, 0.0), Vector2::new(150.0, 100.0), Vector2::new(299.0, 1.0)] {
+            let mut from_grid = Vec::new();
+            grid.for_each_candidate(query, radius, |i| {
+                if distance_torus(&query, &positions[i], width, height) <= radius {
+                    from_grid.push(i);
+                }
+            });
+            from_grid.sort_unstable();
+
+            let brute_force: Vec<usize> = (0..positions.len())
+                .filter(|&i| distance_torus(&query, &positions[i], width, height) <= radius)
+                .collect();
+            assert_eq!(from_grid, brute_force);
+        }
+    }
+}
+
+/// Uniform scatter plus a tight clump straddling the world's corner, so
+/// the quadtree subdivides deeply right where queries wrap
+fn clumped_positions(width: f64, height: f64) -> Vec<Vector2> {
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+    let mut positions: Vec<Vector2> = (0..200)
+        .map(|_| Vector2::new(rng.gen_range(0.0..width), rng.gen_range(0.0..height)))
+        .collect();
+    positions.extend((0..300).map(|_| {
+        let x = (rng.gen_range(-8.0..8.0) + width) % width;
+        let y = (rng.gen_range(-8.0..8.0) + height) % height;
+        Vector2::new(x, y)
+    }));
+    positions
+}
+
+#[test]
+fn test_quadtree_matches_brute_force_for_both_boundaries() {
+    use predator_prey_sim::config::parameters::BoundaryType;
+    use predator_prey_sim::simulation::spatial::QuadTree;
+    use predator_prey_sim::utils::math::{distance, distance_torus};
+
+    let (width, height) = (300.0, 200.0);
+    let positions = clumped_positions(width, height);
+
+    for boundary in [BoundaryType::Wraparound, BoundaryType::Walls] {
+        let separation = |a: &Vector2, b: &Vector2| match boundary {
+            BoundaryType::Wraparound => distance_torus(a, b, width, height),
+            BoundaryType::Walls => distance(a, b),
+        };
+        for capacity in [1, 4, 32] {
+            let tree = QuadTree::new(positions.iter().copied(), width, height, capacity);
+            // Corners and edges, where a wrapped query splits into pieces
+            let queries = [
+                Vector2::new(0.0, 0.0),
+                Vector2::new(299.0, 1.0),
+                Vector2::new(2.0, 199.5),
+                Vector2::new(150.0, 0.0),
+                Vector2::new(150.0, 100.0),
+                Vector2::new(300.0, 200.0),
+            ];
+            for query in queries {
+                for radius in [5.0, 25.0, 120.0, 400.0] {
+                    let mut visits = vec![0; positions.len()];
+                    let mut from_tree = Vec::new();
+                    tree.for_each_candidate(query, radius, |i| {
+                        visits[i] += 1;
+                        if separation(&query, &positions[i]) <= radius {
+                            from_tree.push(i);
+                        }
+                    });
+                    from_tree.sort_unstable();
+                    assert!(visits.iter().all(|&count| count <= 1), "a candidate was visited twice");
+
+                    let brute_force: Vec<usize> = (0..positions.len())
+                        .filter(|&i| separation(&query, &positions[i]) <= radius)
+                        .collect();
+                    assert_eq!(from_tree, brute_force, "{boundary:?} capacity {capacity} at {query:?} r={radius}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_quadtree_remove_and_rebuild() {
+    use predator_prey_sim::simulation::spatial::QuadTree;
+
+    let (width, height) = (300.0, 200.0);
+    let positions = clumped_positions(width, height);
+    let mut tree = QuadTree::new(positions.iter().copied(), width, height, 4);
+    // The corner clump forces subdivision well past the root
+    assert!(tree.node_count() > 4);
+
+    let corner = Vector2::new(0.0, 0.0);
+    let count_near = |tree: &QuadTree| {
+        let mut count = 0;
+        tree.for_each_candidate(corner, 10.0, |_| count += 1);
+        count
+    };
+    let before = count_near(&tree);
+    assert!(tree.remove(200));
+    assert!(!tree.remove(200));
+    assert!(!tree.remove(positions.len()));
+    assert_eq!(count_near(&tree), before - 1);
+
+    // Rebuilding with everything in one spot stays shallow and finds it all
+    tree.rebuild(std::iter::repeat(Vector2::new(50.0, 50.0)).take(100), width, height, 4);
+    let mut found = 0;
+    tree.for_each_candidate(Vector2::new(50.0, 50.0), 0.0, |_| found += 1);
+    assert_eq!(found, 100);
+}
+
+#[test]
+fn test_quadtree_world_matches_grid_world_populations() {
+    use predator_prey_sim::config::parameters::SpatialIndexKind;
+
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(17);
+    let mut grid_world = World::new(params.clone());
+    params.simulation.spatial_index_kind = SpatialIndexKind::QuadTree;
+    params.simulation.quadtree_capacity = 4;
+    let mut tree_world = World::new(params);
+
+    for _ in 0..200 {
+        grid_world.update();
+        tree_world.update();
+    }
+    assert_eq!(tree_world.predator_count(), grid_world.predator_count());
+    assert_eq!(tree_world.prey_count(), grid_world.prey_count());
+}
+
+#[test]
+fn test_scent_gradient_points_toward_stronger_scent() {
+    use predator_prey_sim::simulation::scent::ScentField;
+
+    let mut scent = ScentField::new(100.0, 100.0, 10.0);
+    scent.deposit(Vector2::new(35.0, 55.0), 4.0);
+    assert_eq!(scent.at(Vector2::new(31.0, 59.0)), 4.0);
+
+    // Neighbor to the left of the scented cell: gradient points right (+x)
+    let gradient = scent.gradient(Vector2::new(25.0, 55.0), false);
+    assert_eq!(gradient, Vector2::new(4.0 / 20.0, 0.0));
+
+    // Across the edge only when wrapping
+    let mut edge = ScentField::new(100.0, 100.0, 10.0);
+    edge.deposit(Vector2::new(95.0, 5.0), 2.0);
+    assert_eq!(edge.gradient(Vector2::new(5.0, 5.0), true).x, -2.0 / 20.0);
+    assert_eq!(edge.gradient(Vector2::new(5.0, 5.0), false), Vector2::zero());
+
+    scent.decay(0.5);
+    assert_eq!(scent.max(), 2.0);
+}
@@ -1,7 +1,9 @@
 // Spawn functionality tests
 
 use predator_prey_sim::simulation::world::World;
-use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::config::parameters::{Obstacle, ObstacleGenerator, Parameters, SpawnPattern};
+use predator_prey_sim::simulation::agent::Agent;
+use predator_prey_sim::utils::math::distance_torus;
 
 #[test]
 fn test_spawn_predators() {
@@ -79,3 +81,143 @@ fn test_average_energies() {
     }
 }
 
+#[test]
+fn test_spawn_avoids_obstacles() {
+    let mut params = Parameters::default();
+    // Obstacle covers 95% of the 800x600 world, leaving a strip on the east side
+    let obstacle = Obstacle::new(0.0, 0.0, 760.0, 600.0);
+    params.world.obstacles = vec![obstacle];
+    params.predator.initial_count = 50;
+    params.prey.initial_count = 100;
+    params.simulation.max_agents = 1000;
+    let mut world = World::new(params);
+    world.spawn_predators(100);
+    world.spawn_prey(200);
+
+    assert!(world.total_agents() > 0);
+    for predator in world.predators() {
+        assert!(!obstacle.contains(predator.position()));
+    }
+    for prey in world.prey() {
+        assert!(!obstacle.contains(prey.position()));
+    }
+}
+
+#[test]
+fn test_spawn_respects_min_separation() {
+    let mut params = Parameters::default();
+    params.world.min_spawn_separation = 20.0;
+    let mut world = World::new(params);
+    world.spawn_prey(50);
+
+    let positions: Vec<_> = world
+        .predators()
+        .iter()
+        .map(|p| p.position())
+        .chain(world.prey().iter().map(|p| p.position()))
+        .collect();
+    assert!(positions.len() > 50);
+    for (i, a) in positions.iter().enumerate() {
+        for b in &positions[i + 1..] {
+            assert!(distance_torus(a, b, 800.0, 600.0) >= 20.0);
+        }
+    }
+}
+
+#[test]
+fn test_lattice_places_prey_on_even_grid() {
+    let mut params = Parameters::default();
+    params.world.spawn_pattern = SpawnPattern::Lattice { interleave: false };
+    params.predator.initial_count = 0;
+    params.prey.initial_count = 16;
+    let (width, height) = (params.world.width, params.world.height);
+
+    let world = World::new(params);
+    assert_eq!(world.prey_count(), 16);
+
+    let mut xs: Vec<f64> = world.prey().iter().map(|p| p.position().x).collect();
+    let mut ys: Vec<f64> = world.prey().iter().map(|p| p.position().y).collect();
+    for values in [&mut xs, &mut ys] {
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values.dedup();
+        assert_eq!(values.len(), 4);
+    }
+    for (values, extent) in [(&xs, width), (&ys, height)] {
+        let spacing = extent / 4.0;
+        for (i, value) in values.iter().enumerate() {
+            assert!((value - (i as f64 + 0.5) * spacing).abs() < 1e-9);
+        }
+    }
+
+    // No RNG involved: two worlds place agents identically
+    let again = World::new(world.parameters().clone());
+    let positions = |w: &World| w.prey().iter().map(|p| p.position()).collect::<Vec<_>>();
+    assert_eq!(positions(&world), positions(&again));
+}
+
+#[test]
+fn test_lattice_interleaves_predators_among_prey() {
+    let mut params = Parameters::default();
+    params.world.spawn_pattern = SpawnPattern::Lattice { interleave: true };
+    params.predator.initial_count = 4;
+    params.prey.initial_count = 12;
+
+    let world = World::new(params);
+    assert_eq!(world.predator_count(), 4);
+    assert_eq!(world.prey_count(), 12);
+
+    // One predator in every row of the 4x4 grid
+    let mut rows: Vec<f64> = world.predators().iter().map(|p| p.position().y).collect();
+    rows.dedup();
+    assert_eq!(rows.len(), 4);
+}
+
+#[test]
+fn test_random_blocks_reproducible_from_seed() {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(42);
+    params.world.obstacles = vec![Obstacle::new(0.0, 0.0, 10.0, 10.0)];
+    params.world.obstacle_generator = Some(ObstacleGenerator::RandomBlocks { count: 8, size: 30.0 });
+
+    let first = World::new(params.clone());
+    let second = World::new(params.clone());
+    let layout = |world: &World| world.obstacles().copied().collect::<Vec<_>>();
+
+    // The hand-placed obstacle plus the requested number of blocks
+    assert_eq!(layout(&first).len(), 9);
+    assert_eq!(layout(&first), layout(&second));
+    for obstacle in layout(&first) {
+        assert!(obstacle.x >= 0.0 && obstacle.x + obstacle.width <= params.world.width);
+        assert!(obstacle.y >= 0.0 && obstacle.y + obstacle.height <= params.world.height);
+    }
+
+    // Agents never start inside a generated block
+    for agent in first.predators().iter().map(|p| p.position()).chain(first.prey().iter().map(|p| p.position())) {
+        assert!(first.obstacles().all(|o| !o.contains(agent)));
+    }
+}
+
+#[test]
+fn test_spawn_margin_keeps_agents_off_the_edges() {
+    let mut params = Parameters::default();
+    params.world.spawn_margin = 10.0;
+    params.world.width = 200.0;
+    params.world.height = 150.0;
+    params.predator.initial_count = 100;
+    params.prey.initial_count = 300;
+
+    let mut world = World::new(params);
+    world.spawn_predators(100);
+    world.spawn_prey(300);
+    assert_eq!(world.total_agents(), 800);
+
+    let positions = world
+        .predators()
+        .iter()
+        .map(|p| p.position())
+        .chain(world.prey().iter().map(|p| p.position()));
+    for pos in positions {
+        assert!(pos.x >= 10.0 && pos.x <= 190.0, "x = {}", pos.x);
+        assert!(pos.y >= 10.0 && pos.y <= 140.0, "y = {}", pos.y);
+    }
+}
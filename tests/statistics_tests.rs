@@ -0,0 +1,128 @@
+// Statistics tests (need the `gui` feature)
+#![cfg(feature = "gui")]
+
+use predator_prey_sim::simulation::world::EnergyTotals;
+use predator_prey_sim::ui::statistics::{energy_band_edges, graph_max_count, is_tick_rate_low, legend_label, value_to_y, x_to_index, GraphStyle, StatisticsCollector, TickRateMeter};
+use predator_prey_sim::utils::color::Colors;
+
+#[test]
+fn test_reference_line_uses_data_mapping() {
+    let mut collector = StatisticsCollector::new(10);
+    collector.record(20, 60, 100.0, 80.0, 1.0, 1.5);
+    collector.record(25, 50, 100.0, 80.0, 1.0, 1.5);
+
+    let style = GraphStyle {
+        reference_lines: vec![(50.0, Colors::prey())],
+    };
+    let rect = egui::Rect::from_min_max(egui::Pos2::new(0.0, 0.0), egui::Pos2::new(200.0, 140.0));
+    let max_count = graph_max_count(&collector, &style);
+    assert_eq!(max_count, 60);
+
+    // A reference line at 50 sits exactly where a prey count of 50 is plotted
+    let data_y = value_to_y(collector.latest().unwrap().prey_count as f64, max_count, rect);
+    assert_eq!(value_to_y(50.0, max_count, rect), data_y);
+    assert_eq!(value_to_y(0.0, max_count, rect), rect.bottom());
+    assert_eq!(value_to_y(70.0, max_count, rect), rect.top());
+}
+
+#[test]
+fn test_reference_line_above_data_extends_range() {
+    let mut collector = StatisticsCollector::new(10);
+    collector.record(5, 30, 100.0, 80.0, 1.0, 1.5);
+
+    let style = GraphStyle {
+        reference_lines: vec![(120.0, Colors::prey())],
+    };
+    assert_eq!(graph_max_count(&collector, &style), 120);
+    assert_eq!(graph_max_count(&collector, &GraphStyle::default()), 30);
+}
+
+#[test]
+fn test_tick_rate_low_decision() {
+    assert!(!is_tick_rate_low(60.0, 60.0));
+    assert!(!is_tick_rate_low(55.0, 60.0));
+    assert!(is_tick_rate_low(30.0, 60.0));
+    // Nothing to fall behind when no ticks are requested
+    assert!(!is_tick_rate_low(0.0, 0.0));
+}
+
+#[test]
+fn test_tick_rate_meter_measures_over_one_second() {
+    let mut meter = TickRateMeter::default();
+    meter.record(0.0, 0);
+    for frame in 1..=30 {
+        meter.record(frame as f64 / 30.0, 1);
+    }
+    let rate = meter.rate().expect("a full window has elapsed");
+    assert!((rate - 30.0).abs() < 1e-6);
+
+    meter.reset();
+    assert!(meter.rate().is_none());
+}
+
+#[test]
+fn test_x_to_index_maps_edges_and_middle() {
+    let rect = egui::Rect::from_min_max(egui::Pos2::new(100.0, 0.0), egui::Pos2::new(300.0, 100.0));
+
+    assert_eq!(x_to_index(100.0, rect, 11), Some(0));
+    assert_eq!(x_to_index(200.0, rect, 11), Some(5));
+    assert_eq!(x_to_index(300.0, rect, 11), Some(10));
+
+    // Outside the graph clamps to the first or last point
+    assert_eq!(x_to_index(50.0, rect, 11), Some(0));
+    assert_eq!(x_to_index(350.0, rect, 11), Some(10));
+    assert_eq!(x_to_index(200.0, rect, 0), None);
+}
+
+#[test]
+fn test_phase_csv_has_row_per_point() {
+    let mut collector = StatisticsCollector::new(10);
+    collector.record(20, 60, 100.0, 80.0, 1.0, 1.5);
+    collector.record(25, 50, 100.0, 80.0, 1.0, 1.5);
+    collector.record(22, 41, 100.0, 80.0, 1.0, 1.5);
+
+    let csv = collector.phase_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines, vec!["predator_count,prey_count", "20,60", "25,50", "22,41"]);
+}
+
+#[test]
+fn test_stats_average_speeds() {
+    let mut collector = StatisticsCollector::new(10);
+    collector.record(20, 60, 100.0, 80.0, 1.0, 2.0);
+    collector.record(25, 50, 100.0, 80.0, 2.0, 3.0);
+
+    let stats = collector.stats();
+    assert!((stats.average_predator_speed - 1.5).abs() < 1e-12);
+    assert!((stats.average_prey_speed - 2.5).abs() < 1e-12);
+    assert_eq!(collector.latest().unwrap().prey_speed, 3.0);
+}
+
+#[test]
+fn test_legend_label_appends_current_value() {
+    assert_eq!(legend_label("Predators", 12), "Predators: 12");
+    assert_eq!(legend_label("Prey", 0), "Prey: 0");
+}
+
+#[test]
+fn test_energy_band_edges_stack_cumulative_sums() {
+    let rect = egui::Rect::from_min_max(egui::Pos2::new(0.0, 0.0), egui::Pos2::new(100.0, 200.0));
+
+    // The largest total fills the rect exactly
+    let full = EnergyTotals { predators: 100.0, prey: 200.0, food: 100.0 };
+    assert_eq!(energy_band_edges(&full, 400.0, rect), [200.0, 150.0, 50.0, 0.0]);
+
+    // A smaller point stacks in the same scale; empty bands have zero height
+    let partial = EnergyTotals { predators: 40.0, prey: 0.0, food: 60.0 };
+    assert_eq!(energy_band_edges(&partial, 400.0, rect), [200.0, 180.0, 180.0, 150.0]);
+}
+
+#[test]
+fn test_record_energy_attaches_to_latest_point() {
+    let mut collector = StatisticsCollector::new(10);
+    collector.record(20, 60, 100.0, 80.0, 1.0, 2.0);
+    let energy = EnergyTotals { predators: 2000.0, prey: 4800.0, food: 150.0 };
+    collector.record_energy(energy);
+    assert_eq!(collector.latest().unwrap().energy, energy);
+    assert_eq!(energy.total(), 6950.0);
+}
@@ -1,6 +1,9 @@
 // Torus topography tests
 
-use predator_prey_sim::utils::math::{Vector2, distance_torus, distance_torus_squared, wrap_position};
+mod common;
+
+use common::assert_vec2_approx;
+use predator_prey_sim::utils::math::{Vector2, distance_torus, distance_torus_squared, torus_delta, torus_mean, wrap_position};
 
 #[test]
 fn test_torus_distance_same_position() {
@@ -76,14 +79,41 @@ fn test_wrap_position_overflow_x() {
 fn test_wrap_position_corners() {
     // Top-left corner wraps to bottom-right
     let pos = Vector2::new(-5.0, -5.0);
-    let wrapped = wrap_position(pos, 100.0, 100.0);
-    assert!((wrapped.x - 95.0).abs() < 1e-10);
-    assert!((wrapped.y - 95.0).abs() < 1e-10);
+    assert_vec2_approx(wrap_position(pos, 100.0, 100.0), Vector2::new(95.0, 95.0), 1e-10);
     
     // Bottom-right corner wraps to top-left
     let pos = Vector2::new(105.0, 105.0);
-    let wrapped = wrap_position(pos, 100.0, 100.0);
-    assert!((wrapped.x - 5.0).abs() < 1e-10);
-    assert!((wrapped.y - 5.0).abs() < 1e-10);
+    assert_vec2_approx(wrap_position(pos, 100.0, 100.0), Vector2::new(5.0, 5.0), 1e-10);
+}
+
+#[test]
+fn test_torus_mean_across_seam() {
+    let points = [Vector2::new(1.0, 50.0), Vector2::new(99.0, 50.0)];
+    let mean = torus_mean(&points, 100.0, 100.0);
+
+    // The midpoint across the seam is x≈0 (or ≈100), not the center at x=50
+    let seam_distance = mean.x.min(100.0 - mean.x);
+    assert!(seam_distance < 1e-6, "Expected mean near seam, got {}", mean.x);
+    assert!((mean.y - 50.0).abs() < 1e-6);
 }
 
+#[test]
+fn test_torus_mean_matches_arithmetic_mean_away_from_seam() {
+    let points = [Vector2::new(40.0, 30.0), Vector2::new(60.0, 50.0)];
+    assert_vec2_approx(torus_mean(&points, 100.0, 100.0), Vector2::new(50.0, 40.0), 1e-6);
+}
+
+#[test]
+fn test_torus_mean_empty() {
+    assert_eq!(torus_mean(&[], 100.0, 100.0), Vector2::zero());
+}
+
+#[test]
+fn test_torus_delta_takes_shortest_way() {
+    let (a, b) = (Vector2::new(5.0, 50.0), Vector2::new(95.0, 50.0));
+    // Across the seam rather than through the middle
+    assert_vec2_approx(torus_delta(&a, &b, 100.0, 100.0), Vector2::new(-10.0, 0.0), 1e-9);
+    assert_vec2_approx(torus_delta(&b, &a, 100.0, 100.0), Vector2::new(10.0, 0.0), 1e-9);
+    // Away from the seam it is the plain difference
+    assert_vec2_approx(torus_delta(&Vector2::new(40.0, 30.0), &Vector2::new(55.0, 20.0), 100.0, 100.0), Vector2::new(15.0, -10.0), 1e-9);
+}
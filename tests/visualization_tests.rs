@@ -0,0 +1,174 @@
+// Visualization tests (need the `gui` feature)
+#![cfg(feature = "gui")]
+
+use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::world::World;
+use predator_prey_sim::simulation::agent::Agent;
+use predator_prey_sim::ui::visualization::{agent_under_cursor, dashed_segments, is_visible, statistics_lines, wrap_ghost_offsets, Camera, MAX_ZOOM, MIN_ZOOM};
+
+#[test]
+fn test_statistics_lines_use_real_energy() {
+    let params = Parameters::default();
+    let mut world = World::new(params);
+
+    // Move the averages away from the old placeholder values
+    let predator_ids: Vec<_> = world.predators().iter().map(|p| p.id()).collect();
+    for id in predator_ids {
+        world.set_agent_energy(id, 42.0);
+    }
+    let prey_ids: Vec<_> = world.prey().iter().map(|p| p.id()).collect();
+    for id in prey_ids {
+        world.set_agent_energy(id, 17.5);
+    }
+
+    let lines = statistics_lines(&world);
+    assert!(lines.counts.contains(&format!("Predators: {}", world.predator_count())));
+    assert!(lines.counts.contains(&format!("Prey: {}", world.prey_count())));
+    assert!(lines.energy.contains(&"  Predators: 42.0".to_string()));
+    assert!(lines.energy.contains(&"  Prey: 17.5".to_string()));
+    assert_eq!(lines.groups(), [lines.counts.as_slice(), lines.energy.as_slice()]);
+}
+
+#[test]
+fn test_is_visible() {
+    let rect = egui::Rect::from_min_max(egui::Pos2::new(0.0, 0.0), egui::Pos2::new(100.0, 100.0));
+
+    // On screen
+    assert!(is_visible(egui::Pos2::new(50.0, 50.0), 4.0, rect));
+    // Just off screen but the circle still overlaps the edge
+    assert!(is_visible(egui::Pos2::new(-3.0, 50.0), 4.0, rect));
+    assert!(is_visible(egui::Pos2::new(50.0, 103.0), 4.0, rect));
+    // Just off screen beyond the radius
+    assert!(!is_visible(egui::Pos2::new(-5.0, 50.0), 4.0, rect));
+    // Far off screen
+    assert!(!is_visible(egui::Pos2::new(500.0, -300.0), 4.0, rect));
+}
+
+#[test]
+fn test_dashed_segments_cover_line_with_gaps() {
+    let a = egui::Pos2::new(0.0, 0.0);
+    let b = egui::Pos2::new(25.0, 0.0);
+    let segments = dashed_segments(a, b, 8.0, 2.0);
+
+    // Dashes at 0-8, 10-18, 20-25 (last one clipped at the end point)
+    assert_eq!(segments.len(), 3);
+    assert_eq!(segments[0], [egui::Pos2::new(0.0, 0.0), egui::Pos2::new(8.0, 0.0)]);
+    assert_eq!(segments[1], [egui::Pos2::new(10.0, 0.0), egui::Pos2::new(18.0, 0.0)]);
+    assert_eq!(segments[2], [egui::Pos2::new(20.0, 0.0), egui::Pos2::new(25.0, 0.0)]);
+}
+
+#[test]
+fn test_dashed_segments_without_dash_is_solid() {
+    let a = egui::Pos2::new(0.0, 0.0);
+    let b = egui::Pos2::new(0.0, 10.0);
+    assert_eq!(dashed_segments(a, b, 0.0, 2.0), vec![[a, b]]);
+}
+
+#[test]
+fn test_agent_under_cursor_picks_nearest() {
+    let world = World::new(Parameters::default());
+    let canvas = egui::Rect::from_min_max(egui::Pos2::new(0.0, 0.0), egui::Pos2::new(400.0, 300.0));
+    let width = world.parameters().world.width;
+    let height = world.parameters().world.height;
+    let to_screen = |x: f64, y: f64| egui::Pos2::new((x / width) as f32 * 400.0, (y / height) as f32 * 300.0);
+
+    // Pointing exactly at any one of several agents picks that agent
+    for prey in world.prey().iter().take(5) {
+        let pos = prey.position();
+        assert_eq!(agent_under_cursor(&world, to_screen(pos.x, pos.y), canvas, &Camera::default(), 10.0), Some(prey.id()));
+    }
+
+    // Nothing within a zero-pixel radius of an empty corner
+    assert_eq!(agent_under_cursor(&world, egui::Pos2::new(-50.0, -50.0), canvas, &Camera::default(), 0.0), None);
+}
+
+#[test]
+fn test_camera_centers_on_world_position() {
+    use predator_prey_sim::utils::math::Vector2;
+
+    let (width, height) = (800.0, 600.0);
+    let canvas = egui::Rect::from_min_max(egui::Pos2::new(10.0, 20.0), egui::Pos2::new(410.0, 320.0));
+    let target = Vector2::new(700.0, 50.0);
+
+    let camera = Camera::centered_on(target, width, height);
+    assert_eq!(camera.offset, Vector2::new(-300.0, 250.0));
+    assert_eq!(camera.world_to_screen(target, canvas, width, height), canvas.center());
+
+    // Just across the seam the target still lands on the center: no jump
+    let wrapped = Vector2::new(target.x + width, target.y);
+    assert_eq!(camera.world_to_screen(wrapped, canvas, width, height), canvas.center());
+
+    // Picking maps screen positions back through the inverse transform
+    let view = camera.to_view(Vector2::new(10.0, 10.0), width, height);
+    assert_eq!(camera.to_world(view, width, height), Vector2::new(10.0, 10.0));
+}
+
+#[test]
+fn test_camera_zoom_keeps_cursor_point_fixed() {
+    let (width, height) = (800.0, 600.0);
+    let canvas = egui::Rect::from_min_max(egui::Pos2::new(0.0, 0.0), egui::Pos2::new(400.0, 300.0));
+    let cursor = egui::Pos2::new(100.0, 80.0);
+
+    let mut camera = Camera::default();
+    let before = camera.screen_to_world(cursor, canvas, width, height);
+    camera.zoom_at(cursor, 3.0, canvas, width, height);
+    assert_eq!(camera.zoom, 3.0);
+    let after = camera.screen_to_world(cursor, canvas, width, height);
+    assert!((before.x - after.x).abs() < 1e-3 && (before.y - after.y).abs() < 1e-3);
+
+    // Zoom is clamped to the allowed range
+    camera.zoom_at(cursor, 100.0, canvas, width, height);
+    assert_eq!(camera.zoom, MAX_ZOOM);
+    camera.zoom_at(cursor, 0.0001, canvas, width, height);
+    assert_eq!(camera.zoom, MIN_ZOOM);
+}
+
+#[test]
+fn test_camera_screen_to_world_inverts_world_to_screen() {
+    use predator_prey_sim::utils::math::Vector2;
+
+    let (width, height) = (800.0, 600.0);
+    let canvas = egui::Rect::from_min_max(egui::Pos2::new(10.0, 20.0), egui::Pos2::new(410.0, 320.0));
+    let mut camera = Camera::default();
+    camera.zoom_at(egui::Pos2::new(300.0, 100.0), 2.5, canvas, width, height);
+    camera.pan(egui::Vec2::new(-40.0, 25.0), canvas, width, height);
+
+    let target = Vector2::new(420.0, 310.0);
+    let screen = camera.world_to_screen(target, canvas, width, height);
+    let back = camera.screen_to_world(screen, canvas, width, height);
+    assert!((back.x - target.x).abs() < 1e-2 && (back.y - target.y).abs() < 1e-2);
+}
+
+#[test]
+fn test_agent_under_cursor_when_zoomed() {
+    let world = World::new(Parameters::default());
+    let canvas = egui::Rect::from_min_max(egui::Pos2::new(0.0, 0.0), egui::Pos2::new(400.0, 300.0));
+    let (width, height) = (world.parameters().world.width, world.parameters().world.height);
+    let mut camera = Camera::default();
+    camera.zoom_at(canvas.center(), 4.0, canvas, width, height);
+
+    let prey = &world.prey()[0];
+    camera.center_on(prey.position(), width, height);
+    assert_eq!(agent_under_cursor(&world, canvas.center(), canvas, &camera, 10.0), Some(prey.id()));
+}
+
+#[test]
+fn test_wrap_ghost_offsets_near_seams() {
+    let view = egui::Rect::from_min_max(egui::Pos2::new(0.0, 0.0), egui::Pos2::new(400.0, 300.0));
+
+    // Well inside: only the agent itself
+    assert_eq!(wrap_ghost_offsets(egui::Pos2::new(200.0, 150.0), 4.0, view), vec![egui::Vec2::ZERO]);
+
+    // Over the left edge: a ghost one world width to the right
+    assert_eq!(
+        wrap_ghost_offsets(egui::Pos2::new(2.0, 150.0), 4.0, view),
+        vec![egui::Vec2::ZERO, egui::Vec2::new(400.0, 0.0)]
+    );
+
+    // Over the bottom-right corner: ghosts to the left, above, and diagonally
+    let offsets = wrap_ghost_offsets(egui::Pos2::new(398.0, 299.0), 4.0, view);
+    assert_eq!(offsets.len(), 4);
+    for expected in [egui::Vec2::new(-400.0, 0.0), egui::Vec2::new(0.0, -300.0), egui::Vec2::new(-400.0, -300.0)] {
+        assert!(offsets.contains(&expected), "{:?}", offsets);
+    }
+}
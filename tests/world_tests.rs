@@ -1,7 +1,12 @@
 // World tests
 
+mod common;
+
 use predator_prey_sim::simulation::world::World;
 use predator_prey_sim::config::parameters::Parameters;
+use predator_prey_sim::simulation::agent::{Agent, AgentId, AgentType};
+use predator_prey_sim::simulation::prey::InfectionState;
+use predator_prey_sim::simulation::scent::ScentField;
 
 #[test]
 fn test_world_creation() {
@@ -78,3 +83,1028 @@ fn test_world_max_agents_limit() {
     assert!(world.total_agents() <= 10);
 }
 
+#[test]
+fn test_max_agents_removals_are_reported_as_deaths() {
+    use predator_prey_sim::simulation::world::SimEvent;
+    use std::sync::{Arc, Mutex};
+
+    let mut params = Parameters::default();
+    params.simulation.enable_reproduction = false;
+    params.predator.initial_count = 5;
+    params.prey.initial_count = 5;
+    params.simulation.max_agents = 4;
+    let mut world = World::new(params);
+
+    let before: Vec<AgentId> = world.predators().iter().map(|p| p.id()).chain(world.prey().iter().map(|p| p.id())).collect();
+    let deaths = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&deaths);
+    world.set_observer(move |event| {
+        if let SimEvent::Died { id, .. } = event {
+            sink.lock().unwrap().push(*id);
+        }
+    });
+    world.update();
+
+    assert_eq!(world.total_agents(), 4);
+    let mut gone: Vec<AgentId> = before.into_iter().filter(|id| world.agent(*id).is_none()).collect();
+    let mut deaths = deaths.lock().unwrap().clone();
+    gone.sort_by_key(|id| id.0);
+    deaths.sort_by_key(|id| id.0);
+    assert_eq!(deaths, gone);
+}
+
+
+#[test]
+fn test_kill_agent() {
+    let params = Parameters::default();
+    let mut world = World::new(params);
+
+    let predator_id = world.predators()[0].id();
+    let prey_id = world.prey()[0].id();
+    let initial_predators = world.predator_count();
+    let initial_prey = world.prey_count();
+
+    assert!(world.kill_agent(predator_id));
+    assert!(world.kill_agent(prey_id));
+    assert_eq!(world.predator_count(), initial_predators - 1);
+    assert_eq!(world.prey_count(), initial_prey - 1);
+
+    // Killing an unknown id does nothing
+    assert!(!world.kill_agent(predator_id));
+    assert_eq!(world.predator_count(), initial_predators - 1);
+}
+
+#[test]
+fn test_set_agent_energy() {
+    let params = Parameters::default();
+    let mut world = World::new(params);
+
+    let prey_id = world.prey()[0].id();
+    assert!(world.set_agent_energy(prey_id, 250.0));
+    assert_eq!(world.prey()[0].energy(), 250.0);
+
+    let predator_id = world.predators()[0].id();
+    assert!(world.set_agent_energy(predator_id, 1.5));
+    assert_eq!(world.predators()[0].energy(), 1.5);
+
+    assert!(!world.set_agent_energy(AgentId(9999), 10.0));
+}
+
+#[test]
+fn test_force_reproduce() {
+    let params = Parameters::default();
+    let mut world = World::new(params);
+
+    let predator_id = world.predators()[0].id();
+    let initial_predators = world.predator_count();
+    let parent_energy = world.predators()[0].energy();
+
+    let child_id = world.force_reproduce(predator_id).expect("offspring should spawn");
+    assert_eq!(world.predator_count(), initial_predators + 1);
+    assert_eq!(world.agent_type_of(child_id), Some(AgentType::Predator));
+    assert_eq!(world.predators()[0].energy(), parent_energy);
+
+    let prey_id = world.prey()[0].id();
+    let initial_prey = world.prey_count();
+    world.force_reproduce(prey_id).expect("offspring should spawn");
+    assert_eq!(world.prey_count(), initial_prey + 1);
+}
+
+#[test]
+fn test_structured_offspring_id_is_one_generation_past_parent() {
+    use predator_prey_sim::simulation::world::SimEvent;
+
+    let mut params = Parameters::default();
+    params.simulation.structured_ids = true;
+    let mut world = World::new(params.clone());
+
+    let parent_id = world.predators()[0].id();
+    assert_eq!(parent_id.agent_type(), Some(AgentType::Predator));
+    assert_eq!(parent_id.generation(), 0);
+
+    let child_id = world.force_reproduce(parent_id).unwrap();
+    assert_eq!(child_id.agent_type(), Some(AgentType::Predator));
+    assert_eq!(child_id.generation(), 1);
+    let grandchild_id = world.force_reproduce(child_id).unwrap();
+    assert_eq!(grandchild_id.generation(), 2);
+    assert_ne!(grandchild_id.index(), child_id.index());
+
+    // Births during a tick follow the same rule
+    let births = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let sink = births.clone();
+    world.set_observer(Box::new(move |event: &SimEvent| {
+        if let SimEvent::Born { id, parent_id, .. } = event {
+            sink.lock().unwrap().push((*id, *parent_id));
+        }
+    }));
+    let prey_id = world.prey()[0].id();
+    world.set_agent_energy(prey_id, params.prey.reproduction_threshold + 50.0);
+    world.update();
+
+    let births = births.lock().unwrap();
+    assert!(!births.is_empty());
+    for (id, parent_id) in births.iter() {
+        assert_eq!(id.agent_type(), parent_id.agent_type());
+        assert_eq!(id.generation(), parent_id.generation() + 1);
+    }
+}
+
+#[test]
+fn test_force_reproduce_respects_max_agents() {
+    let mut params = Parameters::default();
+    params.simulation.max_agents = 20;
+    params.predator.initial_count = 10;
+    params.prey.initial_count = 10;
+    let mut world = World::new(params);
+
+    let predator_id = world.predators()[0].id();
+    assert!(world.force_reproduce(predator_id).is_none());
+    assert_eq!(world.total_agents(), 20);
+}
+
+#[test]
+fn test_view_reports_neighbor_velocity() {
+    let mut params = Parameters::default();
+    params.predator.initial_count = 1;
+    params.predator.perception_radius = 2000.0;
+    params.prey.initial_count = 5;
+    params.prey.detection_radius = 2000.0;
+    params.prey.flee_distance = 2000.0;
+    params.simulation.enable_reproduction = false;
+    let mut world = World::new(params);
+    world.update();
+
+    let predator_id = world.predators()[0].id();
+    let view = world.view_of(predator_id).expect("predator should be present");
+    assert_eq!(view.nearby_prey.len(), world.prey_count());
+    for neighbor in &view.nearby_prey {
+        let prey = world.prey().iter().find(|p| p.id() == neighbor.id).unwrap();
+        assert_eq!(neighbor.velocity, prey.velocity());
+        assert!(neighbor.velocity.magnitude() > 0.0);
+    }
+
+    assert!(world.view_of(AgentId(9999)).is_none());
+}
+
+#[test]
+fn test_update_matches_freshly_built_views() {
+    let mut params = Parameters::default();
+    params.simulation.enable_reproduction = false;
+    let mut world = World::new(params);
+    world.update();
+
+    // Update copies of the agents against freshly allocated views
+    let mut predators: Vec<_> = world.predators().to_vec();
+    for predator in &mut predators {
+        let view = world.view_of(predator.id()).unwrap();
+        predator.update(&view);
+    }
+    let mut prey: Vec<_> = world.prey().to_vec();
+    for p in &mut prey {
+        let view = world.view_of(p.id()).unwrap();
+        p.update(&view);
+    }
+
+    // The world's own (buffer-reusing) update must agree for every survivor
+    world.update();
+    for expected in &predators {
+        if let Some(actual) = world.predators().iter().find(|p| p.id() == expected.id()) {
+            assert_eq!(actual.position(), expected.position());
+            assert_eq!(actual.energy(), expected.energy());
+        }
+    }
+    for expected in &prey {
+        if let Some(actual) = world.prey().iter().find(|p| p.id() == expected.id()) {
+            assert_eq!(actual.position(), expected.position());
+            assert_eq!(actual.energy(), expected.energy());
+        }
+    }
+}
+
+#[test]
+fn test_infection_spreads_among_prey() {
+    let mut params = Parameters::default();
+    params.world.width = 60.0;
+    params.world.height = 60.0;
+    params.predator.initial_count = 0;
+    params.prey.initial_count = 30;
+    params.prey.infection_enabled = true;
+    params.prey.infection_radius = 100.0;
+    params.prey.infection_rate = 0.5;
+    params.simulation.enable_reproduction = false;
+    let mut world = World::new(params);
+
+    let patient_zero = world.prey()[0].id();
+    assert!(world.infect_prey(patient_zero));
+    assert!(!world.infect_prey(patient_zero));
+
+    for _ in 0..5 {
+        world.update();
+    }
+
+    let ever_infected = world
+        .prey()
+        .iter()
+        .filter(|p| p.infection() != InfectionState::Susceptible)
+        .count();
+    assert!(ever_infected > 1);
+}
+
+#[test]
+fn test_frozen_predators_stay_put_while_prey_move() {
+    let mut params = Parameters::default();
+    params.prey.flee_distance = 2000.0;
+    params.prey.detection_radius = 2000.0;
+    params.simulation.enable_reproduction = false;
+    let mut world = World::new(params);
+    world.set_frozen(AgentType::Predator, true);
+    assert!(world.is_frozen(AgentType::Predator));
+
+    let predator_positions: Vec<_> = world.predators().iter().map(|p| (p.id(), p.position())).collect();
+    let prey_positions: Vec<_> = world.prey().iter().map(|p| (p.id(), p.position())).collect();
+    for _ in 0..5 {
+        world.update();
+    }
+
+    assert_eq!(world.predator_count(), predator_positions.len());
+    for predator in world.predators() {
+        let (_, before) = predator_positions.iter().find(|(id, _)| *id == predator.id()).unwrap();
+        assert_eq!(predator.position(), *before);
+    }
+    let moved = world
+        .prey()
+        .iter()
+        .filter(|p| prey_positions.iter().any(|(id, before)| *id == p.id() && *before != p.position()))
+        .count();
+    assert!(moved > 0);
+}
+
+#[test]
+fn test_food_spawns_when_enabled() {
+    let mut params = Parameters::default();
+    params.world.enable_food = true;
+    params.world.food_spawn_rate = 0.1;
+    let mut world = World::new(params);
+    world.update();
+    assert!(!world.food().is_empty());
+
+    world.reset();
+    assert!(world.food().is_empty());
+}
+
+#[test]
+fn test_sensor_noise_varies_pursuit_heading() {
+    let mut params = Parameters::default();
+    params.predator.initial_count = 1;
+    params.predator.perception_radius = 2000.0;
+    params.prey.initial_count = 1;
+    params.simulation.enable_reproduction = false;
+    params.simulation.sensor_noise = 5.0;
+    let world = World::new(params);
+    let predator = world.predators()[0].clone();
+
+    // The prey stays put; only the predator's perception of it changes
+    let headings: Vec<f64> = (0..20)
+        .map(|_| {
+            let mut copy = predator.clone();
+            copy.update(&world.view_of(copy.id()).unwrap());
+            copy.velocity().y.atan2(copy.velocity().x)
+        })
+        .collect();
+    let mean = headings.iter().sum::<f64>() / headings.len() as f64;
+    let variance = headings.iter().map(|h| (h - mean).powi(2)).sum::<f64>() / headings.len() as f64;
+    assert!(variance > 1e-6);
+}
+
+#[test]
+fn test_offspring_energy_is_capped() {
+    let mut params = Parameters::default();
+    params.predator.initial_count = 0;
+    params.prey.initial_count = 10;
+    params.prey.initial_energy = 200.0;
+    params.prey.reproduction_threshold = 100.0;
+    params.simulation.max_offspring_energy = 50.0;
+    let mut world = World::new(params);
+    let parent_ids: Vec<_> = world.prey().iter().map(|p| p.id()).collect();
+
+    // Parents would grant their full initial energy; the cap wins
+    world.update();
+    let children: Vec<_> = world.prey().iter().filter(|p| !parent_ids.contains(&p.id())).collect();
+    assert!(!children.is_empty());
+    for child in children {
+        assert!(child.energy() <= 50.0);
+    }
+
+    let child_id = world.force_reproduce(parent_ids[0]).unwrap();
+    let child = world.prey().iter().find(|p| p.id() == child_id).unwrap();
+    assert!(child.energy() <= 50.0);
+}
+
+#[test]
+fn test_auto_balance_raises_regeneration_when_prey_scarce() {
+    let mut params = Parameters::default();
+    params.predator.initial_count = 0;
+    params.prey.initial_count = 5;
+    params.simulation.enable_reproduction = false;
+    params.simulation.auto_balance.enabled = true;
+    params.simulation.auto_balance.target_prey = 200;
+    params.simulation.auto_balance.gain = 0.05;
+    let base_rate = params.prey.energy_regeneration;
+    let mut world = World::new(params);
+
+    let mut last_rate = base_rate;
+    for _ in 0..10 {
+        world.update();
+        let rate = world.prey()[0].energy_regeneration();
+        assert!(rate > last_rate);
+        last_rate = rate;
+    }
+    assert!(world.regeneration_factor() > 1.0);
+}
+
+#[test]
+fn test_disabling_auto_balance_restores_base_regeneration() {
+    let mut params = Parameters::default();
+    params.predator.initial_count = 0;
+    params.prey.initial_count = 5;
+    params.simulation.enable_reproduction = false;
+    params.simulation.auto_balance.enabled = true;
+    params.simulation.auto_balance.target_prey = 200;
+    params.simulation.auto_balance.gain = 0.05;
+    let base_rate = params.prey.energy_regeneration;
+    let mut world = World::new(params.clone());
+    for _ in 0..10 {
+        world.update();
+    }
+    assert!(world.prey()[0].energy_regeneration() > base_rate);
+
+    params.simulation.auto_balance.enabled = false;
+    world.update_parameters(params);
+    world.update();
+    assert_eq!(world.regeneration_factor(), 1.0);
+    assert!(world.prey().iter().all(|prey| prey.energy_regeneration() == base_rate));
+}
+
+/// Run one tick with a predator right next to a target prey, with `crowd`
+/// more prey bunched around the target; return whether any prey was eaten
+fn capture_with_crowd(crowd: usize, seed: u64) -> bool {
+    use predator_prey_sim::simulation::predator::Predator;
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.predator.dilution_factor = 1.0;
+    params.simulation.enable_reproduction = false;
+    params.simulation.seed = Some(seed);
+
+    let predator = Predator::new(AgentId(1), Vector2::new(100.0, 100.0), params.predator.clone());
+    let mut prey = vec![Prey::new(AgentId(2), Vector2::new(101.0, 100.0), params.prey.clone())];
+    for i in 0..crowd {
+        let offset = Vector2::new(8.0 + i as f64, 4.0);
+        prey.push(Prey::new(AgentId(3 + i as u64), Vector2::new(101.0, 100.0).add(&offset), params.prey.clone()));
+    }
+    let count = prey.len();
+    let mut world = World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params,
+        predators: vec![predator],
+        prey,
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: false,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    });
+    world.update();
+    world.prey_count() < count
+}
+
+#[test]
+fn test_dilution_protects_prey_in_groups() {
+    let trials = 200;
+    let isolated = (0..trials).filter(|&seed| capture_with_crowd(0, seed)).count();
+    let grouped = (0..trials).filter(|&seed| capture_with_crowd(9, seed)).count();
+
+    assert_eq!(isolated, trials as usize);
+    assert!(grouped < isolated / 2, "{} grouped vs {} isolated captures", grouped, isolated);
+}
+
+#[test]
+fn test_neighbors_of_matches_perception_radius() {
+    use predator_prey_sim::utils::math::distance_torus;
+
+    let world = World::new(Parameters::default());
+    let params = world.parameters();
+    let (width, height) = (params.world.width, params.world.height);
+
+    let predator = &world.predators()[0];
+    let prey = &world.prey()[0];
+    let cases = [
+        (predator.id(), predator.position(), predator.perception_radius()),
+        (prey.id(), prey.position(), params.prey.detection_radius),
+    ];
+    for (id, position, radius) in cases {
+        let mut expected: Vec<AgentId> = world
+            .predators()
+            .iter()
+            .map(|p| (p.id(), p.position()))
+            .chain(world.prey().iter().map(|p| (p.id(), p.position())))
+            .filter(|(other, pos)| *other != id && distance_torus(&position, pos, width, height) <= radius)
+            .map(|(other, _)| other)
+            .collect();
+        let mut actual: Vec<AgentId> = world.neighbors_of(id).iter().map(|(other, _)| *other).collect();
+        expected.sort_unstable();
+        actual.sort_unstable();
+        assert_eq!(actual, expected);
+    }
+
+    assert!(world.neighbors_of(AgentId(9999)).is_empty());
+}
+
+#[test]
+fn test_time_zone_scales_movement() {
+    use predator_prey_sim::config::parameters::TimeZone;
+    use predator_prey_sim::simulation::predator::Predator;
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.simulation.enable_reproduction = false;
+    params.world.time_zones = vec![TimeZone::new(0.0, 0.0, 200.0, 200.0, 0.5)];
+
+    // Two identical chases, one inside the slow zone and one far outside it
+    let slow_start = Vector2::new(100.0, 100.0);
+    let normal_start = Vector2::new(500.0, 400.0);
+    let mut world = World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params: params.clone(),
+        predators: vec![
+            Predator::new(AgentId(1), slow_start, params.predator.clone()),
+            Predator::new(AgentId(2), normal_start, params.predator.clone()),
+        ],
+        prey: vec![
+            Prey::new(AgentId(3), Vector2::new(130.0, 100.0), params.prey.clone()),
+            Prey::new(AgentId(4), Vector2::new(530.0, 400.0), params.prey.clone()),
+        ],
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: false,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    });
+    world.update();
+
+    let slow = world.agent(AgentId(1)).unwrap().position().subtract(&slow_start).magnitude();
+    let normal = world.agent(AgentId(2)).unwrap().position().subtract(&normal_start).magnitude();
+    assert!(normal > 0.0);
+    assert!((slow - 0.5 * normal).abs() < 1e-9, "{} vs {}", slow, normal);
+}
+
+#[test]
+fn test_max_births_per_tick_limits_births() {
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.simulation.max_births_per_tick = 2;
+
+    // Ten well-separated prey, all ready to reproduce this tick
+    let prey: Vec<Prey> = (0..10)
+        .map(|i| {
+            let mut prey = Prey::new(AgentId(i), Vector2::new(40.0 + 70.0 * i as f64, 300.0), params.prey.clone());
+            prey.set_energy(params.prey.reproduction_threshold + 50.0);
+            prey
+        })
+        .collect();
+    let mut world = World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params,
+        predators: Vec::new(),
+        prey,
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: false,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    });
+    world.update();
+
+    assert_eq!(world.prey_count(), 12);
+}
+
+/// World with one predator and one prey `distance` apart on the x axis
+fn predator_and_prey(distance: f64) -> World {
+    use predator_prey_sim::simulation::predator::Predator;
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let params = Parameters::default();
+    World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params: params.clone(),
+        predators: vec![Predator::new(AgentId(1), Vector2::new(100.0, 100.0), params.predator.clone())],
+        prey: vec![Prey::new(AgentId(2), Vector2::new(100.0 + distance, 100.0), params.prey.clone())],
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: false,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    })
+}
+
+#[test]
+fn test_perception_edges_in_and_out_of_range() {
+    let mut edges = predator_and_prey(30.0).perception_edges();
+    edges.sort_unstable();
+    assert_eq!(edges, vec![(AgentId(1), AgentId(2)), (AgentId(2), AgentId(1))]);
+
+    assert!(predator_and_prey(200.0).perception_edges().is_empty());
+}
+
+#[test]
+fn test_average_prey_speed_is_mean_velocity_magnitude() {
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let params = Parameters::default();
+    let velocities = [Vector2::new(0.0, 0.0), Vector2::new(1.2, -1.6), Vector2::new(0.0, 1.0)];
+    let prey: Vec<Prey> = velocities
+        .iter()
+        .enumerate()
+        .map(|(i, velocity)| {
+            let mut prey = Prey::new(AgentId(i as u64 + 1), Vector2::new(100.0 * (i + 1) as f64, 100.0), params.prey.clone());
+            prey.set_velocity(*velocity);
+            prey
+        })
+        .collect();
+    let world = World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params,
+        predators: Vec::new(),
+        prey,
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: false,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    });
+
+    assert!((world.average_prey_speed() - (0.0 + 2.0 + 1.0) / 3.0).abs() < 1e-12);
+    assert_eq!(world.average_predator_speed(), 0.0);
+}
+
+#[test]
+fn test_max_predators_caps_predator_reproduction() {
+    let mut params = Parameters::default();
+    params.simulation.max_predators = 5;
+    params.predator.initial_count = 3;
+    params.prey.initial_count = 0;
+    params.predator.energy_per_tick = 0.0;
+    let mut world = World::new(params.clone());
+
+    // Keep every predator ready to reproduce
+    for _ in 0..20 {
+        let ids: Vec<AgentId> = world.predators().iter().map(|p| p.id()).collect();
+        for id in ids {
+            world.set_agent_energy(id, params.predator.reproduction_threshold + 100.0);
+        }
+        world.update();
+        assert!(world.predator_count() <= 5);
+    }
+    assert_eq!(world.predator_count(), 5);
+    assert!(world.total_agents() < params.simulation.max_agents as usize);
+
+    // Spawning obeys the same cap
+    assert_eq!(world.spawn_predators(3), 0);
+}
+
+#[test]
+fn test_cold_side_of_temperature_gradient_slows_agents() {
+    use predator_prey_sim::config::parameters::TemperatureGradient;
+    use predator_prey_sim::simulation::predator::Predator;
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.simulation.enable_reproduction = false;
+    params.world.temperature_gradient = Some(TemperatureGradient::default());
+
+    // Two identical chases, one near the cold left edge and one near the warm right edge
+    let cold_start = Vector2::new(50.0, 300.0);
+    let warm_start = Vector2::new(720.0, 300.0);
+    let mut world = World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params: params.clone(),
+        predators: vec![
+            Predator::new(AgentId(1), cold_start, params.predator.clone()),
+            Predator::new(AgentId(2), warm_start, params.predator.clone()),
+        ],
+        prey: vec![
+            Prey::new(AgentId(3), Vector2::new(80.0, 300.0), params.prey.clone()),
+            Prey::new(AgentId(4), Vector2::new(750.0, 300.0), params.prey.clone()),
+        ],
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: true,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    });
+    world.update();
+
+    let cold = world.agent(AgentId(1)).unwrap().position().subtract(&cold_start).magnitude();
+    let warm = world.agent(AgentId(2)).unwrap().position().subtract(&warm_start).magnitude();
+    assert!(cold > 0.0);
+    assert!(cold < warm * 0.5, "cold {} vs warm {}", cold, warm);
+}
+
+#[test]
+fn test_observer_receives_consumption_event() {
+    use predator_prey_sim::simulation::world::SimEvent;
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut world = predator_and_prey(2.0);
+    let sink = Arc::clone(&events);
+    world.set_observer(move |event| sink.lock().unwrap().push(*event));
+    world.update();
+
+    assert_eq!(world.prey_count(), 0);
+    let events = events.lock().unwrap();
+    assert!(events.contains(&SimEvent::Consumed { predator_id: AgentId(1), prey_id: AgentId(2) }));
+}
+
+#[test]
+fn test_observer_reports_each_consumed_prey_once_per_tick() {
+    use predator_prey_sim::simulation::world::SimEvent;
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut world = contested_prey(5, true);
+    let sink = Arc::clone(&events);
+    world.set_observer(move |event| sink.lock().unwrap().push(*event));
+    world.update();
+
+    let events = events.lock().unwrap();
+    let consumed: Vec<AgentId> = events
+        .iter()
+        .filter_map(|event| match event {
+            SimEvent::Consumed { prey_id, .. } => Some(*prey_id),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(consumed, vec![AgentId(3)]);
+}
+
+/// Two predators either side of one prey, both within capture distance
+fn contested_prey(seed: u64, shuffle: bool) -> World {
+    use predator_prey_sim::simulation::predator::Predator;
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(seed);
+    params.simulation.shuffle_update_order = shuffle;
+    params.simulation.enable_reproduction = false;
+    // Learning on, so a capture shows up in the perception radius too
+    params.predator.learning_rate = 0.05;
+    World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params: params.clone(),
+        predators: vec![
+            Predator::new(AgentId(1), Vector2::new(98.0, 100.0), params.predator.clone()),
+            Predator::new(AgentId(2), Vector2::new(102.0, 100.0), params.predator.clone()),
+        ],
+        prey: vec![Prey::new(AgentId(3), Vector2::new(100.0, 100.0), params.prey.clone())],
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: false,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    })
+}
+
+#[test]
+fn test_contested_prey_goes_to_one_predator() {
+    use predator_prey_sim::simulation::world::SimEvent;
+    use std::sync::{Arc, Mutex};
+
+    let initial_energy = Parameters::default().predator.initial_energy;
+    let initial_perception = Parameters::default().predator.perception_radius;
+    let mut winners = Vec::new();
+    for seed in 0..16 {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut world = contested_prey(seed, true);
+        let sink = Arc::clone(&events);
+        world.set_observer(move |event| sink.lock().unwrap().push(*event));
+        world.update();
+
+        assert_eq!(world.prey_count(), 0);
+        let consumed: Vec<AgentId> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                SimEvent::Consumed { predator_id, .. } => Some(*predator_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(consumed.len(), 1, "seed {}: {:?}", seed, consumed);
+
+        // Only the winner keeps the energy of the meal
+        let fed: Vec<AgentId> = world.predators().iter().filter(|p| p.energy() > initial_energy).map(|p| p.id()).collect();
+        assert_eq!(fed, consumed, "seed {}", seed);
+        // ...and the learning boost: the loser's perception is unchanged
+        for predator in world.predators() {
+            if predator.id() == consumed[0] {
+                assert!(predator.perception_radius() > initial_perception, "seed {}", seed);
+            } else {
+                assert_eq!(predator.perception_radius(), initial_perception, "seed {}", seed);
+            }
+        }
+        winners.push(consumed[0]);
+    }
+
+    // The shuffled resolution order decides the winner, so both get a turn
+    assert!(winners.contains(&AgentId(1)) && winners.contains(&AgentId(2)), "{:?}", winners);
+}
+
+/// World with predators at the given positions and no prey
+fn predators_at(positions: &[(f64, f64)], boundary_type: predator_prey_sim::config::parameters::BoundaryType) -> World {
+    use predator_prey_sim::simulation::predator::Predator;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.world.boundary_type = boundary_type;
+    let predators = positions
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, y))| Predator::new(AgentId(i as u64 + 1), Vector2::new(x, y), params.predator.clone()))
+        .collect();
+    World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params,
+        predators,
+        prey: Vec::new(),
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: false,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    })
+}
+
+#[test]
+fn test_nearest_predator_for_world_point() {
+    use predator_prey_sim::config::parameters::BoundaryType;
+    use predator_prey_sim::utils::math::Vector2;
+
+    // 800 x 600 world, predators 1 at (100, 300) and 2 at (500, 300)
+    let walls = predators_at(&[(100.0, 300.0), (500.0, 300.0)], BoundaryType::Walls);
+    assert_eq!(walls.nearest_predator(Vector2::new(250.0, 300.0)), Some(AgentId(1)));
+    assert_eq!(walls.nearest_predator(Vector2::new(350.0, 100.0)), Some(AgentId(2)));
+    assert_eq!(walls.nearest_predator(Vector2::new(780.0, 300.0)), Some(AgentId(2)));
+    // Equidistant: the lower id wins
+    assert_eq!(walls.nearest_predator(Vector2::new(300.0, 300.0)), Some(AgentId(1)));
+
+    // Across the seam, (780, 300) is only 120 from predator 1
+    let torus = predators_at(&[(100.0, 300.0), (500.0, 300.0)], BoundaryType::Wraparound);
+    assert_eq!(torus.nearest_predator(Vector2::new(780.0, 300.0)), Some(AgentId(1)));
+
+    assert_eq!(predators_at(&[], BoundaryType::Walls).nearest_predator(Vector2::new(1.0, 1.0)), None);
+
+    let territories = walls.predator_territories(4, 3);
+    assert_eq!(territories.len(), 12);
+    assert_eq!(territories[0], Some(AgentId(1)));
+    assert_eq!(territories[3], Some(AgentId(2)));
+}
+
+#[test]
+fn test_predator_leaves_decaying_scent_trail() {
+    use predator_prey_sim::config::parameters::ScentTrails;
+    use predator_prey_sim::simulation::predator::Predator;
+    use predator_prey_sim::simulation::prey::Prey;
+    use predator_prey_sim::simulation::snapshot::WorldSnapshot;
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.simulation.tick_rate = 1.0;
+    params.simulation.update_dt();
+    params.world.scent_trails = Some(ScentTrails { cell_size: 5.0, retention: 0.9, ..ScentTrails::default() });
+
+    // A predator chasing a motionless prey walks a straight path toward it
+    let mut world = World::from_snapshot(WorldSnapshot {
+        tick: 0,
+        next_id: 100,
+        params: params.clone(),
+        predators: vec![Predator::new(AgentId(1), Vector2::new(100.0, 100.0), params.predator.clone())],
+        prey: vec![Prey::new(AgentId(2), Vector2::new(145.0, 100.0), params.prey.clone())],
+        alarms: Vec::new(),
+        food: Vec::new(),
+        rng: None,
+        freeze_predators: false,
+        freeze_prey: true,
+        regeneration_factor: 1.0,
+        generated_obstacles: Vec::new(),
+        scent: ScentField::default(),
+    });
+    let mut path = Vec::new();
+    for _ in 0..10 {
+        world.update();
+        path.push(world.predators()[0].position());
+    }
+    assert!(path[9].x - path[0].x > 10.0, "predator barely moved: {:?}", path);
+
+    let trail: Vec<f64> = path.iter().map(|&pos| world.scent().at(pos)).collect();
+    assert!(trail.iter().all(|&scent| scent > 0.0), "trail {:?}", trail);
+    assert_eq!(world.scent().at(Vector2::new(100.0, 300.0)), 0.0);
+
+    // With the predator gone, every cell on the path fades at the retention rate
+    world.kill_agent(AgentId(1));
+    for _ in 0..5 {
+        world.update();
+    }
+    for (pos, before) in path.iter().zip(&trail) {
+        common::assert_f64_approx(world.scent().at(*pos), before * 0.9_f64.powi(5), 1e-9);
+    }
+}
+
+#[test]
+fn test_world_json_round_trip() {
+    let mut params = Parameters::default();
+    params.simulation.seed = Some(4);
+    params.predator.initial_count = 10;
+    params.prey.initial_count = 40;
+    let mut world = World::new(params);
+    assert_eq!(world.total_agents(), 50);
+    // Let the agents move and age so velocities and ages are not all defaults
+    for _ in 0..20 {
+        world.update();
+    }
+
+    let restored = World::from_json(&world.to_json().unwrap()).unwrap();
+    assert_eq!(restored.tick(), world.tick());
+    assert_eq!(restored.state_hash(), world.state_hash());
+    let agents = |world: &World| {
+        let predators = world.predators().iter().map(|p| (p.id(), p.position(), p.velocity(), p.energy(), p.age()));
+        predators.chain(world.prey().iter().map(|p| (p.id(), p.position(), p.velocity(), p.energy(), p.age()))).collect::<Vec<_>>()
+    };
+    assert_eq!(agents(&restored), agents(&world));
+
+    // The id counter survives, so new agents get fresh ids
+    let mut restored = restored;
+    restored.spawn_prey(1);
+    let newest = restored.prey().last().unwrap().id();
+    assert!(agents(&world).iter().all(|agent| agent.0 < newest));
+
+    assert!(World::from_json("{not json").is_err());
+}
+
+#[test]
+fn test_offspring_start_with_configured_offspring_energy() {
+    let mut params = Parameters::default();
+    params.predator.initial_count = 1;
+    params.prey.initial_count = 1;
+    params.predator.offspring_energy = Some(40.0);
+    params.prey.offspring_energy = Some(25.0);
+    assert_ne!(params.prey.offspring_energy, Some(params.prey.initial_energy));
+    let mut world = World::new(params.clone());
+
+    // A birth during a tick
+    let prey_id = world.prey()[0].id();
+    world.set_agent_energy(prey_id, params.prey.reproduction_threshold + 50.0);
+    world.update();
+    assert_eq!(world.prey_count(), 2);
+    assert_eq!(world.prey()[1].energy(), 25.0);
+
+    // A forced birth
+    let predator_id = world.predators()[0].id();
+    let child_id = world.force_reproduce(predator_id).unwrap();
+    let child = world.predators().iter().find(|p| p.id() == child_id).unwrap();
+    assert_eq!(child.energy(), 40.0);
+
+    // Unset, newborns start with initial_energy as before
+    params.prey.offspring_energy = None;
+    assert_eq!(params.prey.newborn_energy(), params.prey.initial_energy);
+}
+
+#[test]
+fn test_agents_die_at_max_age_regardless_of_energy() {
+    let mut params = Parameters::default();
+    params.simulation.enable_reproduction = false;
+    params.predator.max_age = Some(10);
+    params.prey.max_age = Some(10);
+    params.predator.initial_energy = 10_000.0;
+    params.prey.initial_energy = 10_000.0;
+
+    // One species at a time, so no prey is eaten before it can die of age
+    for (predators, prey) in [(5, 0), (0, 5)] {
+        params.predator.initial_count = predators;
+        params.prey.initial_count = prey;
+        let mut world = World::new(params.clone());
+        for _ in 0..9 {
+            world.update();
+        }
+        assert_eq!(world.total_agents(), 5);
+        assert!(world.predators().iter().map(|p| p.energy()).chain(world.prey().iter().map(|p| p.energy())).all(|e| e > 9_000.0));
+
+        world.update();
+        assert_eq!(world.total_agents(), 0, "agents outlived max_age");
+    }
+}
+
+#[test]
+fn test_agent_at_picks_nearest_within_radius() {
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.predator.initial_count = 0;
+    params.prey.initial_count = 1;
+    let world = World::new(params);
+    let prey = &world.prey()[0];
+    let near = Vector2::new(prey.position().x + 0.5, prey.position().y);
+
+    // Just beside an agent picks it; a tiny radius well away from it does not
+    assert_eq!(world.agent_at(near, 1.0), Some(prey.id()));
+    assert_eq!(world.agent_at(near, 0.1), None);
+}
+
+#[test]
+fn test_spawn_at_places_agent_at_position() {
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.predator.initial_count = 0;
+    params.prey.initial_count = 0;
+    let mut world = World::new(params);
+
+    let predator = world.spawn_predator_at(Vector2::new(120.0, 80.0)).expect("room for a predator");
+    let prey = world.spawn_prey_at(Vector2::new(300.0, 250.0)).expect("room for a prey");
+    assert_eq!(world.predator_count(), 1);
+    assert_eq!(world.prey_count(), 1);
+    assert_eq!(world.agent(predator).unwrap().position(), Vector2::new(120.0, 80.0));
+    assert_eq!(world.agent(prey).unwrap().position(), Vector2::new(300.0, 250.0));
+    assert_eq!(world.agent_type_of(predator), Some(AgentType::Predator));
+    assert_eq!(world.agent_type_of(prey), Some(AgentType::Prey));
+
+    // Points outside a wraparound world wrap back in
+    let width = world.parameters().world.width;
+    let wrapped = world.spawn_prey_at(Vector2::new(width + 10.0, 50.0)).unwrap();
+    assert_eq!(world.agent(wrapped).unwrap().position(), Vector2::new(10.0, 50.0));
+}
+
+#[test]
+fn test_spawn_at_respects_max_agents() {
+    use predator_prey_sim::utils::math::Vector2;
+
+    let mut params = Parameters::default();
+    params.predator.initial_count = 0;
+    params.prey.initial_count = 0;
+    params.simulation.max_agents = 2;
+    let mut world = World::new(params);
+
+    assert!(world.spawn_prey_at(Vector2::new(10.0, 10.0)).is_some());
+    assert!(world.spawn_predator_at(Vector2::new(20.0, 20.0)).is_some());
+    assert_eq!(world.spawn_prey_at(Vector2::new(30.0, 30.0)), None);
+    assert_eq!(world.spawn_predator_at(Vector2::new(40.0, 40.0)), None);
+    assert_eq!(world.total_agents(), 2);
+}